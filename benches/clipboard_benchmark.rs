@@ -1,6 +1,7 @@
 use clipmanager::clipboard::types::ClipboardItem;
-use clipmanager::storage::database::Database;
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use clipmanager::storage::database::{Database, DEFAULT_MAX_ITEMS};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
 use tempfile::NamedTempFile;
 
 fn benchmark_insert_items(c: &mut Criterion) {
@@ -11,7 +12,7 @@ fn benchmark_insert_items(c: &mut Criterion) {
         b.iter(|| {
             for i in 0..100 {
                 let item = ClipboardItem::new(format!("测试内容 {}", black_box(i)));
-                let _ = db.insert_item(&item);
+                let _ = db.insert_item(&item, DEFAULT_MAX_ITEMS);
             }
         })
     });
@@ -24,13 +25,15 @@ fn benchmark_search_items(c: &mut Criterion) {
     // 预先插入数据
     for i in 0..1000 {
         let item = ClipboardItem::new(format!("搜索测试内容 {}", i));
-        let _ = db.insert_item(&item);
+        let _ = db.insert_item(&item, DEFAULT_MAX_ITEMS);
     }
 
     c.bench_function("search_in_1000_items", |b| {
         b.iter(|| {
-            let mut filter = clipmanager::clipboard::types::SearchFilter::default();
-            filter.query = black_box("测试".to_string());
+            let filter = clipmanager::clipboard::types::SearchFilter {
+                query: black_box("测试".to_string()),
+                ..Default::default()
+            };
             let _ = db.get_items(&filter, 50, 0);
         })
     });
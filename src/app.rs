@@ -1,83 +1,911 @@
 use crate::clipboard::handler::ClipboardHandler;
-use crate::clipboard::types::{ClipboardItem, SearchFilter};
-use crate::config::settings::{AppConfig, ThemeMode};
+use crate::clipboard::private_mode::{PrivateDuration, PrivateStatus};
+use crate::clipboard::types::{ClipboardContent, ClipboardItem, SearchFilter};
+use crate::config::settings::AppConfig;
+use crate::daemon::InstanceLock;
 use crate::error::Result;
-use crate::storage::database::Database;
-use crate::ui::components::SettingsWindow;
+use crate::hotkey::{self, HotkeyEvent, HotkeyRegistration};
+use crate::i18n::{self, TextKey};
+use crate::ipc;
+use crate::lock::{AppLock, UnlockResult};
+use crate::notify::{NotificationCategory, Notifier};
+use crate::paste::{self, PasteTarget};
+use crate::snippets::template::{Expansion, Values};
+use crate::snippets::{self, FileFormat, Snippet};
+use crate::storage::database::{Database, PageCursor};
+use crate::storage::events::{ItemSummary, StorageEvent};
+use crate::transform::{command, Transform};
+use crate::ui::components::snippets_window::SnippetsAction;
+use crate::ui::components::{LockScreen, SettingsWindow, SnippetsWindow};
 use crate::ui::main_window::MainWindow;
+use crate::ui::popup::{PopupAction, PopupPicker};
 use crate::ui::theme::ThemeManager;
-use directories::ProjectDirs;
+use crate::ui::thumbnail_cache::{ThumbnailCache, DEFAULT_MEMORY_BUDGET};
 use eframe::egui;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+/// 每次从数据库加载的条目数
+const PAGE_SIZE: usize = 100;
+
+/// 检查其他进程写入的间隔
+const EXTERNAL_CHANGE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 搜索时在历史记录前显示的片段数
+const SNIPPET_MATCHES: usize = 5;
+
+/// 剪切板由谁记录
+enum Capture {
+    /// 本进程持有监控锁并记录剪切板
+    Local {
+        _lock: InstanceLock,
+        #[cfg(unix)]
+        service: crate::ipc::Service,
+        #[cfg(unix)]
+        endpoints: crate::ipc::Endpoints,
+    },
+    /// 守护进程在记录，本进程只读写数据库
+    Attached,
+}
 
 pub struct ClipManagerApp {
     clipboard_handler: ClipboardHandler,
+    capture: Capture,
     main_window: MainWindow,
+    popup: PopupPicker,
+    /// 弹出选择器显示的最近条目，与主窗口的分页列表相互独立
+    popup_items: Vec<ClipboardItem>,
+    /// 与弹出选择器搜索内容匹配的片段
+    popup_snippets: Vec<Snippet>,
+    snippets_window: SnippetsWindow,
     settings_window: SettingsWindow,
     theme_manager: ThemeManager,
     items: Vec<ClipboardItem>,
+    has_more_items: bool,
     search_filter: SearchFilter,
     error_message: Option<String>,
     config: AppConfig,
-    last_refresh: std::time::Instant,
-    last_item_count: usize,
+    storage_events: mpsc::Receiver<StorageEvent>,
     copy_feedback_message: Option<String>,
     copy_feedback_timer: std::time::Instant,
+    window_events: mpsc::Receiver<HotkeyEvent>,
+    window_event_sender: mpsc::Sender<HotkeyEvent>,
+    hotkey: Option<HotkeyRegistration>,
+    /// 唤起窗口前获得焦点的窗口，用于选择后自动粘贴
+    paste_target: Option<PasteTarget>,
+    #[cfg(target_os = "linux")]
+    tray: Option<crate::tray::Tray>,
+    #[cfg(target_os = "linux")]
+    tray_events: mpsc::Receiver<crate::tray::TrayEvent>,
+    #[cfg(target_os = "linux")]
+    tray_event_sender: mpsc::Sender<crate::tray::TrayEvent>,
+    notifier: Notifier,
+    /// 隐私模式状态，由 RecordingChanged 事件更新
+    private_status: PrivateStatus,
+    app_lock: AppLock,
+    lock_screen: LockScreen,
+    /// 桌面会话锁定时由监听线程置位
+    screen_locked: Arc<AtomicBool>,
+    /// 后台运行的用户动作返回的结果，`bool` 表示是否保存为新条目
+    action_results: mpsc::Receiver<(bool, Result<String>)>,
+    action_result_sender: mpsc::Sender<(bool, Result<String>)>,
+    /// 从托盘选择退出时为 true，此时关闭窗口真正退出程序
+    quitting: bool,
 }
 
 impl ClipManagerApp {
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Result<Self> {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Result<Self> {
         // Load configuration
         let config = AppConfig::load().map_err(|e| crate::error::ClipManagerError::Config {
             message: format!("Failed to load config: {}", e),
         })?;
 
         // Set up database path
-        let db_path = Self::get_database_path()?;
+        let db_path = crate::storage::default_database_path()?;
+        let lock = InstanceLock::acquire(&db_path)?;
         let database = Database::new(db_path)?;
 
         // Create clipboard handler
         let mut clipboard_handler = ClipboardHandler::new(database)?;
-        clipboard_handler.start_monitoring()?;
+        let storage_events =
+            Self::forward_storage_events(clipboard_handler.subscribe(), cc.egui_ctx.clone());
+        // 守护进程或命令行写入的条目通过轮询发现
+        clipboard_handler
+            .database()
+            .watch_external_changes(EXTERNAL_CHANGE_INTERVAL);
 
         // Create UI components
-        let main_window = MainWindow::new();
-        let settings_window = SettingsWindow::new(config.clone());
+        let thumbnails = ThumbnailCache::new(
+            cc.egui_ctx.clone(),
+            Some(clipboard_handler.database()),
+            DEFAULT_MEMORY_BUDGET,
+        );
+        let main_window = MainWindow::new(thumbnails, &config.keybindings, &config.actions);
+        let (action_result_sender, action_results) = mpsc::channel();
+        let popup = PopupPicker::new(&config.keybindings);
+        let mut settings_window = SettingsWindow::new(config.clone());
         let theme_manager = ThemeManager::new(&config.theme);
 
+        // 全局快捷键与控制套接字都通过同一通道唤起窗口
+        let (window_event_sender, window_events) = mpsc::channel();
+        let hotkey = Self::register_hotkey(&config.hotkey, &window_event_sender, &cc.egui_ctx);
+        settings_window.set_hotkey_backend(hotkey.backend().clone());
+
+        // 已有守护进程在记录时不再启动自己的监控
+        let capture = match lock {
+            Some(lock) => {
+                clipboard_handler.set_sensitive_config(&config.sensitive);
+                clipboard_handler.set_capture_rules(&config.capture);
+                clipboard_handler.set_expiry_config(&config.expiry);
+                clipboard_handler.set_actions(&config.actions);
+                clipboard_handler.set_private_mode_persist(config.private_mode.persist);
+                clipboard_handler.start_monitoring()?;
+                #[cfg(unix)]
+                let service = Self::control_service(
+                    &clipboard_handler,
+                    &config,
+                    window_event_sender.clone(),
+                    cc.egui_ctx.clone(),
+                );
+                Capture::Local {
+                    _lock: lock,
+                    #[cfg(unix)]
+                    endpoints: ipc::serve(&service, &config.http),
+                    #[cfg(unix)]
+                    service,
+                }
+            }
+            None => {
+                log::info!("Clipboard daemon is running, attaching to it");
+                #[cfg(unix)]
+                Self::attach_to_daemon(
+                    window_event_sender.clone(),
+                    clipboard_handler.database(),
+                    cc.egui_ctx.clone(),
+                );
+                Capture::Attached
+            }
+        };
+
+        #[cfg(target_os = "linux")]
+        let (tray_event_sender, tray_events) = mpsc::channel();
+        #[cfg(target_os = "linux")]
+        let tray = Self::start_tray(&config, &tray_event_sender, &cc.egui_ctx);
+        let notifier = Notifier::new(&config);
+        let app_lock = AppLock::new(&config.lock);
+        let screen_locked = Self::watch_screen_lock(&cc.egui_ctx);
+        let private_status = match capture {
+            Capture::Local { .. } => clipboard_handler.private_status(),
+            #[cfg(unix)]
+            Capture::Attached => ipc::Client::connect()
+                .and_then(|mut client| client.private_status())
+                .unwrap_or_default(),
+            #[cfg(not(unix))]
+            Capture::Attached => PrivateStatus::default(),
+        };
+
         let mut app = Self {
             clipboard_handler,
+            capture,
             main_window,
+            popup,
+            popup_items: Vec::new(),
+            popup_snippets: Vec::new(),
+            snippets_window: SnippetsWindow::new(),
             settings_window,
             theme_manager,
             items: Vec::new(),
+            has_more_items: false,
             search_filter: SearchFilter::default(),
             error_message: None,
             config,
-            last_refresh: std::time::Instant::now(),
-            last_item_count: 0,
+            storage_events,
             copy_feedback_message: None,
             copy_feedback_timer: std::time::Instant::now(),
+            window_events,
+            window_event_sender,
+            hotkey: Some(hotkey),
+            paste_target: None,
+            #[cfg(target_os = "linux")]
+            tray,
+            #[cfg(target_os = "linux")]
+            tray_events,
+            #[cfg(target_os = "linux")]
+            tray_event_sender,
+            notifier,
+            private_status,
+            app_lock,
+            lock_screen: LockScreen::new(),
+            screen_locked,
+            action_results,
+            action_result_sender,
+            quitting: false,
         };
 
         // Load initial data
         app.refresh_items();
+        app.refresh_snippets();
+        app.refresh_tray();
+
+        if app.config.window.start_minimized {
+            log::info!("Starting minimized");
+            let command = if app.hides_to_tray() {
+                egui::ViewportCommand::Visible(false)
+            } else {
+                egui::ViewportCommand::Minimized(true)
+            };
+            cc.egui_ctx.send_viewport_cmd(command);
+        }
 
         Ok(app)
     }
 
-    fn get_database_path() -> Result<PathBuf> {
-        let proj_dirs =
-            ProjectDirs::from("com", "clipmanager", "ClipManager").ok_or_else(|| {
-                crate::error::ClipManagerError::Config {
-                    message: "Unable to determine data directory".to_string(),
+    #[cfg(target_os = "linux")]
+    fn start_tray(
+        config: &AppConfig,
+        sender: &mpsc::Sender<crate::tray::TrayEvent>,
+        ctx: &egui::Context,
+    ) -> Option<crate::tray::Tray> {
+        if !config.tray.enabled {
+            return None;
+        }
+
+        let ctx = ctx.clone();
+        match crate::tray::Tray::start(sender.clone(), move || ctx.request_repaint()) {
+            Ok(tray) => Some(tray),
+            Err(e) => {
+                log::warn!("Tray icon unavailable, closing the window will exit: {}", e);
+                None
+            }
+        }
+    }
+
+    /// 监听桌面会话锁定，目前只支持 Linux 的 logind
+    #[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+    fn watch_screen_lock(ctx: &egui::Context) -> Arc<AtomicBool> {
+        let screen_locked = Arc::new(AtomicBool::new(false));
+        #[cfg(target_os = "linux")]
+        {
+            let flag = Arc::clone(&screen_locked);
+            let ctx = ctx.clone();
+            crate::lock::logind::watch_system(move || {
+                flag.store(true, Ordering::Relaxed);
+                ctx.request_repaint();
+            });
+        }
+        screen_locked
+    }
+
+    /// 锁定窗口；记录剪切板不受影响
+    fn lock_window(&mut self, reason: &str) {
+        if self.app_lock.lock(&self.config.lock, reason) {
+            self.on_locked();
+        }
+    }
+
+    fn on_locked(&mut self) {
+        self.lock_screen.reset();
+        self.main_window.on_locked();
+        self.popup.close();
+        self.settings_window.open = false;
+        self.snippets_window.close();
+        self.refresh_tray();
+    }
+
+    /// 有托盘图标时关闭窗口只隐藏到托盘
+    fn hides_to_tray(&self) -> bool {
+        #[cfg(target_os = "linux")]
+        {
+            self.tray.is_some()
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            false
+        }
+    }
+
+    /// 把最近条目和暂停状态同步到托盘菜单
+    fn refresh_tray(&mut self) {
+        #[cfg(target_os = "linux")]
+        if let Some(tray) = &self.tray {
+            // 锁定时托盘菜单不显示最近条目
+            let limit = if self.app_lock.is_locked() {
+                0
+            } else {
+                self.config.tray.recent_items
+            };
+            let recent = match self.clipboard_handler.search_items_page(
+                &SearchFilter::default(),
+                None,
+                limit,
+            ) {
+                Ok(items) => items,
+                Err(e) => {
+                    log::warn!("Failed to load recent items for tray: {}", e);
+                    return;
+                }
+            };
+            let recent = recent
+                .iter()
+                .filter_map(|item| {
+                    let summary = match &item.content {
+                        ClipboardContent::Text(text) => {
+                            crate::sensitive::safe_preview(item.sensitive.as_deref(), text, 40)
+                        }
+                        ClipboardContent::Image(image) => {
+                            format!("🖼️ {} × {}", image.width, image.height)
+                        }
+                    };
+                    item.id.map(|id| (id, summary))
+                })
+                .collect();
+            tray.update(recent, self.private_status.paused);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn handle_tray_event(&mut self, event: crate::tray::TrayEvent, ctx: &egui::Context) {
+        use crate::tray::TrayEvent;
+
+        match event {
+            TrayEvent::CopyItem(id) => {
+                self.copy_item_by_id(id);
+            }
+            TrayEvent::TogglePause => {
+                let duration =
+                    (!self.private_status.paused).then_some(PrivateDuration::UntilResumed);
+                self.set_private_mode(duration);
+            }
+            TrayEvent::Pause(duration) => self.set_private_mode(Some(duration)),
+            TrayEvent::OpenWindow => {
+                self.handle_window_event(HotkeyEvent::Show { pointer: None }, ctx);
+            }
+            TrayEvent::OpenSettings => {
+                self.handle_window_event(HotkeyEvent::Show { pointer: None }, ctx);
+                self.settings_window.open();
+            }
+            TrayEvent::Quit => {
+                log::info!("Quit requested from tray");
+                self.quitting = true;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+        }
+    }
+
+    fn register_hotkey(
+        hotkey: &str,
+        sender: &mpsc::Sender<HotkeyEvent>,
+        ctx: &egui::Context,
+    ) -> HotkeyRegistration {
+        let ctx = ctx.clone();
+        hotkey::register(hotkey, sender.clone(), move || ctx.request_repaint())
+    }
+
+    /// 本进程记录剪切板时对外提供的服务，窗口请求转为快捷键事件
+    #[cfg(unix)]
+    fn control_service(
+        handler: &ClipboardHandler,
+        config: &AppConfig,
+        sender: mpsc::Sender<HotkeyEvent>,
+        ctx: egui::Context,
+    ) -> crate::ipc::Service {
+        let window: crate::ipc::server::WindowHandler = Box::new(move |mode| {
+            let _ = sender.send(Self::window_event(mode));
+            ctx.request_repaint();
+        });
+        let service =
+            crate::ipc::Service::new(handler.database(), handler.monitor_control(), Some(window));
+        service.set_limits(config.max_items, config.max_item_size);
+        service.set_capture_filter(handler.capture_filter());
+        service
+    }
+
+    /// 附加到守护进程时订阅其窗口请求、条目变化和隐私模式变化，连接断开后不再重连
+    ///
+    /// 条目事件按本地事件增量应用，轮询只用来发现命令行等其他进程的写入。
+    #[cfg(unix)]
+    fn attach_to_daemon(
+        sender: mpsc::Sender<HotkeyEvent>,
+        database: std::sync::Arc<Database>,
+        ctx: egui::Context,
+    ) {
+        let mut client = match ipc::Client::connect().and_then(|mut client| {
+            client.subscribe(true)?;
+            Ok(client)
+        }) {
+            Ok(client) => client,
+            Err(e) => {
+                log::warn!("Failed to subscribe to clipboard daemon: {}", e);
+                return;
+            }
+        };
+
+        std::thread::spawn(move || {
+            while let Ok(event) = client.next_event() {
+                match event {
+                    ipc::protocol::Event::Window { mode } => {
+                        if sender.send(Self::window_event(mode)).is_err() {
+                            break;
+                        }
+                        ctx.request_repaint();
+                    }
+                    // 守护进程的隐私模式变化按本地事件处理
+                    ipc::protocol::Event::RecordingPaused {
+                        paused,
+                        resume_at,
+                        until_restart,
+                    } => database.publish(StorageEvent::RecordingChanged(PrivateStatus {
+                        paused,
+                        resume_at,
+                        until_restart,
+                    })),
+                    ipc::protocol::Event::ItemAdded { item } => {
+                        database.acknowledge_external_changes();
+                        // 事件中没有内容哈希，从数据库读取条目生成本地摘要
+                        match item.id.map(|id| (id, database.get_item(id))) {
+                            Some((id, Ok(Some(item)))) => database
+                                .publish(StorageEvent::ItemAdded(ItemSummary::new(id, &item))),
+                            Some((_, Ok(None))) | None => {}
+                            Some((_, Err(e))) => {
+                                log::warn!("Failed to load item from daemon: {}", e);
+                                database.publish(StorageEvent::ExternalChange);
+                            }
+                        }
+                    }
+                    ipc::protocol::Event::ItemUpdated { id } => {
+                        database.acknowledge_external_changes();
+                        database.publish(StorageEvent::ItemUpdated(id));
+                    }
+                    ipc::protocol::Event::ItemDeleted { id } => {
+                        database.acknowledge_external_changes();
+                        database.publish(StorageEvent::ItemDeleted(id));
+                    }
+                    ipc::protocol::Event::Cleared => {
+                        database.acknowledge_external_changes();
+                        database.publish(StorageEvent::Cleared(Vec::new()));
+                    }
+                    ipc::protocol::Event::Changed => database.publish(StorageEvent::ExternalChange),
+                }
+            }
+            log::info!("Clipboard daemon connection closed");
+        });
+    }
+
+    #[cfg(unix)]
+    fn window_event(mode: ipc::protocol::WindowMode) -> HotkeyEvent {
+        use ipc::protocol::WindowMode;
+
+        let pointer = hotkey::pointer_position();
+        match mode {
+            WindowMode::Toggle => HotkeyEvent::Toggle { pointer },
+            WindowMode::Show => HotkeyEvent::Show { pointer },
+            WindowMode::Popup => HotkeyEvent::Popup { pointer },
+        }
+    }
+
+    /// 响应全局快捷键或 `clipmanager toggle` 命令
+    fn handle_window_event(&mut self, event: HotkeyEvent, ctx: &egui::Context) {
+        let (show, popup, pointer) = match event {
+            HotkeyEvent::Toggle { pointer } if self.config.popup.enabled => {
+                (!self.popup.is_open(), true, pointer)
+            }
+            HotkeyEvent::Toggle { pointer } => {
+                let visible = ctx.input(|i| {
+                    let viewport = i.viewport();
+                    viewport.focused.unwrap_or(false) && !viewport.minimized.unwrap_or(false)
+                });
+                (!visible, false, pointer)
+            }
+            HotkeyEvent::Show { pointer } => (true, false, pointer),
+            HotkeyEvent::Popup { pointer } => (true, true, pointer),
+        };
+        // 锁定时改为显示主窗口的锁定界面
+        let popup = popup && !self.app_lock.is_locked();
+
+        if !show {
+            log::debug!("Hiding window via hotkey");
+            if popup {
+                self.popup.close();
+            } else {
+                self.hide_main_window(ctx);
+            }
+            return;
+        }
+
+        log::debug!("Showing window via hotkey at {:?}", pointer);
+        if self.config.paste.paste_on_select {
+            // 必须在本窗口获得焦点之前记录
+            self.paste_target = paste::capture_target();
+            log::debug!("Paste target: {:?}", self.paste_target);
+        }
+
+        // 鼠标坐标是物理像素，窗口位置使用逻辑坐标
+        let (scale, monitor) = ctx.input(|i| {
+            let viewport = i.viewport();
+            (
+                viewport.native_pixels_per_point.unwrap_or(1.0),
+                viewport.monitor_size,
+            )
+        });
+        let position = pointer.map(|(x, y)| egui::pos2(x as f32 / scale, y as f32 / scale));
+
+        if popup {
+            // 无法获取其他程序的文本光标位置，弹出在鼠标附近
+            let position = position
+                .unwrap_or_else(|| monitor.map_or(egui::Pos2::ZERO, |size| (size / 3.0).to_pos2()));
+            self.popup.open_at(position, monitor, &self.config.popup);
+            self.refresh_popup_items();
+            return;
+        }
+
+        ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+        if let Some(position) = position {
+            ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(position));
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        self.main_window
+            .focus_search(ctx, &self.search_filter.query);
+    }
+
+    /// 有托盘时隐藏到托盘，否则最小化
+    fn hide_main_window(&self, ctx: &egui::Context) {
+        if self.hides_to_tray() {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+        } else {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+        }
+    }
+
+    fn refresh_popup_items(&mut self) {
+        let filter = SearchFilter {
+            query: self.popup.query().to_string(),
+            ..Default::default()
+        };
+        match self
+            .clipboard_handler
+            .search_items_page(&filter, None, self.config.popup.max_items)
+        {
+            Ok(items) => self.popup_items = items,
+            Err(e) => log::error!("Failed to load popup items: {}", e),
+        }
+        self.popup_snippets = self.matching_snippets(self.popup.query());
+    }
+
+    /// 搜索内容匹配的片段，搜索为空时返回空列表
+    fn matching_snippets(&self, query: &str) -> Vec<Snippet> {
+        if query.trim().is_empty() {
+            return Vec::new();
+        }
+        self.clipboard_handler
+            .search_snippets(query.trim(), SNIPPET_MATCHES)
+            .unwrap_or_else(|e| {
+                log::error!("Failed to search snippets: {}", e);
+                Vec::new()
+            })
+    }
+
+    /// 片段变化后刷新搜索结果和管理窗口
+    fn refresh_snippets(&mut self) {
+        self.main_window
+            .set_snippets(self.matching_snippets(&self.search_filter.query));
+        if self.popup.is_open() {
+            self.popup_snippets = self.matching_snippets(self.popup.query());
+        }
+        if self.snippets_window.is_open() {
+            match self.clipboard_handler.search_snippets("", usize::MAX) {
+                Ok(all) => self.snippets_window.set_snippets(all),
+                Err(e) => log::error!("Failed to load snippets: {}", e),
+            }
+        }
+    }
+
+    /// 开启隐私模式，`None` 恢复记录；状态变化通过 RecordingChanged 事件回到界面
+    fn set_private_mode(&mut self, duration: Option<PrivateDuration>) {
+        match &self.capture {
+            Capture::Local { .. } => {
+                self.private_status = self.clipboard_handler.set_private_mode(duration);
+            }
+            #[cfg(unix)]
+            Capture::Attached => {
+                let result = ipc::Client::connect().and_then(|mut client| match duration {
+                    Some(duration) => client.pause_for(duration).map(|_| ()),
+                    None => client.resume(),
+                });
+                if let Err(e) = result {
+                    log::error!("Failed to reach clipboard daemon: {}", e);
+                    self.error_message = Some(format!("Failed to reach clipboard daemon: {}", e));
+                }
+            }
+            #[cfg(not(unix))]
+            Capture::Attached => {}
+        }
+    }
+
+    fn apply_private_status(&mut self, status: PrivateStatus) {
+        self.private_status = status;
+        let summary = if status.paused {
+            TextKey::NotifyRecordingPaused
+        } else {
+            TextKey::NotifyRecordingResumed
+        };
+        let body = match status.resume_at {
+            _ if !status.paused => String::new(),
+            Some(resume_at) => format!(
+                "{} {}",
+                i18n::t(TextKey::PrivateModeResumesAt),
+                resume_at.with_timezone(&chrono::Local).format("%H:%M")
+            ),
+            None if status.until_restart => i18n::t(TextKey::PrivateModeUntilRestart).to_string(),
+            None => String::new(),
+        };
+        self.notifier.notify(
+            NotificationCategory::RecordingPaused,
+            i18n::t(summary),
+            &body,
+        );
+    }
+
+    /// 按 ID 将条目放回系统剪切板并记录访问，成功返回 true
+    fn copy_item_by_id(&mut self, id: i64) -> bool {
+        let item = match self
+            .items
+            .iter()
+            .chain(&self.popup_items)
+            .find(|item| item.id == Some(id))
+        {
+            Some(item) => Some(item.clone()),
+            None => self.clipboard_handler.get_item(id).ok().flatten(),
+        };
+        let Some(item) = item else {
+            return false;
+        };
+
+        if let Err(e) = self.clipboard_handler.copy_item(&item) {
+            log::error!("Failed to copy item {}: {}", id, e);
+            self.error_message = Some(format!("Failed to copy: {}", e));
+            return false;
+        }
+        if let Err(e) = self.clipboard_handler.update_item_access(id) {
+            log::warn!("Failed to update access record: {}", e);
+        }
+        self.notifier.notify(
+            NotificationCategory::ItemCopied,
+            i18n::t(TextKey::NotifyItemCopied),
+            &item.safe_preview(80),
+        );
+        true
+    }
+
+    /// 复制选中的条目，隐藏窗口，并按配置粘贴到之前的窗口
+    fn pick_item(&mut self, id: i64, ctx: &egui::Context, hide_main_window: bool) {
+        if !self.copy_item_by_id(id) {
+            return;
+        }
+        if hide_main_window {
+            log::info!("Item {} copied via keyboard, hiding window", id);
+            self.hide_main_window(ctx);
+        } else {
+            log::info!("Item {} picked from popup", id);
+        }
+
+        if !self.config.paste.paste_on_select {
+            return;
+        }
+        let Some(target) = self.paste_target.take() else {
+            log::info!("No window to paste into, skipping auto-paste");
+            return;
+        };
+        let paste_config = self.config.paste.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = paste::paste_into(&target, &paste_config) {
+                log::error!("Auto-paste skipped: {}", e);
+            }
+        });
+    }
+
+    /// 展开片段的占位符，`{clipboard}` 使用系统剪切板上的文本，读取失败时使用最近的文本条目
+    fn expand_snippet(&mut self, id: i64, fields: &HashMap<String, String>) -> Option<Expansion> {
+        let snippet = match self.clipboard_handler.get_snippet(id) {
+            Ok(Some(snippet)) => snippet,
+            Ok(None) => return None,
+            Err(e) => {
+                log::error!("Failed to load snippet {}: {}", id, e);
+                self.error_message = Some(format!("Failed to load snippet: {}", e));
+                return None;
+            }
+        };
+        let clipboard = match self.clipboard_handler.clipboard_text() {
+            Ok(text) => text,
+            Err(e) => {
+                log::debug!(
+                    "Clipboard text unavailable ({}), using newest history item",
+                    e
+                );
+                self.newest_text_item()
+            }
+        };
+        Some(snippet.template().expand(&Values {
+            now: chrono::Local::now(),
+            clipboard: &clipboard,
+            fields,
+        }))
+    }
+
+    /// 历史中最近的文本条目
+    fn newest_text_item(&self) -> String {
+        self.clipboard_handler
+            .search_items_page(
+                &SearchFilter {
+                    content_type: Some(crate::clipboard::types::ContentType::Text),
+                    ..Default::default()
+                },
+                None,
+                1,
+            )
+            .ok()
+            .and_then(|items| items.into_iter().next())
+            .and_then(|item| item.get_text_content().map(str::to_string))
+            .unwrap_or_default()
+    }
+
+    /// 展开片段并复制结果，`paste` 时按配置粘贴并把光标移到 `{cursor}`
+    fn use_snippet(&mut self, id: i64, fields: &HashMap<String, String>, paste: bool) {
+        let Some(expansion) = self.expand_snippet(id, fields) else {
+            return;
+        };
+        if let Err(e) = self.clipboard_handler.copy_to_clipboard(&expansion.text) {
+            log::error!("Failed to copy snippet {}: {}", id, e);
+            self.error_message = Some(format!("Failed to copy: {}", e));
+            return;
+        }
+        log::info!("Snippet {} expanded and copied", id);
+        self.copy_feedback_message = Some("Copied!".to_string());
+        self.copy_feedback_timer = std::time::Instant::now();
+
+        if !paste || !self.config.paste.paste_on_select {
+            return;
+        }
+        let Some(target) = self.paste_target.take() else {
+            log::info!("No window to paste into, skipping auto-paste");
+            return;
+        };
+        let paste_config = self.config.paste.clone();
+        let left = expansion.chars_after_cursor();
+        std::thread::spawn(move || {
+            let result = paste::paste_into(&target, &paste_config)
+                .and_then(|_| paste::move_cursor_left(left, &paste_config));
+            if let Err(e) = result {
+                log::error!("Auto-paste skipped: {}", e);
+            }
+        });
+    }
+
+    fn handle_snippets_action(&mut self, action: SnippetsAction) {
+        match action {
+            SnippetsAction::Save(snippet) => match self.clipboard_handler.save_snippet(&snippet) {
+                Ok(id) => self.snippets_window.on_saved(id),
+                Err(e) => self.snippets_window.set_message(Err(e.to_string())),
+            },
+            SnippetsAction::Delete(id) => match self.clipboard_handler.delete_snippet(id) {
+                Ok(()) => self.snippets_window.on_deleted(),
+                Err(e) => self.snippets_window.set_message(Err(e.to_string())),
+            },
+            SnippetsAction::Import(path) => {
+                let result = std::fs::read_to_string(&path)
+                    .map_err(Into::into)
+                    .and_then(|content| snippets::import(&content, FileFormat::from_path(&path)))
+                    .and_then(|snippets| self.clipboard_handler.import_snippets(&snippets));
+                let message = result
+                    .map(|(inserted, updated)| {
+                        format!(
+                            "Imported {} snippets ({} replaced)",
+                            inserted + updated,
+                            updated
+                        )
+                    })
+                    .map_err(|e| e.to_string());
+                self.snippets_window.set_message(message);
+            }
+            SnippetsAction::Export(path) => {
+                let result = self
+                    .clipboard_handler
+                    .search_snippets("", usize::MAX)
+                    .and_then(|all| {
+                        let content = snippets::export(&all, FileFormat::from_path(&path))?;
+                        std::fs::write(&path, content)?;
+                        Ok(all.len())
+                    });
+                let message = result
+                    .map(|count| format!("Exported {} snippets to {}", count, path.display()))
+                    .map_err(|e| e.to_string());
+                self.snippets_window.set_message(message);
+            }
+        }
+    }
+
+    /// 转发存储事件，并在新数据到达时唤醒 UI 重绘
+    fn forward_storage_events(
+        receiver: mpsc::Receiver<StorageEvent>,
+        ctx: egui::Context,
+    ) -> mpsc::Receiver<StorageEvent> {
+        let (sender, forwarded) = mpsc::channel();
+        std::thread::spawn(move || {
+            while let Ok(event) = receiver.recv() {
+                if sender.send(event).is_err() {
+                    break;
                 }
-            })?;
+                ctx.request_repaint();
+            }
+            log::debug!("Storage event forwarder exiting");
+        });
+        forwarded
+    }
 
-        let data_dir = proj_dirs.data_dir();
-        std::fs::create_dir_all(data_dir)?;
+    /// 增量应用存储变更，避免整表重新加载
+    fn apply_storage_event(&mut self, event: StorageEvent) {
+        match event {
+            // 事件只带摘要，新增与修改都从数据库读取完整条目
+            StorageEvent::ItemAdded(ItemSummary { id, .. }) | StorageEvent::ItemUpdated(id) => {
+                match self.clipboard_handler.get_item(id) {
+                    Ok(Some(item)) => {
+                        self.items.retain(|existing| existing.id != Some(id));
+                        if self.search_filter.matches(&item) {
+                            self.insert_item_sorted(item);
+                        }
+                    }
+                    Ok(None) => self.items.retain(|existing| existing.id != Some(id)),
+                    Err(e) => log::warn!("Failed to reload item {}: {}", id, e),
+                }
+            }
+            StorageEvent::ItemDeleted(id) => {
+                self.items.retain(|existing| existing.id != Some(id));
+            }
+            StorageEvent::Cleared(_) => self.items.clear(),
+            // 条目已通过 ItemDeleted 移除，清除系统剪切板由监控负责
+            StorageEvent::ContentExpired(_) => {}
+            StorageEvent::ExternalChange => {
+                self.refresh_items();
+                self.refresh_snippets();
+            }
+            StorageEvent::SnippetsChanged => self.refresh_snippets(),
+            StorageEvent::WriteFailed(message) => {
+                self.notifier.notify(
+                    NotificationCategory::StorageFailed,
+                    i18n::t(TextKey::NotifyStorageFailed),
+                    &message,
+                );
+                self.error_message = Some(format!("Failed to save clipboard item: {}", message));
+            }
+            StorageEvent::CaptureSkipped(label) => {
+                self.notifier.notify(
+                    NotificationCategory::SensitiveSkipped,
+                    i18n::t(TextKey::NotifySensitiveSkipped),
+                    &label,
+                );
+            }
+            StorageEvent::CaptureBlocked { rule, app } => {
+                self.settings_window.record_blocked(rule, app);
+            }
+            StorageEvent::RecordingChanged(status) => self.apply_private_status(status),
+        }
+    }
 
-        Ok(data_dir.join("clipmanager.db"))
+    fn insert_item_sorted(&mut self, item: ClipboardItem) {
+        // 与数据库的排序保持一致：created_at DESC, id DESC
+        let key = (item.created_at, item.id);
+        let position = self
+            .items
+            .iter()
+            .position(|existing| (existing.created_at, existing.id) < key)
+            .unwrap_or(self.items.len());
+        // 超出已加载范围的条目交给后续分页加载
+        if position < self.items.len() || !self.has_more_items {
+            self.items.insert(position, item);
+        }
     }
 
     fn refresh_items(&mut self) {
@@ -87,19 +915,17 @@ impl ClipManagerApp {
             self.search_filter.favorites_only
         );
 
+        // 重新加载与当前已加载数量相同的条目，保持滚动位置
+        let limit = self.items.len().max(PAGE_SIZE);
         match self
             .clipboard_handler
-            .search_items(&self.search_filter, self.config.max_items, 0)
+            .search_items_page(&self.search_filter, None, limit)
         {
             Ok(items) => {
                 log::info!("Loaded {} items from database", items.len());
+                self.has_more_items = items.len() == limit;
                 self.items = items;
                 self.error_message = None;
-
-                // Update item count
-                if let Ok(count) = self.clipboard_handler.get_item_count() {
-                    self.last_item_count = count;
-                }
             }
             Err(e) => {
                 log::error!("Failed to load items: {}", e);
@@ -108,7 +934,78 @@ impl ClipManagerApp {
         }
     }
 
-    fn apply_config_changes(&mut self) {
+    fn load_more_items(&mut self) {
+        if !self.has_more_items {
+            return;
+        }
+
+        let cursor = self.items.last().and_then(PageCursor::after);
+        match self.clipboard_handler.search_items_page(
+            &self.search_filter,
+            cursor.as_ref(),
+            PAGE_SIZE,
+        ) {
+            Ok(page) => {
+                log::debug!("Loaded next page of {} items", page.len());
+                self.has_more_items = page.len() == PAGE_SIZE;
+                self.items.extend(page);
+            }
+            Err(e) => {
+                log::error!("Failed to load more items: {}", e);
+                self.has_more_items = false;
+                self.error_message = Some(format!("Failed to load data: {}", e));
+            }
+        }
+    }
+
+    fn apply_config_changes(&mut self, ctx: &egui::Context, hotkey_changed: bool) {
+        self.main_window
+            .set_key_bindings(&self.config.keybindings, &self.config.actions);
+        self.popup.set_key_bindings(&self.config.keybindings);
+        self.notifier.set_config(&self.config);
+        self.app_lock.apply_config(&self.config.lock);
+        self.clipboard_handler
+            .set_sensitive_config(&self.config.sensitive);
+        self.clipboard_handler
+            .set_capture_rules(&self.config.capture);
+        self.clipboard_handler
+            .set_expiry_config(&self.config.expiry);
+        self.clipboard_handler.set_actions(&self.config.actions);
+        if let Capture::Local { .. } = self.capture {
+            self.clipboard_handler
+                .set_private_mode_persist(self.config.private_mode.persist);
+        }
+        #[cfg(unix)]
+        if let Capture::Local {
+            service, endpoints, ..
+        } = &mut self.capture
+        {
+            service.set_limits(self.config.max_items, self.config.max_item_size);
+            endpoints.apply_http_config(&self.config.http);
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(
+            if self.config.window.always_on_top {
+                egui::WindowLevel::AlwaysOnTop
+            } else {
+                egui::WindowLevel::Normal
+            },
+        ));
+
+        // 快捷键变化时先释放旧的抓取，否则新注册会与自己冲突
+        if hotkey_changed {
+            self.hotkey = None;
+            let hotkey = Self::register_hotkey(&self.config.hotkey, &self.window_event_sender, ctx);
+            self.settings_window
+                .set_hotkey_backend(hotkey.backend().clone());
+            self.hotkey = Some(hotkey);
+        }
+
+        #[cfg(target_os = "linux")]
+        if self.config.tray.enabled != self.tray.is_some() {
+            self.tray = Self::start_tray(&self.config, &self.tray_event_sender, ctx);
+        }
+        self.refresh_tray();
+
         // Apply max items limit
         if let Err(e) = self
             .clipboard_handler
@@ -121,6 +1018,68 @@ impl ClipManagerApp {
         self.refresh_items();
     }
 
+    fn transform_item(&mut self, id: i64, transform: Transform, save: bool) {
+        let Some(text) = self
+            .items
+            .iter()
+            .find(|item| item.id == Some(id))
+            .and_then(|item| item.get_text_content())
+        else {
+            return;
+        };
+        let result = match transform.apply(text) {
+            Ok(result) => result,
+            Err(e) => {
+                log::warn!("Transform {:?} failed on item {}: {}", transform, id, e);
+                self.error_message = Some(format!("{}: {}", i18n::t(transform.text_key()), e));
+                return;
+            }
+        };
+
+        self.deliver_result(result, save);
+    }
+
+    /// 在后台线程中对文本条目运行用户动作，避免命令阻塞界面
+    fn run_action(&mut self, id: i64, index: usize, save: bool, ctx: &egui::Context) {
+        let Some(action) = self.config.actions.actions.get(index).cloned() else {
+            return;
+        };
+        let Some(text) = self
+            .items
+            .iter()
+            .find(|item| item.id == Some(id))
+            .and_then(|item| item.get_text_content())
+            .map(str::to_string)
+        else {
+            return;
+        };
+        log::info!("Running action '{}' on item {}", action.name, id);
+        let sender = self.action_result_sender.clone();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let _ = sender.send((save, command::run(&action, &text)));
+            ctx.request_repaint();
+        });
+    }
+
+    /// 复制转换结果，或保存为新条目
+    fn deliver_result(&mut self, result: String, save: bool) {
+        let outcome = if save {
+            self.clipboard_handler.add_text(result).map(|_| "Saved!")
+        } else {
+            self.clipboard_handler
+                .copy_to_clipboard(&result)
+                .map(|_| "Copied!")
+        };
+        match outcome {
+            Ok(feedback) => {
+                self.copy_feedback_message = Some(feedback.to_string());
+                self.copy_feedback_timer = std::time::Instant::now();
+            }
+            Err(e) => self.error_message = Some(format!("Failed to apply transform: {}", e)),
+        }
+    }
+
     fn handle_item_action(&mut self, action: ItemAction, ctx: &egui::Context) {
         match action {
             ItemAction::Copy(content) => {
@@ -131,15 +1090,11 @@ impl ClipManagerApp {
             ItemAction::Delete(id) => {
                 if let Err(e) = self.clipboard_handler.delete_item(id) {
                     self.error_message = Some(format!("Failed to delete: {}", e));
-                } else {
-                    self.refresh_items();
                 }
             }
             ItemAction::ClearAll => {
                 if let Err(e) = self.clipboard_handler.clear_all_items() {
                     self.error_message = Some(format!("Failed to clear: {}", e));
-                } else {
-                    self.refresh_items();
                 }
             }
             ItemAction::UpdateAccess(id) => {
@@ -156,14 +1111,37 @@ impl ClipManagerApp {
                         .update_favorite(id, new_favorite_state)
                     {
                         self.error_message = Some(format!("Failed to update favorite: {}", e));
-                    } else {
-                        self.refresh_items();
                     }
                 }
             }
             ItemAction::OpenSettings => {
                 self.settings_window.open();
             }
+            ItemAction::LoadMore => {
+                self.load_more_items();
+            }
+            ItemAction::CopyAndClose(id) => self.pick_item(id, ctx, true),
+            ItemAction::SetPrivateMode(duration) => self.set_private_mode(duration),
+            ItemAction::Lock => self.lock_window("locked manually"),
+            ItemAction::Transform {
+                id,
+                transform,
+                save,
+            } => self.transform_item(id, transform, save),
+            ItemAction::RunAction { id, index, save } => self.run_action(id, index, save, ctx),
+            ItemAction::UseSnippet(id, fields) => self.use_snippet(id, &fields, false),
+            ItemAction::OpenSnippets => {
+                match self.clipboard_handler.search_snippets("", usize::MAX) {
+                    Ok(all) => self.snippets_window.open(all),
+                    Err(e) => self.error_message = Some(format!("Failed to load snippets: {}", e)),
+                }
+            }
+            ItemAction::SetExpiry(id, ttl) => {
+                if let Err(e) = self.clipboard_handler.set_item_expiry(id, ttl) {
+                    log::error!("Failed to set expiry of item {}: {}", id, e);
+                    self.error_message = Some(format!("Failed to set expiry: {}", e));
+                }
+            }
             ItemAction::ToggleTheme => {
                 let new_theme = self.theme_manager.toggle_theme(ctx);
                 log::info!("Theme toggled to: {:?}", new_theme);
@@ -175,19 +1153,6 @@ impl ClipManagerApp {
                     self.error_message = Some(format!("Failed to save theme: {}", e));
                 }
             }
-            ItemAction::DoubleClickCopy(content) => {
-                if let Err(e) = self.clipboard_handler.copy_to_clipboard(&content) {
-                    log::error!("Failed to copy content on double-click: {}", e);
-                    self.error_message = Some(format!("Failed to copy: {}", e));
-                } else {
-                    log::info!(
-                        "Content copied via double-click: {} characters",
-                        content.len()
-                    );
-                    self.copy_feedback_message = Some("Copied!".to_string());
-                    self.copy_feedback_timer = std::time::Instant::now();
-                }
-            }
         }
     }
 }
@@ -201,7 +1166,29 @@ pub enum ItemAction {
     ToggleFavorite(i64),
     OpenSettings,
     ToggleTheme,
-    DoubleClickCopy(String),
+    LoadMore,
+    CopyAndClose(i64),
+    /// 开启隐私模式，`None` 恢复记录
+    SetPrivateMode(Option<PrivateDuration>),
+    /// 条目在指定时间后过期，`None` 取消过期
+    SetExpiry(i64, Option<chrono::Duration>),
+    /// 立即锁定窗口
+    Lock,
+    /// 转换文本条目，`save` 时保存为新条目，否则复制结果
+    Transform {
+        id: i64,
+        transform: Transform,
+        save: bool,
+    },
+    /// 对文本条目运行配置中的第 `index` 个用户动作
+    RunAction {
+        id: i64,
+        index: usize,
+        save: bool,
+    },
+    /// 展开片段并复制，附带用户填写的字段
+    UseSnippet(i64, HashMap<String, String>),
+    OpenSnippets,
 }
 
 impl eframe::App for ClipManagerApp {
@@ -209,13 +1196,92 @@ impl eframe::App for ClipManagerApp {
         // Apply theme
         self.theme_manager.apply_theme(ctx);
 
+        // Apply storage changes pushed by the writer
+        let mut storage_changed = false;
+        while let Ok(event) = self.storage_events.try_recv() {
+            self.apply_storage_event(event);
+            storage_changed = true;
+        }
+        while let Ok((save, result)) = self.action_results.try_recv() {
+            match result {
+                Ok(output) => self.deliver_result(output, save),
+                Err(e) => {
+                    log::warn!("{}", e);
+                    self.error_message = Some(e.to_string());
+                }
+            }
+        }
+        if storage_changed {
+            if self.popup.is_open() {
+                self.refresh_popup_items();
+            }
+            self.refresh_tray();
+        }
+
+        #[cfg(target_os = "linux")]
+        while let Ok(event) = self.tray_events.try_recv() {
+            self.handle_tray_event(event, ctx);
+        }
+
+        // 有托盘图标时，关闭窗口只是隐藏，剪切板监控继续运行
+        if ctx.input(|i| i.viewport().close_requested()) && self.hides_to_tray() && !self.quitting {
+            log::info!("Window closed, hiding to tray");
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+        }
+
+        while let Ok(event) = self.window_events.try_recv() {
+            self.handle_window_event(event, ctx);
+        }
+
+        // 用户操作重新开始空闲计时，空闲超时或屏幕锁定时锁定窗口
+        let now = Instant::now();
+        if ctx.input(|i| !i.events.is_empty()) && self.app_lock.touch(&self.config.lock, now) {
+            self.on_locked();
+        }
+        if self.screen_locked.swap(false, Ordering::Relaxed) && self.config.lock.lock_with_screen {
+            self.lock_window("screen locked");
+        }
+        if self.app_lock.check_idle(&self.config.lock, now) {
+            self.on_locked();
+        }
+        if self.app_lock.is_locked() {
+            match self.app_lock.poll_unlock() {
+                Some(UnlockResult::Unlocked) => {
+                    self.refresh_tray();
+                    self.main_window
+                        .focus_search(ctx, &self.search_filter.query);
+                    return;
+                }
+                Some(UnlockResult::WrongPassphrase) => self.lock_screen.set_failed(),
+                Some(UnlockResult::Misconfigured(message)) => self.lock_screen.set_error(message),
+                None => {}
+            }
+            self.lock_screen.set_verifying(self.app_lock.is_verifying());
+            if let Some(passphrase) = self.lock_screen.show(ctx) {
+                let ctx = ctx.clone();
+                self.app_lock
+                    .start_unlock(&self.config.lock, passphrase, move || ctx.request_repaint());
+            }
+            return;
+        }
+        if let Some(deadline) = self.app_lock.idle_deadline(&self.config.lock) {
+            ctx.request_repaint_after(deadline.saturating_duration_since(now));
+        }
+
         // Handle search filter changes
         let old_filter = self.search_filter.clone();
 
         // Render main window
+        self.main_window
+            .set_skipped_count(self.clipboard_handler.skipped_count());
+        self.main_window.set_private_status(self.private_status);
+        self.main_window
+            .set_lock_enabled(self.config.lock.is_enabled());
         if let Some(action) = self.main_window.show(
             ctx,
-            &mut self.items,
+            &self.items,
+            self.has_more_items,
             &mut self.search_filter,
             &self.error_message,
             &self.copy_feedback_message,
@@ -223,14 +1289,35 @@ impl eframe::App for ClipManagerApp {
             self.handle_item_action(action, ctx);
         }
 
+        // Render popup picker
+        match self.popup.show(
+            ctx,
+            &self.popup_items,
+            &self.popup_snippets,
+            &self.config.popup,
+        ) {
+            Some(PopupAction::Pick(id)) => self.pick_item(id, ctx, false),
+            Some(PopupAction::PickSnippet(id, fields)) => {
+                log::info!("Snippet {} picked from popup", id);
+                self.use_snippet(id, &fields, true);
+            }
+            Some(PopupAction::QueryChanged) => self.refresh_popup_items(),
+            Some(PopupAction::Closed) | None => {}
+        }
+
+        if let Some(action) = self.snippets_window.show(ctx) {
+            self.handle_snippets_action(action);
+        }
+
         // Render settings window
         if let Some(new_config) = self.settings_window.show(ctx) {
+            let hotkey_changed = new_config.hotkey != self.config.hotkey;
             self.config = new_config;
             if let Err(e) = self.config.save() {
                 self.error_message = Some(format!("Failed to save config: {}", e));
             } else {
                 // Apply configuration changes
-                self.apply_config_changes();
+                self.apply_config_changes(ctx, hotkey_changed);
             }
         }
 
@@ -241,43 +1328,23 @@ impl eframe::App for ClipManagerApp {
         {
             self.refresh_items();
         }
-
-        // Auto-refresh mechanism: check for new items every 2 seconds
-        let now = std::time::Instant::now();
-        if now.duration_since(self.last_refresh) >= std::time::Duration::from_secs(2) {
-            log::debug!("Performing periodic refresh check");
-
-            // Check if item count has changed
-            match self.clipboard_handler.get_item_count() {
-                Ok(current_count) => {
-                    if current_count != self.last_item_count {
-                        log::info!(
-                            "Item count changed from {} to {}, refreshing UI",
-                            self.last_item_count,
-                            current_count
-                        );
-                        self.refresh_items();
-                        self.last_item_count = current_count;
-                    }
-                }
-                Err(e) => {
-                    log::warn!("Failed to get item count: {}", e);
-                }
-            }
-
-            self.last_refresh = now;
+        if self.search_filter.query != old_filter.query {
+            self.main_window
+                .set_snippets(self.matching_snippets(&self.search_filter.query));
         }
 
         // Clear copy feedback after 2 seconds
-        if self.copy_feedback_message.is_some() {
-            if self.copy_feedback_timer.elapsed() >= std::time::Duration::from_secs(2) {
-                self.copy_feedback_message = None;
-                log::debug!("Copy feedback message cleared");
-            }
+        if self.copy_feedback_message.is_some()
+            && self.copy_feedback_timer.elapsed() >= std::time::Duration::from_secs(2)
+        {
+            self.copy_feedback_message = None;
+            log::debug!("Copy feedback message cleared");
         }
 
-        // Request repaint more frequently for better responsiveness
-        ctx.request_repaint_after(std::time::Duration::from_millis(500));
+        // New data wakes the UI via request_repaint; only the feedback timer needs polling
+        if self.copy_feedback_message.is_some() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(500));
+        }
     }
 
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
@@ -0,0 +1,1093 @@
+//! Command-line interface
+//!
+//! `clipmanager <subcommand>` reads and edits the history from shell scripts
+//! and launcher pipelines such as dmenu or rofi. All commands go through the
+//! same [`Database`] and [`ClipboardHandler`] APIs as the GUI; without a
+//! subcommand the GUI starts. `clipmanager snippet <subcommand>` manages the
+//! snippet library (see [`crate::snippets`]).
+
+use crate::clipboard::handler::ClipboardHandler;
+use crate::clipboard::private_mode::PrivateDuration;
+use crate::clipboard::types::{ClipboardContent, ClipboardItem, ContentType, SearchFilter};
+use crate::config::settings::AppConfig;
+use crate::error::{ClipManagerError, Result};
+use crate::ipc::protocol::ItemInfo;
+use crate::ipc::{self, ControlCommand};
+use crate::snippets::template::Values;
+use crate::snippets::{self, FileFormat, Snippet};
+use crate::storage::database::BatchOutcome;
+use crate::storage::writer::CaptureFilter;
+use crate::storage::{self, Database};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// 导出文件格式版本
+const EXPORT_VERSION: u32 = 1;
+
+/// 列表中每个条目摘要的最大字符数
+const PREVIEW_CHARS: usize = 80;
+
+pub fn command() -> Command {
+    Command::new("clipmanager")
+        .about("Clipboard history manager")
+        .version(env!("CARGO_PKG_VERSION"))
+        .arg(
+            Arg::new("database")
+                .long("database")
+                .global(true)
+                .value_name("PATH")
+                .value_parser(value_parser!(PathBuf))
+                .help("History database to use instead of the default one"),
+        )
+        .subcommand(
+            Command::new("daemon").about("Record the clipboard in the background without a window"),
+        )
+        .subcommand(Command::new("toggle").about("Show or hide the window of the running instance"))
+        .subcommand(Command::new("show").about("Show the window of the running instance"))
+        .subcommand(Command::new("popup").about("Open the popup picker of the running instance"))
+        .subcommand(
+            Command::new("pause")
+                .about("Pause clipboard recording (private mode)")
+                .arg(
+                    Arg::new("for")
+                        .long("for")
+                        .value_name("DURATION")
+                        .value_parser(PrivateDuration::parse)
+                        .help("Resume automatically after DURATION, e.g. 5m or 1h"),
+                )
+                .arg(
+                    Arg::new("until-restart")
+                        .long("until-restart")
+                        .action(ArgAction::SetTrue)
+                        .conflicts_with("for")
+                        .help("Resume when ClipManager restarts, even if the state is persisted"),
+                ),
+        )
+        .subcommand(Command::new("resume").about("Resume clipboard recording"))
+        .subcommand(Command::new("status").about("Print whether recording is paused"))
+        .subcommand(
+            filter_args(Command::new("list").about("List history items, newest first")).arg(
+                Arg::new("query")
+                    .long("query")
+                    .short('q')
+                    .help("Only items whose text contains QUERY"),
+            ),
+        )
+        .subcommand(
+            filter_args(Command::new("search").about("Search the text of history items"))
+                .arg(Arg::new("query").required(true).help("Text to search for")),
+        )
+        .subcommand(
+            Command::new("get")
+                .about("Write the raw content of an item to stdout (images as PNG)")
+                .arg(id_arg()),
+        )
+        .subcommand(
+            Command::new("copy")
+                .about("Put an item back on the system clipboard")
+                .arg(id_arg())
+                .arg(
+                    Arg::new("foreground")
+                        .long("foreground")
+                        .action(ArgAction::SetTrue)
+                        .help("Stay in the foreground until the clipboard is replaced"),
+                ),
+        )
+        .subcommand(
+            Command::new("add")
+                .about("Add an item read from stdin")
+                .arg(
+                    Arg::new("mime")
+                        .long("mime")
+                        .short('m')
+                        .default_value("text/plain")
+                        .help("MIME type of the input, e.g. text/plain or image/png"),
+                ),
+        )
+        .subcommand(
+            Command::new("delete").about("Delete items").arg(
+                Arg::new("ids")
+                    .value_name("ID")
+                    .required(true)
+                    .num_args(1..)
+                    .value_parser(value_parser!(i64)),
+            ),
+        )
+        .subcommand(
+            Command::new("favorite")
+                .about("Mark an item as favorite")
+                .arg(id_arg())
+                .arg(
+                    Arg::new("off")
+                        .long("off")
+                        .action(ArgAction::SetTrue)
+                        .help("Remove the favorite mark instead"),
+                ),
+        )
+        .subcommand(
+            Command::new("clear").about("Delete all history items").arg(
+                Arg::new("yes")
+                    .long("yes")
+                    .action(ArgAction::SetTrue)
+                    .help("Confirm deleting everything"),
+            ),
+        )
+        .subcommand(
+            Command::new("export")
+                .about("Export the history as JSON")
+                .arg(file_arg("File to write, stdout if omitted")),
+        )
+        .subcommand(
+            Command::new("import")
+                .about("Import items from a JSON export")
+                .arg(file_arg("File to read, stdin if omitted")),
+        )
+        .subcommand(
+            Command::new("stats")
+                .about("Show history statistics")
+                .arg(json_arg()),
+        )
+        .subcommand(snippet_command())
+}
+
+fn snippet_command() -> Command {
+    Command::new("snippet")
+        .about("Manage and expand snippets")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("list")
+                .about("List snippets, optionally matching QUERY")
+                .arg(Arg::new("query").help("Text to search in names, abbreviations and bodies"))
+                .arg(json_arg()),
+        )
+        .subcommand(
+            Command::new("show")
+                .about("Write the body of a snippet to stdout")
+                .arg(snippet_key_arg()),
+        )
+        .subcommand(
+            Command::new("expand")
+                .about("Expand the placeholders of a snippet and write the result to stdout")
+                .long_about(
+                    "Expand the placeholders of a snippet and write the result to stdout.\n\n\
+                     Fields not given with --set are asked for on stderr and read from stdin; \
+                     an empty answer keeps the field's default.",
+                )
+                .arg(snippet_key_arg())
+                .arg(
+                    Arg::new("set")
+                        .long("set")
+                        .short('s')
+                        .value_name("FIELD=VALUE")
+                        .action(ArgAction::Append)
+                        .value_parser(parse_field)
+                        .help("Value of a {field:...} placeholder"),
+                ),
+        )
+        .subcommand(
+            Command::new("add")
+                .about("Add a snippet whose body is read from stdin")
+                .arg(Arg::new("name").required(true).help("Snippet name"))
+                .arg(
+                    Arg::new("abbreviation")
+                        .long("abbreviation")
+                        .short('a')
+                        .help("Short text that finds the snippet"),
+                )
+                .arg(
+                    Arg::new("folder")
+                        .long("folder")
+                        .short('f')
+                        .help("Folder to put the snippet in"),
+                ),
+        )
+        .subcommand(
+            Command::new("delete")
+                .about("Delete a snippet")
+                .arg(snippet_key_arg()),
+        )
+        .subcommand(
+            Command::new("export")
+                .about("Export all snippets as TOML or JSON")
+                .arg(file_arg("File to write, stdout if omitted"))
+                .arg(format_arg()),
+        )
+        .subcommand(
+            Command::new("import")
+                .about("Import snippets, replacing ones with the same folder and name")
+                .arg(file_arg("File to read, stdin if omitted"))
+                .arg(format_arg()),
+        )
+}
+
+fn snippet_key_arg() -> Arg {
+    Arg::new("snippet")
+        .required(true)
+        .help("Abbreviation, name, folder/name or ID of the snippet")
+}
+
+fn format_arg() -> Arg {
+    Arg::new("format")
+        .long("format")
+        .value_parser(["toml", "json"])
+        .help("File format; defaults to the file extension, JSON for stdin/stdout")
+}
+
+fn parse_field(value: &str) -> std::result::Result<(String, String), String> {
+    value
+        .split_once('=')
+        .map(|(name, value)| (name.trim().to_string(), value.to_string()))
+        .ok_or_else(|| format!("'{}' is not FIELD=VALUE", value))
+}
+
+fn id_arg() -> Arg {
+    Arg::new("id")
+        .required(true)
+        .value_parser(value_parser!(i64))
+}
+
+fn json_arg() -> Arg {
+    Arg::new("json")
+        .long("json")
+        .action(ArgAction::SetTrue)
+        .help("Print JSON")
+}
+
+fn file_arg(help: &'static str) -> Arg {
+    Arg::new("file")
+        .value_parser(value_parser!(PathBuf))
+        .help(help)
+}
+
+/// `list` 和 `search` 共用的 [`SearchFilter`] 选项
+fn filter_args(command: Command) -> Command {
+    command
+        .arg(
+            Arg::new("type")
+                .long("type")
+                .short('t')
+                .value_parser(["text", "image"])
+                .help("Only items of this type"),
+        )
+        .arg(
+            Arg::new("favorites")
+                .long("favorites")
+                .short('f')
+                .action(ArgAction::SetTrue)
+                .help("Only favorite items"),
+        )
+        .arg(
+            Arg::new("since")
+                .long("since")
+                .value_name("DATE")
+                .value_parser(parse_since)
+                .help("Only items copied at or after DATE (YYYY-MM-DD or RFC 3339)"),
+        )
+        .arg(
+            Arg::new("until")
+                .long("until")
+                .value_name("DATE")
+                .value_parser(parse_until)
+                .help("Only items copied on or before DATE (YYYY-MM-DD or RFC 3339)"),
+        )
+        .arg(
+            Arg::new("limit")
+                .long("limit")
+                .short('n')
+                .default_value("100")
+                .value_parser(value_parser!(usize))
+                .help("Maximum number of items, 0 for all"),
+        )
+        .arg(json_arg())
+}
+
+fn parse_date(value: &str, end_of_day: bool) -> std::result::Result<DateTime<Utc>, String> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("'{}' is not a date (YYYY-MM-DD or RFC 3339)", value))?;
+    // 只有日期时按本地时间解释，结束日期包含当天
+    let date = if end_of_day {
+        date.succ_opt().unwrap_or(date)
+    } else {
+        date
+    };
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|time| time.and_local_timezone(Local).earliest())
+        .map(|time| time.with_timezone(&Utc))
+        .ok_or_else(|| format!("'{}' is not a valid local date", value))
+}
+
+fn parse_since(value: &str) -> std::result::Result<DateTime<Utc>, String> {
+    parse_date(value, false)
+}
+
+fn parse_until(value: &str) -> std::result::Result<DateTime<Utc>, String> {
+    parse_date(value, true)
+}
+
+/// 命令行指定的数据库，未指定时使用默认位置
+pub fn database_path(matches: &ArgMatches) -> Result<PathBuf> {
+    match matches.get_one::<PathBuf>("database") {
+        Some(path) => Ok(path.clone()),
+        None => storage::default_database_path(),
+    }
+}
+
+/// 运行子命令，返回进程退出码
+pub fn run(matches: &ArgMatches) -> i32 {
+    let Some((name, args)) = matches.subcommand() else {
+        return 0;
+    };
+
+    let result = match ControlCommand::parse(name) {
+        Some(ControlCommand::Pause(_)) => control(ControlCommand::Pause(pause_duration(args))),
+        Some(command) => control(command),
+        None => database_path(matches).and_then(|db_path| {
+            let config = AppConfig::load().unwrap_or_default();
+            let database = Database::new(&db_path)?;
+            if name == "copy" {
+                return copy(args, &db_path, database);
+            }
+            let context = Context {
+                database: &database,
+                config: &config,
+                db_path: &db_path,
+            };
+            execute(
+                name,
+                args,
+                &context,
+                &mut std::io::stdin().lock(),
+                &mut std::io::stdout().lock(),
+            )
+        }),
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("clipmanager {}: {}", name, e);
+            1
+        }
+    }
+}
+
+fn pause_duration(args: &ArgMatches) -> PrivateDuration {
+    match args.get_one::<PrivateDuration>("for") {
+        Some(duration) => *duration,
+        None if args.get_flag("until-restart") => PrivateDuration::UntilRestart,
+        None => PrivateDuration::UntilResumed,
+    }
+}
+
+/// 向正在运行的实例发送控制命令并打印回复
+fn control(command: ControlCommand) -> Result<()> {
+    let reply = ipc::send_command(command).map_err(|e| ClipManagerError::Config {
+        message: format!("No running ClipManager instance: {}", e),
+    })?;
+    if !reply.is_empty() {
+        println!("{}", reply);
+    }
+    Ok(())
+}
+
+struct Context<'a> {
+    database: &'a Database,
+    config: &'a AppConfig,
+    db_path: &'a Path,
+}
+
+fn execute(
+    name: &str,
+    args: &ArgMatches,
+    context: &Context<'_>,
+    input: &mut dyn Read,
+    output: &mut dyn Write,
+) -> Result<()> {
+    let database = context.database;
+    match name {
+        "list" | "search" => {
+            let filter = search_filter(args);
+            let limit = match *args.get_one::<usize>("limit").unwrap_or(&0) {
+                0 => i64::MAX as usize,
+                limit => limit,
+            };
+            let items = database.get_items(&filter, limit, 0)?;
+            if args.get_flag("json") {
+                let items: Vec<ItemInfo> = items.iter().map(ItemInfo::from).collect();
+                serde_json::to_writer_pretty(&mut *output, &items)?;
+                writeln!(output)?;
+            } else {
+                for item in &items {
+                    writeln!(
+                        output,
+                        "{}\t{}",
+                        item.id.unwrap_or_default(),
+                        one_line(item)
+                    )?;
+                }
+            }
+        }
+        "get" => {
+            let item = find_item(database, id(args))?;
+            match &item.content {
+                ClipboardContent::Text(text) => output.write_all(text.as_bytes())?,
+                ClipboardContent::Image(image) => output.write_all(&image.to_png()?)?,
+            }
+        }
+        "add" => {
+            let mime = args
+                .get_one::<String>("mime")
+                .map(String::as_str)
+                .unwrap_or("text/plain");
+            let mut data = Vec::new();
+            input.read_to_end(&mut data)?;
+            let item = ClipboardItem::from_bytes(data, mime, context.config.max_item_size)?;
+            let item = CaptureFilter::from_config(context.config)
+                .screen_item(database, item)
+                .map_err(|reason| ClipManagerError::Config {
+                    message: format!("Not saved: {}", reason),
+                })?;
+            let id = match database.insert_batch(&[item], context.config.max_items)?[0] {
+                BatchOutcome::Inserted(id) | BatchOutcome::Bumped(id) => id,
+            };
+            writeln!(output, "{}", id)?;
+        }
+        "delete" => {
+            let mut missing = Vec::new();
+            for &id in args.get_many::<i64>("ids").into_iter().flatten() {
+                if database.get_item(id)?.is_some() {
+                    database.delete_item(id)?;
+                } else {
+                    missing.push(id.to_string());
+                }
+            }
+            if !missing.is_empty() {
+                return Err(ClipManagerError::Config {
+                    message: format!("No item with id {}", missing.join(", ")),
+                });
+            }
+        }
+        "favorite" => {
+            let id = id(args);
+            find_item(database, id)?;
+            database.update_favorite(id, !args.get_flag("off"))?;
+        }
+        "clear" => {
+            if !args.get_flag("yes") {
+                return Err(ClipManagerError::Config {
+                    message: "Refusing to delete the whole history without --yes".to_string(),
+                });
+            }
+            database.clear_all()?;
+        }
+        "export" => {
+            let export = ExportFile {
+                version: EXPORT_VERSION,
+                exported_at: Utc::now(),
+                items: database.get_items(&SearchFilter::default(), i64::MAX as usize, 0)?,
+            };
+            match args.get_one::<PathBuf>("file") {
+                Some(path) => {
+                    let file = std::fs::File::create(path)?;
+                    serde_json::to_writer_pretty(std::io::BufWriter::new(file), &export)?;
+                    eprintln!(
+                        "Exported {} items to {}",
+                        export.items.len(),
+                        path.display()
+                    );
+                }
+                None => {
+                    serde_json::to_writer_pretty(&mut *output, &export)?;
+                    writeln!(output)?;
+                }
+            }
+        }
+        "import" => {
+            let export: ExportFile = match args.get_one::<PathBuf>("file") {
+                Some(path) => {
+                    serde_json::from_reader(std::io::BufReader::new(std::fs::File::open(path)?))?
+                }
+                None => serde_json::from_reader(input)?,
+            };
+            if export.version > EXPORT_VERSION {
+                return Err(ClipManagerError::Config {
+                    message: format!("Unsupported export version {}", export.version),
+                });
+            }
+
+            // 导出文件按时间倒序，按原顺序逆序写入以保持相对先后
+            let mut items = export.items;
+            items.reverse();
+            for item in &mut items {
+                item.id = None;
+            }
+            let total = items.len();
+            let items = CaptureFilter::from_config(context.config).screen(database, items);
+            let skipped = total - items.len();
+            let outcomes = database.insert_batch(&items, context.config.max_items)?;
+            let inserted = outcomes
+                .iter()
+                .filter(|outcome| matches!(outcome, BatchOutcome::Inserted(_)))
+                .count();
+            write!(
+                output,
+                "Imported {} items ({} already present",
+                inserted,
+                outcomes.len() - inserted
+            )?;
+            if skipped > 0 {
+                write!(output, ", {} skipped", skipped)?;
+            }
+            writeln!(output, ")")?;
+        }
+        "stats" => {
+            let stats = database.get_stats()?;
+            let file_size = std::fs::metadata(context.db_path)
+                .map(|metadata| metadata.len())
+                .unwrap_or(0);
+            if args.get_flag("json") {
+                let json = serde_json::json!({
+                    "database": context.db_path,
+                    "database_size": file_size,
+                    "items": stats,
+                });
+                serde_json::to_writer_pretty(&mut *output, &json)?;
+                writeln!(output)?;
+            } else {
+                let format_date = |date: Option<DateTime<Utc>>| {
+                    date.map(|date| {
+                        date.with_timezone(&Local)
+                            .format("%Y-%m-%d %H:%M:%S")
+                            .to_string()
+                    })
+                    .unwrap_or_else(|| "-".to_string())
+                };
+                writeln!(
+                    output,
+                    "Items:     {} ({} text, {} images, {} favorites)",
+                    stats.total_items, stats.text_items, stats.image_items, stats.favorite_items
+                )?;
+                writeln!(output, "Content:   {}", format_size(stats.total_size))?;
+                writeln!(output, "Oldest:    {}", format_date(stats.oldest))?;
+                writeln!(output, "Newest:    {}", format_date(stats.newest))?;
+                writeln!(
+                    output,
+                    "Database:  {} ({})",
+                    context.db_path.display(),
+                    format_size(file_size)
+                )?;
+            }
+        }
+        "snippet" => {
+            let (name, args) = args.subcommand().expect("snippet requires a subcommand");
+            snippet(name, args, context, input, output)?;
+        }
+        other => {
+            return Err(ClipManagerError::Config {
+                message: format!("Unknown command '{}'", other),
+            })
+        }
+    }
+    Ok(())
+}
+
+/// `snippet` 的子命令
+fn snippet(
+    name: &str,
+    args: &ArgMatches,
+    context: &Context<'_>,
+    input: &mut dyn Read,
+    output: &mut dyn Write,
+) -> Result<()> {
+    let database = context.database;
+    match name {
+        "list" => {
+            let query = args.get_one::<String>("query").map_or("", String::as_str);
+            let snippets = database.get_snippets(query, i64::MAX as usize)?;
+            if args.get_flag("json") {
+                let json: Vec<_> = snippets
+                    .iter()
+                    .map(|snippet| {
+                        serde_json::json!({
+                            "id": snippet.id,
+                            "name": snippet.name,
+                            "abbreviation": snippet.abbreviation,
+                            "folder": snippet.folder,
+                            "body": snippet.body,
+                        })
+                    })
+                    .collect();
+                serde_json::to_writer_pretty(&mut *output, &json)?;
+                writeln!(output)?;
+            } else {
+                for snippet in &snippets {
+                    writeln!(
+                        output,
+                        "{}\t{}\t{}",
+                        snippet.id.unwrap_or_default(),
+                        snippet.abbreviation,
+                        snippet.path()
+                    )?;
+                }
+            }
+        }
+        "show" => {
+            let snippet = find_snippet(database, args)?;
+            output.write_all(snippet.body.as_bytes())?;
+        }
+        "expand" => {
+            let snippet = find_snippet(database, args)?;
+            let mut fields: HashMap<String, String> = args
+                .get_many::<(String, String)>("set")
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect();
+
+            // 未通过 --set 指定的字段逐行询问
+            let template = snippet.template();
+            let mut answers = BufReader::new(input);
+            for field in template.fields() {
+                if fields.contains_key(&field.name) {
+                    continue;
+                }
+                if field.default.is_empty() {
+                    eprint!("{}: ", field.name);
+                } else {
+                    eprint!("{} [{}]: ", field.name, field.default);
+                }
+                let mut answer = String::new();
+                answers.read_line(&mut answer)?;
+                let answer = answer.trim_end_matches(['\r', '\n']);
+                if !answer.is_empty() {
+                    fields.insert(field.name, answer.to_string());
+                }
+            }
+
+            let clipboard = current_clipboard(database);
+            let expansion = template.expand(&Values {
+                now: Local::now(),
+                clipboard: &clipboard,
+                fields: &fields,
+            });
+            output.write_all(expansion.text.as_bytes())?;
+        }
+        "add" => {
+            let mut body = String::new();
+            input.read_to_string(&mut body)?;
+            let mut snippet =
+                Snippet::new(args.get_one::<String>("name").expect("required"), &body);
+            snippet.abbreviation = args
+                .get_one::<String>("abbreviation")
+                .cloned()
+                .unwrap_or_default();
+            snippet.folder = args
+                .get_one::<String>("folder")
+                .cloned()
+                .unwrap_or_default();
+            snippet
+                .validate()
+                .map_err(|message| ClipManagerError::Config { message })?;
+            writeln!(output, "{}", database.insert_snippet(&snippet)?)?;
+        }
+        "delete" => {
+            let snippet = find_snippet(database, args)?;
+            database.delete_snippet(snippet.id.expect("stored snippets have an id"))?;
+        }
+        "export" => {
+            let path = args.get_one::<PathBuf>("file");
+            let format = file_format(args, path);
+            let exported =
+                snippets::export(&database.get_snippets("", i64::MAX as usize)?, format)?;
+            match path {
+                Some(path) => std::fs::write(path, exported)?,
+                None => output.write_all(exported.as_bytes())?,
+            }
+        }
+        "import" => {
+            let path = args.get_one::<PathBuf>("file");
+            let content = match path {
+                Some(path) => std::fs::read_to_string(path)?,
+                None => {
+                    let mut content = String::new();
+                    input.read_to_string(&mut content)?;
+                    content
+                }
+            };
+            let imported = snippets::import(&content, file_format(args, path))?;
+            let (inserted, updated) = database.import_snippets(&imported)?;
+            writeln!(
+                output,
+                "Imported {} snippets ({} replaced)",
+                inserted + updated,
+                updated
+            )?;
+        }
+        other => {
+            return Err(ClipManagerError::Config {
+                message: format!("Unknown command 'snippet {}'", other),
+            })
+        }
+    }
+    Ok(())
+}
+
+fn find_snippet(database: &Database, args: &ArgMatches) -> Result<Snippet> {
+    let key = args
+        .get_one::<String>("snippet")
+        .expect("snippet is required");
+    let snippet = match key.parse::<i64>() {
+        Ok(id) => database.get_snippet(id)?,
+        Err(_) => database.find_snippet(key)?,
+    };
+    snippet.ok_or_else(|| ClipManagerError::Config {
+        message: format!("No snippet '{}'", key),
+    })
+}
+
+fn file_format(args: &ArgMatches, path: Option<&PathBuf>) -> FileFormat {
+    match args.get_one::<String>("format").map(String::as_str) {
+        Some("toml") => FileFormat::Toml,
+        Some(_) => FileFormat::Json,
+        None => path.map_or(FileFormat::Json, |path| FileFormat::from_path(path)),
+    }
+}
+
+/// `{clipboard}` 的内容：系统剪切板中的文本，无法读取时使用最新的文本条目
+fn current_clipboard(database: &Database) -> String {
+    if let Ok(text) = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_text()) {
+        return text;
+    }
+    let filter = SearchFilter {
+        content_type: Some(ContentType::Text),
+        ..SearchFilter::default()
+    };
+    database
+        .get_items(&filter, 1, 0)
+        .ok()
+        .and_then(|items| items.into_iter().next())
+        .and_then(|item| item.get_text_content().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// 复制条目到系统剪切板
+///
+/// 优先交给正在记录同一数据库的实例。Linux 上剪切板内容由写入进程提供，
+/// 没有运行中的实例时默认在后台启动一个子进程持有剪切板，命令本身立即返回。
+fn copy(args: &ArgMatches, db_path: &Path, database: Database) -> Result<()> {
+    let id = id(args);
+    let item = find_item(&database, id)?;
+
+    #[cfg(unix)]
+    if !args.get_flag("foreground")
+        && storage::default_database_path().is_ok_and(|path| path == db_path)
+    {
+        if let Ok(mut client) = ipc::Client::connect() {
+            return client.copy(id);
+        }
+    }
+
+    if cfg!(target_os = "linux") && !args.get_flag("foreground") {
+        std::process::Command::new(std::env::current_exe()?)
+            .arg("--database")
+            .arg(db_path)
+            .args(["copy", &id.to_string(), "--foreground"])
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()?;
+        return Ok(());
+    }
+
+    database.update_access(id)?;
+    let handler = ClipboardHandler::new(database)?;
+    handler.copy_item_and_wait(&item)
+}
+
+fn id(args: &ArgMatches) -> i64 {
+    *args.get_one::<i64>("id").expect("id is required")
+}
+
+fn find_item(database: &Database, id: i64) -> Result<ClipboardItem> {
+    database
+        .get_item(id)?
+        .ok_or_else(|| ClipManagerError::Config {
+            message: format!("No item with id {}", id),
+        })
+}
+
+fn search_filter(args: &ArgMatches) -> SearchFilter {
+    let since = args.get_one::<DateTime<Utc>>("since").copied();
+    let until = args.get_one::<DateTime<Utc>>("until").copied();
+    SearchFilter {
+        query: args.get_one::<String>("query").cloned().unwrap_or_default(),
+        content_type: match args.get_one::<String>("type").map(String::as_str) {
+            Some("text") => Some(ContentType::Text),
+            Some("image") => Some(ContentType::Image),
+            _ => None,
+        },
+        favorites_only: args.get_flag("favorites"),
+        date_range: (since.is_some() || until.is_some()).then(|| {
+            (
+                since.unwrap_or(DateTime::<Utc>::MIN_UTC),
+                until.unwrap_or_else(Utc::now),
+            )
+        }),
+    }
+}
+
+/// 单行摘要，便于 dmenu/rofi 按行选择
+fn one_line(item: &ClipboardItem) -> String {
+    match &item.content {
+        ClipboardContent::Text(text) => {
+            let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            if line.chars().count() > PREVIEW_CHARS {
+                format!("{}…", line.chars().take(PREVIEW_CHARS).collect::<String>())
+            } else {
+                line
+            }
+        }
+        ClipboardContent::Image(image) => format!("[image {}x{}]", image.width, image.height),
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// `export` 写出、`import` 读入的文件
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportFile {
+    version: u32,
+    exported_at: DateTime<Utc>,
+    items: Vec<ClipboardItem>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard::types::{ImageData, ImageFormat};
+
+    fn run_with(database: &Database, args: &[&str], input: &[u8]) -> Result<String> {
+        let matches = command()
+            .try_get_matches_from(std::iter::once("clipmanager").chain(args.iter().copied()))
+            .unwrap();
+        let (name, sub) = matches.subcommand().unwrap();
+        let context = Context {
+            database,
+            config: &AppConfig::default(),
+            db_path: Path::new(":memory:"),
+        };
+        let mut output = Vec::new();
+        execute(name, sub, &context, &mut &input[..], &mut output)?;
+        Ok(String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_add_list_get() {
+        let db = Database::new(":memory:").unwrap();
+        let id = run_with(&db, &["add"], b"hello\nworld").unwrap();
+        run_with(&db, &["add"], b"another").unwrap();
+        run_with(&db, &["favorite", id.trim()], b"").unwrap();
+
+        let listed = run_with(&db, &["list"], b"").unwrap();
+        assert_eq!(listed.lines().count(), 2);
+        assert!(listed.contains(&format!("{}\thello world", id.trim())));
+
+        let json = run_with(&db, &["search", "hello", "--favorites", "--json"], b"").unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 1);
+        assert_eq!(json[0]["text"], "hello\nworld");
+        assert_eq!(json[0]["favorite"], true);
+
+        assert_eq!(
+            run_with(&db, &["get", id.trim()], b"").unwrap(),
+            "hello\nworld"
+        );
+        assert!(run_with(&db, &["get", "999"], b"").is_err());
+    }
+
+    #[test]
+    fn test_add_image_and_get_png() {
+        let db = Database::new(":memory:").unwrap();
+        let mut png = Vec::new();
+        image::RgbaImage::new(3, 2)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let id = run_with(&db, &["add", "--mime", "image/png"], &png).unwrap();
+        let item = db.get_item(id.trim().parse().unwrap()).unwrap().unwrap();
+        assert_eq!(item.get_image_data().unwrap().width, 3);
+        assert!(run_with(&db, &["add", "--mime", "image/png"], b"not an image").is_err());
+        assert!(run_with(&db, &["add", "--mime", "application/pdf"], b"x").is_err());
+
+        // 原始 RGBA 数据也能导出为 PNG
+        let raw = ImageData::new(vec![255; 3 * 2 * 4], ImageFormat::Bmp, 3, 2);
+        let encoded = raw.to_png().unwrap();
+        assert_eq!(image::load_from_memory(&encoded).unwrap().width(), 3);
+    }
+
+    #[test]
+    fn test_export_import_roundtrip() {
+        let db = Database::new(":memory:").unwrap();
+        run_with(&db, &["add"], b"first").unwrap();
+        run_with(&db, &["add"], b"second").unwrap();
+        let exported = run_with(&db, &["export"], b"").unwrap();
+
+        let other = Database::new(":memory:").unwrap();
+        run_with(&other, &["add"], b"first").unwrap();
+        let report = run_with(&other, &["import"], exported.as_bytes()).unwrap();
+        assert_eq!(report.trim(), "Imported 1 items (1 already present)");
+        assert_eq!(other.get_item_count().unwrap(), 2);
+
+        assert!(run_with(&other, &["clear"], b"").is_err());
+        run_with(&other, &["clear", "--yes"], b"").unwrap();
+        assert_eq!(other.get_item_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_snippet_commands() {
+        let db = Database::new(":memory:").unwrap();
+        let id = run_with(
+            &db,
+            &["snippet", "add", "thanks", "-a", "ty", "-f", "mail"],
+            b"Dear {field:Name},\nthanks for {field:Topic=your message}.{cursor}",
+        )
+        .unwrap();
+        run_with(&db, &["snippet", "add", "sig"], b"Ann").unwrap();
+        assert!(run_with(&db, &["snippet", "add", "a/b"], b"x").is_err());
+
+        let listed = run_with(&db, &["snippet", "list"], b"").unwrap();
+        // 顶层片段在前，之后按文件夹和名称排序
+        assert_eq!(
+            listed,
+            format!("2\t\tsig\n{}\tty\tmail/thanks\n", id.trim())
+        );
+        let json = run_with(&db, &["snippet", "list", "Ann", "--json"], b"").unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json[0]["name"], "sig");
+
+        // 未指定的字段从标准输入读取，空行使用默认值
+        assert_eq!(
+            run_with(&db, &["snippet", "expand", "ty"], b"Bob\n\n").unwrap(),
+            "Dear Bob,\nthanks for your message."
+        );
+        assert_eq!(
+            run_with(
+                &db,
+                &[
+                    "snippet",
+                    "expand",
+                    "mail/thanks",
+                    "--set",
+                    "Name=Eve",
+                    "-s",
+                    "Topic=the cake"
+                ],
+                b""
+            )
+            .unwrap(),
+            "Dear Eve,\nthanks for the cake."
+        );
+        assert_eq!(
+            run_with(&db, &["snippet", "show", "2"], b"").unwrap(),
+            "Ann"
+        );
+        assert!(run_with(&db, &["snippet", "show", "missing"], b"").is_err());
+
+        run_with(&db, &["snippet", "delete", "sig"], b"").unwrap();
+        assert!(run_with(&db, &["snippet", "show", "sig"], b"").is_err());
+        // 片段不会出现在历史记录中
+        assert_eq!(db.get_item_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_snippet_export_import() {
+        let db = Database::new(":memory:").unwrap();
+        run_with(&db, &["snippet", "add", "sig", "-f", "mail"], b"Ann").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snippets.toml");
+        let path = path.to_str().unwrap();
+        run_with(&db, &["snippet", "export", path], b"").unwrap();
+        assert!(std::fs::read_to_string(path)
+            .unwrap()
+            .contains("[[snippets]]"));
+
+        let other = Database::new(":memory:").unwrap();
+        let report = run_with(&other, &["snippet", "import", path], b"").unwrap();
+        assert_eq!(report.trim(), "Imported 1 snippets (0 replaced)");
+        let json = run_with(&other, &["snippet", "export"], b"").unwrap();
+        let report = run_with(&other, &["snippet", "import"], json.as_bytes()).unwrap();
+        assert_eq!(report.trim(), "Imported 1 snippets (1 replaced)");
+        assert_eq!(
+            run_with(&other, &["snippet", "show", "mail/sig"], b"").unwrap(),
+            "Ann"
+        );
+    }
+
+    #[test]
+    fn test_filter_options() {
+        let matches = command()
+            .try_get_matches_from([
+                "clipmanager",
+                "list",
+                "--type",
+                "image",
+                "--since",
+                "2024-01-01",
+                "--until",
+                "2024-01-31T12:00:00Z",
+            ])
+            .unwrap();
+        let filter = search_filter(matches.subcommand_matches("list").unwrap());
+        assert_eq!(filter.content_type, Some(ContentType::Image));
+        let (since, until) = filter.date_range.unwrap();
+        assert!(since < until);
+        assert_eq!(until.to_rfc3339(), "2024-01-31T12:00:00+00:00");
+
+        assert!(command()
+            .try_get_matches_from(["clipmanager", "list", "--since", "yesterday"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_pause_options() {
+        let duration = |args: &[&str]| {
+            let matches = command()
+                .try_get_matches_from(["clipmanager", "pause"].iter().chain(args))
+                .unwrap();
+            pause_duration(matches.subcommand_matches("pause").unwrap())
+        };
+        assert_eq!(duration(&[]), PrivateDuration::UntilResumed);
+        assert_eq!(duration(&["--for", "1h"]), PrivateDuration::minutes(60));
+        assert_eq!(
+            duration(&["--until-restart"]),
+            PrivateDuration::UntilRestart
+        );
+
+        assert!(command()
+            .try_get_matches_from(["clipmanager", "pause", "--for", "soon"])
+            .is_err());
+        assert!(command()
+            .try_get_matches_from(["clipmanager", "pause", "--for", "5m", "--until-restart"])
+            .is_err());
+    }
+}
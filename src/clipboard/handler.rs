@@ -1,25 +1,83 @@
 use crate::clipboard::monitor::ClipboardMonitor;
-use crate::clipboard::types::{ClipboardItem, SearchFilter};
-use crate::error::Result;
-use crate::storage::database::Database;
+use crate::clipboard::private_mode::{PrivateDuration, PrivateMode, PrivateStatus};
+use crate::clipboard::rules::CaptureRules;
+use crate::clipboard::types::{ClipboardContent, ClipboardItem, ContentType, SearchFilter};
+use crate::config::actions::ActionsConfig;
+use crate::config::capture::CaptureConfig;
+use crate::config::sensitive::SensitiveConfig;
+use crate::config::settings::ExpiryConfig;
+use crate::error::{ClipManagerError, Result};
+use crate::sensitive::SensitiveDetector;
+use crate::snippets::Snippet;
+use crate::storage::database::{BatchOutcome, Database, PageCursor, DEFAULT_MAX_ITEMS};
+use crate::storage::events::StorageEvent;
+use crate::storage::writer::{CaptureFilter, StorageWriter};
+use crate::transform::command::{self, AutoActions};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::mpsc;
-use std::sync::{Arc, Mutex};
-use std::thread;
+use std::sync::{Arc, Mutex, RwLock};
+
+/// 记住最近自动生成的内容数量，避免结果再次触发动作
+const RECENT_OUTPUTS: usize = 32;
+
+#[derive(Clone)]
+pub struct MonitorControl {
+    monitor: Arc<Mutex<ClipboardMonitor>>,
+    private: Arc<PrivateMode>,
+    skipped: Arc<AtomicU64>,
+}
+
+impl MonitorControl {
+    /// 开启隐私模式，`None` 恢复记录
+    pub fn set_private_mode(&self, duration: Option<PrivateDuration>) -> PrivateStatus {
+        self.private.set(duration)
+    }
+
+    pub fn private_status(&self) -> PrivateStatus {
+        self.private.status()
+    }
+
+    /// 本次运行中因敏感或被密码管理器标记而未保存的条目数
+    pub fn skipped_count(&self) -> u64 {
+        self.skipped.load(Ordering::Relaxed)
+    }
+
+    pub fn copy_item(&self, item: &ClipboardItem) -> Result<()> {
+        let mut monitor = self.monitor.lock().unwrap();
+        match &item.content {
+            ClipboardContent::Text(text) => monitor.set_clipboard_content(text),
+            ClipboardContent::Image(image) => monitor.set_clipboard_image(image),
+        }
+    }
+}
 
 pub struct ClipboardHandler {
     database: Arc<Database>,
     monitor: Arc<Mutex<ClipboardMonitor>>,
-    receiver: mpsc::Receiver<ClipboardItem>,
+    private: Arc<PrivateMode>,
+    receiver: Option<mpsc::Receiver<ClipboardItem>>,
+    max_items: Arc<AtomicUsize>,
+    filter: CaptureFilter,
+    writer: Option<StorageWriter>,
+    auto_actions: Arc<RwLock<AutoActions>>,
 }
 
 impl ClipboardHandler {
     pub fn new(database: Database) -> Result<Self> {
-        let (monitor, receiver) = ClipboardMonitor::new()?;
+        let database = Arc::new(database);
+        let private = Arc::new(PrivateMode::with_database(Arc::clone(&database)));
+        let (monitor, receiver) = ClipboardMonitor::new(Arc::clone(&private))?;
 
         Ok(Self {
-            database: Arc::new(database),
+            database,
             monitor: Arc::new(Mutex::new(monitor)),
-            receiver,
+            private,
+            receiver: Some(receiver),
+            max_items: Arc::new(AtomicUsize::new(DEFAULT_MAX_ITEMS)),
+            filter: CaptureFilter::default(),
+            writer: None,
+            auto_actions: Arc::new(RwLock::new(AutoActions::default())),
         })
     }
 
@@ -30,33 +88,174 @@ impl ClipboardHandler {
             monitor.start()?;
         }
 
-        // 启动处理线程
-        let database = Arc::clone(&self.database);
-        let receiver = std::mem::replace(&mut self.receiver, {
-            let (_, rx) = mpsc::channel();
-            rx
+        // 启动写入线程
+        if let Some(receiver) = self.receiver.take() {
+            self.clear_expired_content();
+            self.run_auto_actions();
+            self.writer = Some(StorageWriter::spawn(
+                Arc::clone(&self.database),
+                receiver,
+                Arc::clone(&self.max_items),
+                self.filter.clone(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// 过期内容仍在系统剪切板上时按配置清除
+    fn clear_expired_content(&self) {
+        let events = self.database.subscribe();
+        let monitor = Arc::clone(&self.monitor);
+        let expiry = Arc::clone(&self.filter.expiry);
+        std::thread::spawn(move || {
+            for event in events {
+                let StorageEvent::ContentExpired(hashes) = event else {
+                    continue;
+                };
+                if !expiry.read().unwrap().clear_clipboard {
+                    continue;
+                }
+                if let Err(e) = monitor.lock().unwrap().clear_if_expired(&hashes) {
+                    log::warn!("Failed to clear expired clipboard content: {}", e);
+                }
+            }
         });
+    }
 
-        thread::spawn(move || {
-            log::info!("Clipboard handler thread started");
-            while let Ok(item) = receiver.recv() {
-                log::info!(
-                    "Received clipboard item for insertion: {} bytes",
-                    item.content_size
-                );
-                match database.insert_item(&item) {
-                    Ok(id) => {
-                        log::info!("Successfully inserted clipboard item with ID: {}", id);
+    /// 新文本条目命中动作的表达式时运行该动作，结果保存为新条目并放回剪切板
+    fn run_auto_actions(&self) {
+        let events = self.database.subscribe();
+        let database = Arc::clone(&self.database);
+        let monitor = Arc::clone(&self.monitor);
+        let max_items = Arc::clone(&self.max_items);
+        let auto_actions = Arc::clone(&self.auto_actions);
+        let filter = self.filter.clone();
+        std::thread::spawn(move || {
+            let mut produced: VecDeque<String> = VecDeque::new();
+            for event in events {
+                let StorageEvent::ItemAdded(summary) = event else {
+                    continue;
+                };
+                if summary.content_type != ContentType::Text
+                    || summary.sensitive.is_some()
+                    || produced.contains(&summary.content_hash)
+                {
+                    continue;
+                }
+                let item = match database.get_item(summary.id) {
+                    Ok(Some(item)) => item,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        log::warn!("Failed to load item {}: {}", summary.id, e);
+                        continue;
                     }
+                };
+                let ClipboardContent::Text(text) = &item.content else {
+                    continue;
+                };
+                let Some(action) = auto_actions.read().unwrap().find(text).cloned() else {
+                    continue;
+                };
+
+                let output = match command::run(&action, text) {
+                    Ok(output) if output != *text => output,
+                    Ok(_) => continue,
                     Err(e) => {
-                        log::warn!("Failed to insert clipboard item: {}", e);
+                        log::warn!("{}", e);
+                        continue;
                     }
+                };
+                log::info!("Action '{}' ran on captured text", action.name);
+                let result = ClipboardItem::new_text(output.clone());
+                if produced.len() == RECENT_OUTPUTS {
+                    produced.pop_front();
+                }
+                produced.push_back(result.content_hash.clone());
+                let result = match filter.screen_item(&database, result) {
+                    Ok(result) => result,
+                    Err(reason) => {
+                        log::info!("Output of action '{}' not saved: {}", action.name, reason);
+                        continue;
+                    }
+                };
+                if let Err(e) = database.insert_batch(&[result], max_items.load(Ordering::Relaxed))
+                {
+                    log::warn!("Failed to save output of action '{}': {}", action.name, e);
+                    continue;
+                }
+                if let Err(e) = monitor.lock().unwrap().set_clipboard_content(&output) {
+                    log::warn!("Failed to copy output of action '{}': {}", action.name, e);
                 }
             }
-            log::info!("Clipboard handler thread exiting");
         });
+    }
 
-        Ok(())
+    /// 更新捕获时自动运行的动作
+    pub fn set_actions(&self, config: &ActionsConfig) {
+        *self.auto_actions.write().unwrap() = AutoActions::new(config);
+    }
+
+    /// 更新按内容类型自动过期的设置
+    pub fn set_expiry_config(&self, config: &ExpiryConfig) {
+        *self.filter.expiry.write().unwrap() = config.clone();
+    }
+
+    /// 更新写入前使用的敏感内容规则
+    pub fn set_sensitive_config(&self, config: &SensitiveConfig) {
+        *self.filter.detector.write().unwrap() = SensitiveDetector::new(config);
+    }
+
+    /// 更新按来源程序、窗口标题或内容过滤的捕获规则
+    pub fn set_capture_rules(&self, config: &CaptureConfig) {
+        *self.filter.rules.write().unwrap() = CaptureRules::new(config);
+    }
+
+    /// 开启隐私模式（暂停记录），`None` 恢复记录
+    pub fn set_private_mode(&self, duration: Option<PrivateDuration>) -> PrivateStatus {
+        self.private.set(duration)
+    }
+
+    pub fn private_status(&self) -> PrivateStatus {
+        self.private.status()
+    }
+
+    /// 是否在重启后恢复隐私模式，开启时立即恢复上次保存的状态
+    pub fn set_private_mode_persist(&self, persist: bool) {
+        self.private.set_persist(persist);
+    }
+
+    /// 写入前使用的过滤设置，配置更新会同步到所有持有者
+    pub fn capture_filter(&self) -> CaptureFilter {
+        self.filter.clone()
+    }
+
+    /// 可在其他线程中暂停/恢复记录的句柄
+    pub fn monitor_control(&self) -> MonitorControl {
+        MonitorControl {
+            monitor: Arc::clone(&self.monitor),
+            private: Arc::clone(&self.private),
+            skipped: Arc::clone(&self.filter.skipped),
+        }
+    }
+
+    pub fn skipped_count(&self) -> u64 {
+        self.filter.skipped.load(Ordering::Relaxed)
+    }
+
+    pub fn database(&self) -> Arc<Database> {
+        Arc::clone(&self.database)
+    }
+
+    /// 订阅存储变更事件
+    pub fn subscribe(&self) -> mpsc::Receiver<StorageEvent> {
+        self.database.subscribe()
+    }
+
+    /// 系统剪切板上当前的文本
+    pub fn clipboard_text(&self) -> Result<String> {
+        let mut monitor = self.monitor.lock().unwrap();
+        monitor.clipboard_text()
     }
 
     pub fn copy_to_clipboard(&self, content: &str) -> Result<()> {
@@ -64,6 +263,17 @@ impl ClipboardHandler {
         monitor.set_clipboard_content(content)
     }
 
+    /// 将条目放回系统剪切板，并持有到其他程序替换内容（供命令行使用）
+    pub fn copy_item_and_wait(&self, item: &ClipboardItem) -> Result<()> {
+        let mut monitor = self.monitor.lock().unwrap();
+        monitor.set_clipboard_and_wait(&item.content)
+    }
+
+    /// 将条目内容（文本或图片）放回系统剪切板
+    pub fn copy_item(&self, item: &ClipboardItem) -> Result<()> {
+        self.monitor_control().copy_item(item)
+    }
+
     pub fn search_items(
         &self,
         filter: &SearchFilter,
@@ -73,6 +283,37 @@ impl ClipboardHandler {
         self.database.get_items(filter, limit, offset)
     }
 
+    pub fn search_items_page(
+        &self,
+        filter: &SearchFilter,
+        before: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<ClipboardItem>> {
+        self.database.get_items_page(filter, before, limit)
+    }
+
+    /// 直接保存一条文本，不经过系统剪切板，返回条目 ID
+    ///
+    /// 与捕获的内容一样经过捕获规则和敏感内容检测，被拦截时返回错误。
+    pub fn add_text(&self, text: String) -> Result<i64> {
+        let item = self
+            .filter
+            .screen_item(&self.database, ClipboardItem::new_text(text))
+            .map_err(|reason| ClipManagerError::Config {
+                message: format!("Not saved: {}", reason),
+            })?;
+        match self
+            .database
+            .insert_batch(&[item], self.max_items.load(Ordering::Relaxed))?[0]
+        {
+            BatchOutcome::Inserted(id) | BatchOutcome::Bumped(id) => Ok(id),
+        }
+    }
+
+    pub fn get_item(&self, id: i64) -> Result<Option<ClipboardItem>> {
+        self.database.get_item(id)
+    }
+
     pub fn delete_item(&self, id: i64) -> Result<()> {
         self.database.delete_item(id)
     }
@@ -93,7 +334,40 @@ impl ClipboardHandler {
         self.database.update_favorite(id, is_favorite)
     }
 
+    /// 条目在 `ttl` 后过期，`None` 取消过期
+    pub fn set_item_expiry(&self, id: i64, ttl: Option<chrono::Duration>) -> Result<()> {
+        self.database
+            .set_expiry(id, ttl.map(|ttl| chrono::Utc::now() + ttl))
+    }
+
+    /// 搜索片段，`query` 为空时返回全部
+    pub fn search_snippets(&self, query: &str, limit: usize) -> Result<Vec<Snippet>> {
+        self.database.get_snippets(query, limit)
+    }
+
+    pub fn get_snippet(&self, id: i64) -> Result<Option<Snippet>> {
+        self.database.get_snippet(id)
+    }
+
+    /// 保存片段，没有 ID 时新建，返回片段 ID
+    pub fn save_snippet(&self, snippet: &Snippet) -> Result<i64> {
+        match snippet.id {
+            Some(id) => self.database.update_snippet(snippet).map(|_| id),
+            None => self.database.insert_snippet(snippet),
+        }
+    }
+
+    pub fn delete_snippet(&self, id: i64) -> Result<()> {
+        self.database.delete_snippet(id)
+    }
+
+    /// 导入片段，返回新增和替换的数量
+    pub fn import_snippets(&self, snippets: &[Snippet]) -> Result<(usize, usize)> {
+        self.database.import_snippets(snippets)
+    }
+
     pub fn cleanup_with_config(&self, max_items: usize) -> Result<()> {
+        self.max_items.store(max_items, Ordering::Relaxed);
         self.database.cleanup_with_limit(max_items)
     }
 }
@@ -1,5 +1,8 @@
 pub mod handler;
 pub mod monitor;
+pub mod private_mode;
+pub mod rules;
+pub mod source;
 pub mod types;
 
 pub use handler::ClipboardHandler;
@@ -1,9 +1,13 @@
-use crate::clipboard::types::{ClipboardItem, ImageData, ImageFormat};
+use crate::clipboard::private_mode::PrivateMode;
+use crate::clipboard::source::{concealed_hint, ClipboardSource, SystemSource};
+use crate::clipboard::types::{ClipboardContent, ClipboardItem, ImageData, ImageFormat};
 use crate::error::Result;
+use crate::sensitive::CONCEALED_LABEL;
+use crate::storage::writer::StorageWriter;
 use arboard::Clipboard;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
-use std::sync::mpsc;
+use std::sync::{mpsc, Arc};
 use std::thread;
 use std::time::Duration;
 
@@ -11,14 +15,16 @@ pub struct ClipboardMonitor {
     clipboard: Clipboard,
     last_text_content: String,
     last_image_hash: Option<String>,
-    sender: mpsc::Sender<ClipboardItem>,
+    sender: mpsc::SyncSender<ClipboardItem>,
     is_running: bool,
+    /// 隐私模式下仍然跟踪剪切板变化，但不保存
+    private: Arc<PrivateMode>,
 }
 
 impl ClipboardMonitor {
-    pub fn new() -> Result<(Self, mpsc::Receiver<ClipboardItem>)> {
+    pub fn new(private: Arc<PrivateMode>) -> Result<(Self, mpsc::Receiver<ClipboardItem>)> {
         let clipboard = Clipboard::new()?;
-        let (sender, receiver) = mpsc::channel();
+        let (sender, receiver) = StorageWriter::channel();
 
         let monitor = Self {
             clipboard,
@@ -26,6 +32,7 @@ impl ClipboardMonitor {
             last_image_hash: None,
             sender,
             is_running: false,
+            private,
         };
 
         Ok((monitor, receiver))
@@ -44,66 +51,23 @@ impl ClipboardMonitor {
         }
 
         let sender = self.sender.clone();
-        let mut clipboard = Clipboard::new()?;
-        let mut last_text_content = self.last_text_content.clone();
-        let mut last_image_hash: Option<String> = self.last_image_hash.clone();
+        let mut source = SystemSource::new()?;
+        let mut poller = Poller {
+            last_text_content: self.last_text_content.clone(),
+            last_image_hash: self.last_image_hash.clone(),
+        };
+        let private = Arc::clone(&self.private);
 
         thread::spawn(move || {
             log::info!("Clipboard monitor thread started");
             loop {
                 thread::sleep(Duration::from_millis(500)); // 每500ms检查一次
 
-                // 检查文本内容
-                if let Ok(current_text) = clipboard.get_text() {
-                    if current_text != last_text_content && !current_text.trim().is_empty() {
-                        log::info!(
-                            "Detected new text content: {} characters",
-                            current_text.len()
-                        );
-                        let item = ClipboardItem::new_text(current_text.clone());
-
-                        if let Err(e) = sender.send(item) {
-                            log::error!("Failed to send clipboard item: {}", e);
-                            // 接收端已关闭，退出监控
-                            break;
-                        }
-
-                        log::info!("Successfully sent text item to handler");
-                        last_text_content = current_text;
-                        // 文本内容变化时清除图片哈希
-                        last_image_hash = None;
-                    }
-                } else {
-                    // 如果获取文本失败，记录但不退出
-                    // log::debug!("Failed to get clipboard text, continuing...");
-                }
-
-                // 检查图片内容
-                if let Ok(image_data) = clipboard.get_image() {
-                    let image_bytes = image_data.bytes.to_vec();
-                    let current_image_hash = Self::calculate_image_hash(&image_bytes);
-
-                    if last_image_hash.as_ref() != Some(&current_image_hash) {
-                        // 尝试检测图片格式
-                        if let Some(format) = Self::detect_image_format(&image_bytes) {
-                            let clipboard_image = ImageData::new(
-                                image_bytes,
-                                format,
-                                image_data.width as u32,
-                                image_data.height as u32,
-                            );
-
-                            let item = ClipboardItem::new_image(clipboard_image);
-
-                            if let Err(_) = sender.send(item) {
-                                // 接收端已关闭，退出监控
-                                break;
-                            }
-
-                            last_image_hash = Some(current_image_hash);
-                            // 图片内容变化时清除文本内容
-                            last_text_content.clear();
-                        }
+                for item in poller.poll(&mut source, private.is_paused()) {
+                    if let Err(e) = sender.send(item) {
+                        log::error!("Failed to send clipboard item: {}", e);
+                        // 接收端已关闭，退出监控
+                        return;
                     }
                 }
             }
@@ -116,12 +80,80 @@ impl ClipboardMonitor {
         self.is_running = false;
     }
 
+    /// 系统剪切板上仍是已过期的内容时清空剪切板，返回是否清除
+    pub fn clear_if_expired(&mut self, hashes: &[String]) -> Result<bool> {
+        let mut source = SystemSource::new()?;
+        if !current_hash(&mut source).is_some_and(|hash| hashes.contains(&hash)) {
+            return Ok(false);
+        }
+        self.clipboard.clear()?;
+        log::info!("Cleared expired content from the system clipboard");
+        Ok(true)
+    }
+
+    /// 读取系统剪切板上的文本
+    pub fn clipboard_text(&mut self) -> Result<String> {
+        Ok(self.clipboard.get_text()?)
+    }
+
     pub fn set_clipboard_content(&mut self, content: &str) -> Result<()> {
         self.clipboard.set_text(content)?;
         self.last_text_content = content.to_string();
         Ok(())
     }
 
+    pub fn set_clipboard_image(&mut self, image: &ImageData) -> Result<()> {
+        self.clipboard.set_image(Self::to_arboard_image(image)?)?;
+        Ok(())
+    }
+
+    /// 写入剪切板并阻塞到内容被其他程序替换
+    ///
+    /// X11/Wayland 上剪切板内容由写入的进程提供，命令行这类短生命周期的进程
+    /// 需要一直持有到被替换为止。
+    pub fn set_clipboard_and_wait(&mut self, content: &ClipboardContent) -> Result<()> {
+        #[cfg(target_os = "linux")]
+        {
+            use arboard::SetExtLinux;
+
+            match content {
+                ClipboardContent::Text(text) => self.clipboard.set().wait().text(text.as_str())?,
+                ClipboardContent::Image(image) => self
+                    .clipboard
+                    .set()
+                    .wait()
+                    .image(Self::to_arboard_image(image)?)?,
+            }
+            Ok(())
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        match content {
+            ClipboardContent::Text(text) => self.set_clipboard_content(text),
+            ClipboardContent::Image(image) => self.set_clipboard_image(image),
+        }
+    }
+
+    fn to_arboard_image(image: &ImageData) -> Result<arboard::ImageData<'static>> {
+        // 剪切板需要原始 RGBA 像素，编码过的图片先解码
+        let (width, height, rgba) = match image::load_from_memory(&image.data) {
+            Ok(decoded) => {
+                let decoded = decoded.to_rgba8();
+                (decoded.width(), decoded.height(), decoded.into_raw())
+            }
+            Err(_) if image.data.len() == (image.width * image.height * 4) as usize => {
+                (image.width, image.height, image.data.clone())
+            }
+            Err(_) => return Err(crate::error::ClipManagerError::UnsupportedContentType),
+        };
+
+        Ok(arboard::ImageData {
+            width: width as usize,
+            height: height as usize,
+            bytes: rgba.into(),
+        })
+    }
+
     fn calculate_image_hash(data: &[u8]) -> String {
         let mut hasher = DefaultHasher::new();
         data.hash(&mut hasher);
@@ -156,3 +188,155 @@ impl ClipboardMonitor {
         None
     }
 }
+
+/// 系统剪切板当前内容的哈希，与条目的 `content_hash` 计算方式相同
+fn current_hash(source: &mut dyn ClipboardSource) -> Option<String> {
+    match source.text() {
+        Some(text) if !text.trim().is_empty() => Some(ClipboardItem::calculate_hash_text(&text)),
+        _ => source
+            .image()
+            .map(|image| ClipboardItem::calculate_hash_bytes(&image.bytes)),
+    }
+}
+
+/// 监控线程的轮询状态：与上一次读取的内容比较，只为新内容生成条目
+struct Poller {
+    last_text_content: String,
+    last_image_hash: Option<String>,
+}
+
+impl Poller {
+    fn poll(&mut self, source: &mut dyn ClipboardSource, paused: bool) -> Vec<ClipboardItem> {
+        let mut items = Vec::new();
+
+        // 检查文本内容
+        if let Some(current_text) = source.text() {
+            if current_text != self.last_text_content && !current_text.trim().is_empty() {
+                if paused {
+                    log::debug!("Recording paused, ignoring new text content");
+                } else {
+                    log::info!(
+                        "Detected new text content: {} characters",
+                        current_text.len()
+                    );
+                    let mut item = ClipboardItem::new_text(current_text.clone());
+                    Self::describe(&mut item, source);
+                    items.push(item);
+                }
+                self.last_text_content = current_text;
+                // 文本内容变化时清除图片哈希
+                self.last_image_hash = None;
+            }
+        }
+
+        // 检查图片内容
+        if let Some(image_data) = source.image() {
+            let current_image_hash = ClipboardMonitor::calculate_image_hash(&image_data.bytes);
+
+            if self.last_image_hash.as_ref() != Some(&current_image_hash) {
+                if paused {
+                    log::debug!("Recording paused, ignoring new image content");
+                    self.last_image_hash = Some(current_image_hash);
+                    self.last_text_content.clear();
+                } else if let Some(format) =
+                    ClipboardMonitor::detect_image_format(&image_data.bytes)
+                {
+                    let clipboard_image = ImageData::new(
+                        image_data.bytes,
+                        format,
+                        image_data.width as u32,
+                        image_data.height as u32,
+                    );
+                    let mut item = ClipboardItem::new_image(clipboard_image);
+                    Self::describe(&mut item, source);
+                    items.push(item);
+
+                    self.last_image_hash = Some(current_image_hash);
+                    // 图片内容变化时清除文本内容
+                    self.last_text_content.clear();
+                }
+            }
+        }
+
+        items
+    }
+
+    /// 记录来源程序；所有者声明内容为机密时打上标记，由写入线程按配置跳过或设置过期
+    fn describe(item: &mut ClipboardItem, source: &mut dyn ClipboardSource) {
+        item.source = source.owner();
+        if let Some(target) = concealed_hint(source) {
+            log::info!("Clipboard owner marked content as secret ({})", target);
+            item.sensitive = Some(CONCEALED_LABEL.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::FakeSource;
+
+    fn poller() -> Poller {
+        Poller {
+            last_text_content: String::new(),
+            last_image_hash: None,
+        }
+    }
+
+    #[test]
+    fn test_poll_marks_concealed_content() {
+        let mut poller = poller();
+        let mut source = FakeSource::default();
+
+        source.set_text("meeting notes", &["UTF8_STRING", "TARGETS"]);
+        let items = poller.poll(&mut source, false);
+        assert_eq!(items.len(), 1);
+        assert!(items[0].sensitive.is_none());
+        // 内容未变化时不会重复记录
+        assert!(poller.poll(&mut source, false).is_empty());
+
+        source.set_text("hunter2", &["UTF8_STRING", "x-kde-passwordManagerHint"]);
+        source.set_data("x-kde-passwordManagerHint", b"secret");
+        source.set_owner("KeePassXC", 4242, "Passwords.kdbx - KeePassXC");
+        let items = poller.poll(&mut source, false);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].sensitive.as_deref(), Some(CONCEALED_LABEL));
+        let owner = items[0].source.as_ref().unwrap();
+        assert_eq!(owner.name.as_deref(), Some("KeePassXC"));
+        assert_eq!(owner.pid, Some(4242));
+
+        // 暂停期间的内容既不记录，也不会在恢复后补记
+        source.set_text("while paused", &["UTF8_STRING"]);
+        assert!(poller.poll(&mut source, true).is_empty());
+        assert!(poller.poll(&mut source, false).is_empty());
+    }
+
+    #[test]
+    fn test_poll_concealed_image() {
+        let mut poller = poller();
+        let mut source = FakeSource::default();
+        let png = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0, 0];
+        source.set_image(1, 1, &png, &["image/png", "CLIPBOARD_MANAGER_IGNORE"]);
+
+        let items = poller.poll(&mut source, false);
+        assert_eq!(items.len(), 1);
+        assert!(items[0].get_image_data().is_some());
+        assert_eq!(items[0].sensitive.as_deref(), Some(CONCEALED_LABEL));
+    }
+
+    #[test]
+    fn test_current_hash_matches_items() {
+        let mut poller = poller();
+        let mut source = FakeSource::default();
+        assert_eq!(current_hash(&mut source), None);
+
+        source.set_text("temporary token", &["UTF8_STRING"]);
+        let text = poller.poll(&mut source, false).remove(0);
+        assert_eq!(current_hash(&mut source), Some(text.content_hash));
+
+        let png = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 1, 2];
+        source.set_image(1, 1, &png, &["image/png"]);
+        let image = poller.poll(&mut source, false).remove(0);
+        assert_eq!(current_hash(&mut source), Some(image.content_hash));
+    }
+}
@@ -0,0 +1,299 @@
+// 隐私模式：暂停记录剪切板，可在指定时间后自动恢复
+
+use crate::storage::{Database, StorageEvent};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// 持久化时在 `app_state` 表中使用的键
+const STATE_KEY: &str = "private_mode";
+
+/// 隐私模式持续多久
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrivateDuration {
+    /// 到时间后自动恢复记录
+    For(Duration),
+    /// 直到程序退出，不会被保存
+    UntilRestart,
+    /// 直到手动恢复
+    UntilResumed,
+}
+
+impl PrivateDuration {
+    pub fn minutes(minutes: i64) -> Self {
+        PrivateDuration::For(Duration::minutes(minutes))
+    }
+
+    /// 解析 `30s`、`5m`、`1h` 这样的时长，不带单位时按分钟计算
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        let split = value
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(value.len());
+        let (number, unit) = value.split_at(split);
+        let number: i64 = number
+            .parse()
+            .map_err(|_| format!("Invalid duration '{}'", value))?;
+        let duration = match unit {
+            "s" => Duration::try_seconds(number),
+            "" | "m" | "min" => Duration::try_minutes(number),
+            "h" => Duration::try_hours(number),
+            _ => return Err(format!("Unknown duration unit '{}'", unit)),
+        };
+        let duration = duration
+            .filter(|duration| Utc::now().checked_add_signed(*duration).is_some())
+            .ok_or_else(|| format!("Duration too long: '{}'", value))?;
+        if duration <= Duration::zero() {
+            return Err(format!("Duration must be positive: '{}'", value));
+        }
+        Ok(PrivateDuration::For(duration))
+    }
+}
+
+/// 隐私模式的当前状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct PrivateStatus {
+    pub paused: bool,
+    /// 自动恢复记录的时间
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resume_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub until_restart: bool,
+}
+
+impl PrivateStatus {
+    fn new(duration: PrivateDuration) -> Self {
+        Self {
+            paused: true,
+            // 超出时间范围时不自动恢复，需要手动恢复记录
+            resume_at: match duration {
+                PrivateDuration::For(duration) => Utc::now().checked_add_signed(duration),
+                _ => None,
+            },
+            until_restart: duration == PrivateDuration::UntilRestart,
+        }
+    }
+
+    /// 距离自动恢复的剩余时间
+    pub fn remaining(&self) -> Option<Duration> {
+        self.resume_at
+            .map(|resume_at| (resume_at - Utc::now()).max(Duration::zero()))
+    }
+
+    fn is_expired(&self) -> bool {
+        self.resume_at
+            .is_some_and(|resume_at| resume_at <= Utc::now())
+    }
+}
+
+/// 监控线程、IPC 与界面共享的隐私模式状态
+///
+/// 是否到期在读取状态时检查，监控线程每次轮询都会读取，因此到期后最多延迟一个轮询周期恢复。
+/// 每次变化都通过数据库发布 [`StorageEvent::RecordingChanged`]。
+#[derive(Default)]
+pub struct PrivateMode {
+    status: Mutex<PrivateStatus>,
+    database: Option<Arc<Database>>,
+    persist: AtomicBool,
+}
+
+impl PrivateMode {
+    pub fn with_database(database: Arc<Database>) -> Self {
+        Self {
+            database: Some(database),
+            ..Self::default()
+        }
+    }
+
+    /// 开启或关闭持久化；开启时恢复上次保存且尚未到期的状态
+    pub fn set_persist(&self, persist: bool) {
+        if self.persist.swap(persist, Ordering::Relaxed) == persist {
+            return;
+        }
+        if !persist {
+            self.store(None);
+            return;
+        }
+
+        let mut status = self.status.lock().unwrap();
+        if status.paused {
+            self.save(&status);
+            return;
+        }
+        match self.load() {
+            Some(saved) if saved.paused && !saved.is_expired() => {
+                log::info!("Restored private mode, clipboard recording paused");
+                *status = saved;
+                self.publish(saved);
+            }
+            Some(_) => self.save(&PrivateStatus::default()),
+            None => {}
+        }
+    }
+
+    /// 暂停记录，`None` 恢复记录
+    pub fn set(&self, duration: Option<PrivateDuration>) -> PrivateStatus {
+        let status = duration.map(PrivateStatus::new).unwrap_or_default();
+        match (duration, status.resume_at) {
+            (None, _) => log::info!("Private mode off, clipboard recording resumed"),
+            (_, Some(resume_at)) => log::info!(
+                "Private mode on, clipboard recording paused until {}",
+                resume_at.with_timezone(&chrono::Local).format("%H:%M:%S")
+            ),
+            (Some(PrivateDuration::UntilRestart), _) => {
+                log::info!("Private mode on, clipboard recording paused until restart")
+            }
+            _ => log::info!("Private mode on, clipboard recording paused"),
+        }
+        *self.status.lock().unwrap() = status;
+        self.save(&status);
+        self.publish(status);
+        status
+    }
+
+    /// 当前状态，已到期时先自动恢复
+    pub fn status(&self) -> PrivateStatus {
+        let mut status = self.status.lock().unwrap();
+        if status.is_expired() {
+            log::info!("Private mode expired, clipboard recording resumed");
+            *status = PrivateStatus::default();
+            self.save(&status);
+            self.publish(*status);
+        }
+        *status
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.status().paused
+    }
+
+    fn publish(&self, status: PrivateStatus) {
+        if let Some(database) = &self.database {
+            database.publish(StorageEvent::RecordingChanged(status));
+        }
+    }
+
+    fn load(&self) -> Option<PrivateStatus> {
+        let value = self.database.as_ref()?.get_state(STATE_KEY).ok()??;
+        serde_json::from_str(&value)
+            .inspect_err(|e| log::warn!("Ignoring saved private mode state: {}", e))
+            .ok()
+    }
+
+    fn save(&self, status: &PrivateStatus) {
+        if !self.persist.load(Ordering::Relaxed) {
+            return;
+        }
+        // 直到重启的暂停不保存
+        let value = (status.paused && !status.until_restart)
+            .then(|| serde_json::to_string(status).unwrap_or_default());
+        self.store(value.as_deref());
+    }
+
+    fn store(&self, value: Option<&str>) {
+        if let Some(database) = &self.database {
+            if let Err(e) = database.set_state(STATE_KEY, value) {
+                log::warn!("Failed to save private mode state: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(
+            PrivateDuration::parse("5m").unwrap(),
+            PrivateDuration::minutes(5)
+        );
+        assert_eq!(
+            PrivateDuration::parse("1h").unwrap(),
+            PrivateDuration::minutes(60)
+        );
+        assert_eq!(
+            PrivateDuration::parse("90").unwrap(),
+            PrivateDuration::minutes(90)
+        );
+        assert_eq!(
+            PrivateDuration::parse("30s").unwrap(),
+            PrivateDuration::For(Duration::seconds(30))
+        );
+        assert!(PrivateDuration::parse("0m").is_err());
+        assert!(PrivateDuration::parse("5d").is_err());
+        assert!(PrivateDuration::parse("m").is_err());
+        assert!(PrivateDuration::parse("9223372036854775807h").is_err());
+        assert!(PrivateDuration::parse("99999999999999m").is_err());
+
+        let status = PrivateStatus::new(PrivateDuration::For(Duration::MAX));
+        assert!(status.paused);
+        assert_eq!(status.resume_at, None);
+    }
+
+    #[test]
+    fn test_auto_resume() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = Arc::new(Database::new(dir.path().join("test.db")).unwrap());
+        let events = database.subscribe();
+        let private = PrivateMode::with_database(Arc::clone(&database));
+
+        let status = private.set(Some(PrivateDuration::minutes(5)));
+        assert!(status.paused);
+        assert!(status.remaining().unwrap() > Duration::minutes(4));
+        assert!(private.is_paused());
+        assert!(matches!(
+            events.try_recv(),
+            Ok(StorageEvent::RecordingChanged(changed)) if changed == status
+        ));
+
+        private.set(Some(PrivateDuration::For(Duration::milliseconds(20))));
+        assert!(events.try_recv().is_ok());
+        std::thread::sleep(std::time::Duration::from_millis(40));
+        assert!(!private.is_paused());
+        assert!(matches!(
+            events.try_recv(),
+            Ok(StorageEvent::RecordingChanged(PrivateStatus {
+                paused: false,
+                ..
+            }))
+        ));
+
+        private.set(Some(PrivateDuration::UntilResumed));
+        assert_eq!(private.status().resume_at, None);
+        private.set(None);
+        assert!(!private.is_paused());
+    }
+
+    #[test]
+    fn test_persist_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = Arc::new(Database::new(dir.path().join("test.db")).unwrap());
+
+        // 未开启持久化时不保存
+        let first = PrivateMode::with_database(Arc::clone(&database));
+        first.set(Some(PrivateDuration::UntilResumed));
+        assert!(!PrivateMode::with_database(Arc::clone(&database)).is_paused());
+
+        first.set_persist(true);
+        let status = first.set(Some(PrivateDuration::minutes(60)));
+        let second = PrivateMode::with_database(Arc::clone(&database));
+        second.set_persist(true);
+        assert_eq!(second.status(), status);
+
+        // 直到重启的暂停不会恢复
+        second.set(Some(PrivateDuration::UntilRestart));
+        let third = PrivateMode::with_database(Arc::clone(&database));
+        third.set_persist(true);
+        assert!(!third.is_paused());
+
+        // 关闭持久化时清除保存的状态
+        third.set(Some(PrivateDuration::UntilResumed));
+        third.set_persist(false);
+        let fourth = PrivateMode::with_database(database);
+        fourth.set_persist(true);
+        assert!(!fourth.is_paused());
+    }
+}
@@ -0,0 +1,240 @@
+// 捕获规则匹配：按来源程序、窗口标题或内容拒绝、只允许记录或设置过期时间
+
+use crate::clipboard::types::ClipboardItem;
+use crate::config::capture::{CaptureConfig, CaptureRule, RuleField, RuleKind};
+use chrono::Duration;
+use regex::{Regex, RegexBuilder};
+
+/// 没有命中任何允许规则时报告的名称
+pub const ALLOWLIST_MISS: &str = "not in allowlist";
+
+/// 被规则拦截的捕获
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blocked {
+    /// 拦截的规则名称
+    pub rule: String,
+    pub app: Option<String>,
+}
+
+struct CompiledRule {
+    name: String,
+    kind: RuleKind,
+    field: RuleField,
+    regex: Regex,
+    ttl_secs: u64,
+}
+
+#[derive(Default)]
+pub struct CaptureRules {
+    rules: Vec<CompiledRule>,
+}
+
+impl CaptureRules {
+    /// 编译启用的规则，无效的表达式会被忽略并记录警告
+    pub fn new(config: &CaptureConfig) -> Self {
+        let rules = config
+            .rules
+            .iter()
+            .filter(|rule| rule.enabled && !rule.pattern.is_empty())
+            .filter_map(|rule| match Self::compile(rule) {
+                Ok(regex) => Some(CompiledRule {
+                    name: rule.name.clone(),
+                    kind: rule.kind,
+                    field: rule.field,
+                    regex,
+                    ttl_secs: rule.ttl_secs,
+                }),
+                Err(e) => {
+                    log::warn!("Ignoring invalid capture rule '{}': {}", rule.name, e);
+                    None
+                }
+            })
+            .collect();
+        Self { rules }
+    }
+
+    fn compile(rule: &CaptureRule) -> Result<Regex, regex::Error> {
+        RegexBuilder::new(&rule.pattern)
+            .case_insensitive(rule.field != RuleField::Content)
+            .build()
+    }
+
+    /// 拒绝规则优先；存在允许规则时，未命中任何允许规则的内容也会被拦截
+    pub fn check(&self, item: &ClipboardItem) -> Result<(), Blocked> {
+        let blocked = |rule: &str| Blocked {
+            rule: rule.to_string(),
+            app: item.source.as_ref().and_then(|source| source.name.clone()),
+        };
+
+        if let Some(rule) = self
+            .rules
+            .iter()
+            .find(|rule| rule.kind == RuleKind::Deny && Self::matches(rule, item))
+        {
+            return Err(blocked(&rule.name));
+        }
+
+        let mut allow_rules = self
+            .rules
+            .iter()
+            .filter(|rule| rule.kind == RuleKind::Allow)
+            .peekable();
+        if allow_rules.peek().is_some() && !allow_rules.any(|rule| Self::matches(rule, item)) {
+            return Err(blocked(ALLOWLIST_MISS));
+        }
+        Ok(())
+    }
+
+    /// 命中的过期规则中最短的存活时间
+    pub fn ttl(&self, item: &ClipboardItem) -> Option<Duration> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.kind == RuleKind::Expire && Self::matches(rule, item))
+            .map(|rule| Duration::seconds(rule.ttl_secs as i64))
+            .min()
+    }
+
+    fn matches(rule: &CompiledRule, item: &ClipboardItem) -> bool {
+        let source = item.source.as_ref();
+        let value = match rule.field {
+            RuleField::App => source.and_then(|source| source.name.as_deref()),
+            RuleField::Title => source.and_then(|source| source.title.as_deref()),
+            RuleField::Content => item.get_text_content(),
+        };
+        value.is_some_and(|value| rule.regex.is_match(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard::types::SourceApp;
+
+    fn rule(name: &str, kind: RuleKind, field: RuleField, pattern: &str) -> CaptureRule {
+        CaptureRule {
+            name: name.to_string(),
+            kind,
+            field,
+            pattern: pattern.to_string(),
+            enabled: true,
+            ttl_secs: 60,
+        }
+    }
+
+    fn item(text: &str, app: &str, title: &str) -> ClipboardItem {
+        let mut item = ClipboardItem::new_text(text.to_string());
+        item.source = Some(SourceApp {
+            name: Some(app.to_string()),
+            pid: Some(42),
+            title: Some(title.to_string()),
+        });
+        item
+    }
+
+    #[test]
+    fn test_deny_rules() {
+        let rules = CaptureRules::new(&CaptureConfig {
+            rules: vec![
+                rule(
+                    "password manager",
+                    RuleKind::Deny,
+                    RuleField::App,
+                    "keepassxc",
+                ),
+                rule("bank", RuleKind::Deny, RuleField::Title, r"online banking"),
+                rule(
+                    "ticket",
+                    RuleKind::Deny,
+                    RuleField::Content,
+                    r"^SECRET-\d+$",
+                ),
+            ],
+        });
+
+        let blocked = rules
+            .check(&item("hunter2", "KeePassXC", "Passwords.kdbx"))
+            .unwrap_err();
+        assert_eq!(blocked.rule, "password manager");
+        assert_eq!(blocked.app.as_deref(), Some("KeePassXC"));
+
+        assert_eq!(
+            rules
+                .check(&item("IBAN", "firefox", "Online Banking — Mozilla Firefox"))
+                .unwrap_err()
+                .rule,
+            "bank"
+        );
+        assert_eq!(
+            rules
+                .check(&item("SECRET-12", "code", "main.rs"))
+                .unwrap_err()
+                .rule,
+            "ticket"
+        );
+        // 内容规则区分大小写
+        assert!(rules.check(&item("secret-12", "code", "main.rs")).is_ok());
+        // 来源未知时只有内容规则生效
+        assert!(rules
+            .check(&ClipboardItem::new_text("hello".to_string()))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_allowlist_and_disabled_rules() {
+        let mut config = CaptureConfig {
+            rules: vec![
+                rule("editors", RuleKind::Allow, RuleField::App, "^(code|kate)$"),
+                rule("no keys", RuleKind::Deny, RuleField::Content, "BEGIN"),
+            ],
+        };
+        let rules = CaptureRules::new(&config);
+        assert!(rules.check(&item("fn main()", "Code", "main.rs")).is_ok());
+        assert_eq!(
+            rules.check(&item("text", "firefox", "")).unwrap_err().rule,
+            ALLOWLIST_MISS
+        );
+        assert_eq!(
+            rules.check(&item("BEGIN", "kate", "")).unwrap_err().rule,
+            "no keys"
+        );
+
+        config.rules[0].enabled = false;
+        assert!(CaptureRules::new(&config)
+            .check(&item("text", "firefox", ""))
+            .is_ok());
+
+        config
+            .rules
+            .push(rule("broken", RuleKind::Deny, RuleField::App, "("));
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_expire_rules() {
+        let mut short = rule("otp", RuleKind::Expire, RuleField::Content, r"^\d{6}$");
+        short.ttl_secs = 30;
+        let rules = CaptureRules::new(&CaptureConfig {
+            rules: vec![
+                rule("editors", RuleKind::Allow, RuleField::App, "code"),
+                rule("chat", RuleKind::Expire, RuleField::Title, "slack"),
+                short,
+            ],
+        });
+
+        let otp = item("123456", "code", "Slack — general");
+        // 过期规则不影响是否记录
+        assert!(rules.check(&otp).is_ok());
+        assert_eq!(rules.ttl(&otp), Some(Duration::seconds(30)));
+        assert_eq!(
+            rules.ttl(&item("hello", "code", "Slack")),
+            Some(Duration::seconds(60))
+        );
+        assert_eq!(rules.ttl(&item("hello", "code", "main.rs")), None);
+
+        let mut config = CaptureConfig {
+            rules: vec![rule("zero", RuleKind::Expire, RuleField::App, "x")],
+        };
+        config.rules[0].ttl_secs = 0;
+        assert!(config.validate().is_err());
+    }
+}
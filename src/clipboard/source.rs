@@ -0,0 +1,422 @@
+// 剪切板读取后端：内容之外还提供所有者提供的格式列表（TARGETS）
+
+use crate::clipboard::types::SourceApp;
+use crate::error::Result;
+use arboard::Clipboard;
+
+/// 剪切板中的原始 RGBA 图片
+pub struct RawImage {
+    pub width: usize,
+    pub height: usize,
+    pub bytes: Vec<u8>,
+}
+
+/// 监控线程读取剪切板的接口，测试中可替换为假的后端
+pub trait ClipboardSource: Send {
+    fn text(&mut self) -> Option<String>;
+
+    fn image(&mut self) -> Option<RawImage>;
+
+    /// 当前内容提供的格式（MIME 类型或 X11 atom 名称），不支持时返回空列表
+    fn targets(&mut self) -> Vec<String>;
+
+    /// 读取指定格式的数据
+    fn read(&mut self, target: &str) -> Option<Vec<u8>>;
+
+    /// 当前剪切板所有者的程序信息，无法获取时返回 None
+    fn owner(&mut self) -> Option<SourceApp>;
+}
+
+/// 密码管理器用来声明“不要记录”的格式
+///
+/// KeePassXC、KDE 使用 `x-kde-passwordManagerHint`（值为 `secret`）；
+/// macOS 的 nspasteboard.org 约定和 Windows 的排除格式也会被跨平台程序带到 X11/Wayland。
+const CONCEAL_TARGETS: &[&str] = &[
+    "x-kde-passwordManagerHint",
+    "org.nspasteboard.ConcealedType",
+    "org.nspasteboard.TransientType",
+    "application/x-nspasteboard-concealed-type",
+    "ExcludeClipboardContentFromMonitorProcessing",
+    "CLIPBOARD_MANAGER_IGNORE",
+    "x-clipboard-manager-ignore",
+];
+
+/// 检查格式列表中的机密标记，返回命中的格式名称
+pub fn concealed_hint(source: &mut dyn ClipboardSource) -> Option<String> {
+    let targets = source.targets();
+    let target = targets
+        .iter()
+        .find(|target| CONCEAL_TARGETS.contains(&target.as_str()))?
+        .clone();
+
+    // KDE 的提示带有取值，只有 "secret" 表示机密；读取失败时按机密处理
+    if target == "x-kde-passwordManagerHint" {
+        if let Some(value) = source.read(&target) {
+            let value = String::from_utf8_lossy(&value);
+            if value.trim().trim_end_matches('\0') != "secret" {
+                return None;
+            }
+        }
+    }
+    Some(target)
+}
+
+/// 系统剪切板：内容通过 arboard 读取，格式列表在 X11 上通过 TARGETS 查询，
+/// 在 Wayland 上通过 `wl-paste --list-types` 查询。来源程序只在 X11 上可用
+pub struct SystemSource {
+    clipboard: Clipboard,
+    #[cfg(target_os = "linux")]
+    targets: TargetBackend,
+}
+
+#[cfg(target_os = "linux")]
+enum TargetBackend {
+    X11(Box<x11::TargetReader>),
+    Wayland,
+    None,
+}
+
+impl SystemSource {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            clipboard: Clipboard::new()?,
+            #[cfg(target_os = "linux")]
+            targets: if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+                TargetBackend::Wayland
+            } else {
+                match x11::TargetReader::new() {
+                    Ok(reader) => TargetBackend::X11(Box::new(reader)),
+                    Err(e) => {
+                        log::warn!("Clipboard targets unavailable: {}", e);
+                        TargetBackend::None
+                    }
+                }
+            },
+        })
+    }
+}
+
+impl ClipboardSource for SystemSource {
+    fn text(&mut self) -> Option<String> {
+        self.clipboard.get_text().ok()
+    }
+
+    fn image(&mut self) -> Option<RawImage> {
+        let image = self.clipboard.get_image().ok()?;
+        Some(RawImage {
+            width: image.width,
+            height: image.height,
+            bytes: image.bytes.into_owned(),
+        })
+    }
+
+    fn targets(&mut self) -> Vec<String> {
+        #[cfg(target_os = "linux")]
+        match &mut self.targets {
+            TargetBackend::X11(reader) => return reader.targets(),
+            TargetBackend::Wayland => {
+                return wl_paste(&["--list-types"])
+                    .map(|output| {
+                        String::from_utf8_lossy(&output)
+                            .lines()
+                            .map(str::to_string)
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }
+            TargetBackend::None => {}
+        }
+        Vec::new()
+    }
+
+    fn read(&mut self, target: &str) -> Option<Vec<u8>> {
+        #[cfg(target_os = "linux")]
+        match &mut self.targets {
+            TargetBackend::X11(reader) => return reader.read(target),
+            TargetBackend::Wayland => return wl_paste(&["--no-newline", "--type", target]),
+            TargetBackend::None => {}
+        }
+        let _ = target;
+        None
+    }
+
+    fn owner(&mut self) -> Option<SourceApp> {
+        #[cfg(target_os = "linux")]
+        if let TargetBackend::X11(reader) = &mut self.targets {
+            return reader.owner();
+        }
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn wl_paste(args: &[&str]) -> Option<Vec<u8>> {
+    let output = std::process::Command::new("wl-paste")
+        .args(args)
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    output.status.success().then_some(output.stdout)
+}
+
+/// 读取 /proc 中的进程名
+#[cfg(target_os = "linux")]
+fn process_name(pid: u32) -> Option<String> {
+    let name = std::fs::read_to_string(format!("/proc/{}/comm", pid)).ok()?;
+    Some(name.trim().to_string()).filter(|name| !name.is_empty())
+}
+
+#[cfg(target_os = "linux")]
+mod x11 {
+    use crate::clipboard::types::SourceApp;
+    use std::collections::HashMap;
+    use std::time::{Duration, Instant};
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{Atom, AtomEnum, ConnectionExt, CreateWindowAux, WindowClass};
+    use x11rb::protocol::Event;
+    use x11rb::rust_connection::RustConnection;
+    use x11rb::{COPY_DEPTH_FROM_PARENT, CURRENT_TIME, NONE};
+
+    /// 剪切板所有者需要在这段时间内响应
+    const REPLY_TIMEOUT: Duration = Duration::from_millis(200);
+
+    /// 通过隐藏窗口发起 ConvertSelection 请求
+    pub struct TargetReader {
+        conn: RustConnection,
+        window: u32,
+        root: u32,
+        clipboard: Atom,
+        targets: Atom,
+        property: Atom,
+        wm_pid: Atom,
+        wm_name: Atom,
+        utf8_string: Atom,
+        active_window: Atom,
+        names: HashMap<Atom, String>,
+    }
+
+    impl TargetReader {
+        pub fn new() -> Result<Self, String> {
+            let (conn, screen_num) =
+                x11rb::connect(None).map_err(|e| format!("Cannot connect to X server: {}", e))?;
+            let root = conn.setup().roots[screen_num].root;
+            let window = conn.generate_id().map_err(|e| e.to_string())?;
+            conn.create_window(
+                COPY_DEPTH_FROM_PARENT,
+                window,
+                root,
+                0,
+                0,
+                1,
+                1,
+                0,
+                WindowClass::INPUT_ONLY,
+                0,
+                &CreateWindowAux::new(),
+            )
+            .map_err(|e| e.to_string())?;
+
+            let mut reader = Self {
+                conn,
+                window,
+                root,
+                clipboard: 0,
+                targets: 0,
+                property: 0,
+                wm_pid: 0,
+                wm_name: 0,
+                utf8_string: 0,
+                active_window: 0,
+                names: HashMap::new(),
+            };
+            reader.clipboard = reader.intern("CLIPBOARD")?;
+            reader.targets = reader.intern("TARGETS")?;
+            reader.property = reader.intern("CLIPMANAGER_TARGETS")?;
+            reader.wm_pid = reader.intern("_NET_WM_PID")?;
+            reader.wm_name = reader.intern("_NET_WM_NAME")?;
+            reader.utf8_string = reader.intern("UTF8_STRING")?;
+            reader.active_window = reader.intern("_NET_ACTIVE_WINDOW")?;
+            Ok(reader)
+        }
+
+        fn intern(&self, name: &str) -> Result<Atom, String> {
+            Ok(self
+                .conn
+                .intern_atom(false, name.as_bytes())
+                .map_err(|e| e.to_string())?
+                .reply()
+                .map_err(|e| e.to_string())?
+                .atom)
+        }
+
+        fn atom_name(&mut self, atom: Atom) -> Option<String> {
+            if let Some(name) = self.names.get(&atom) {
+                return Some(name.clone());
+            }
+            let reply = self.conn.get_atom_name(atom).ok()?.reply().ok()?;
+            let name = String::from_utf8_lossy(&reply.name).into_owned();
+            self.names.insert(atom, name.clone());
+            Some(name)
+        }
+
+        pub fn targets(&mut self) -> Vec<String> {
+            let Some(value) = self.convert(self.targets) else {
+                return Vec::new();
+            };
+            value
+                .chunks_exact(4)
+                .map(|chunk| u32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                .filter_map(|atom| self.atom_name(atom))
+                .collect()
+        }
+
+        pub fn read(&mut self, target: &str) -> Option<Vec<u8>> {
+            let atom = self.intern(target).ok()?;
+            self.convert(atom)
+        }
+
+        /// 剪切板所有者窗口的 WM_CLASS、PID 和标题
+        ///
+        /// 所有者通常是程序不可见的辅助窗口，缺少的信息从同一进程的活动窗口补全。
+        pub fn owner(&mut self) -> Option<SourceApp> {
+            let owner = self
+                .conn
+                .get_selection_owner(self.clipboard)
+                .ok()?
+                .reply()
+                .ok()?
+                .owner;
+            if owner == NONE {
+                return None;
+            }
+
+            let mut source = self.window_info(owner);
+            if source.name.is_none() || source.title.is_none() {
+                if let Some(active) = self.active() {
+                    let active = self.window_info(active);
+                    if source.pid.is_none() || source.pid == active.pid {
+                        source.pid = source.pid.or(active.pid);
+                        source.name = source.name.or(active.name);
+                        source.title = source.title.or(active.title);
+                    }
+                }
+            }
+            if source.name.is_none() {
+                source.name = source.pid.and_then(super::process_name);
+            }
+            (source.name.is_some() || source.pid.is_some()).then_some(source)
+        }
+
+        fn active(&self) -> Option<u32> {
+            let reply = self
+                .conn
+                .get_property(false, self.root, self.active_window, AtomEnum::WINDOW, 0, 1)
+                .ok()?
+                .reply()
+                .ok()?;
+            let window = reply.value32()?.next()?;
+            (window != NONE).then_some(window)
+        }
+
+        fn window_info(&self, window: u32) -> SourceApp {
+            let property = |property: Atom, kind: Atom, length: u32| {
+                self.conn
+                    .get_property(false, window, property, kind, 0, length)
+                    .ok()?
+                    .reply()
+                    .ok()
+                    .filter(|reply| !reply.value.is_empty())
+            };
+
+            // WM_CLASS 由 "instance\0class\0" 组成，取类名
+            let name = property(AtomEnum::WM_CLASS.into(), AtomEnum::STRING.into(), 256).and_then(
+                |reply| {
+                    String::from_utf8_lossy(&reply.value)
+                        .split('\0')
+                        .rfind(|part| !part.is_empty())
+                        .map(str::to_string)
+                },
+            );
+            let pid = property(self.wm_pid, AtomEnum::CARDINAL.into(), 1)
+                .and_then(|reply| reply.value32().and_then(|mut values| values.next()));
+            let title = property(self.wm_name, self.utf8_string, 1024)
+                .or_else(|| property(AtomEnum::WM_NAME.into(), AtomEnum::STRING.into(), 1024))
+                .map(|reply| String::from_utf8_lossy(&reply.value).into_owned());
+
+            SourceApp { name, pid, title }
+        }
+
+        /// 请求所有者把剪切板转换为指定格式，并读取写入的属性
+        fn convert(&self, target: Atom) -> Option<Vec<u8>> {
+            self.conn
+                .convert_selection(
+                    self.window,
+                    self.clipboard,
+                    target,
+                    self.property,
+                    CURRENT_TIME,
+                )
+                .ok()?;
+            self.conn.flush().ok()?;
+
+            let deadline = Instant::now() + REPLY_TIMEOUT;
+            loop {
+                match self.conn.poll_for_event() {
+                    Ok(Some(Event::SelectionNotify(event))) if event.requestor == self.window => {
+                        if event.property == NONE {
+                            return None;
+                        }
+                        let reply = self
+                            .conn
+                            .get_property(
+                                true,
+                                self.window,
+                                self.property,
+                                AtomEnum::ANY,
+                                0,
+                                u32::MAX / 4,
+                            )
+                            .ok()?
+                            .reply()
+                            .ok()?;
+                        return Some(reply.value);
+                    }
+                    Ok(Some(_)) => {}
+                    Ok(None) if Instant::now() < deadline => {
+                        std::thread::sleep(Duration::from_millis(5));
+                    }
+                    Ok(None) | Err(_) => return None,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::FakeSource;
+
+    #[test]
+    fn test_concealed_hint() {
+        let mut source = FakeSource::default();
+        source.set_text("hunter2", &["UTF8_STRING", "TARGETS"]);
+        assert_eq!(concealed_hint(&mut source), None);
+
+        source.set_text("hunter2", &["UTF8_STRING", "CLIPBOARD_MANAGER_IGNORE"]);
+        assert_eq!(
+            concealed_hint(&mut source).as_deref(),
+            Some("CLIPBOARD_MANAGER_IGNORE")
+        );
+
+        // KDE 提示只有值为 secret 时才表示机密
+        source.set_text("hunter2", &["text/plain", "x-kde-passwordManagerHint"]);
+        source.set_data("x-kde-passwordManagerHint", b"secret");
+        assert_eq!(
+            concealed_hint(&mut source).as_deref(),
+            Some("x-kde-passwordManagerHint")
+        );
+        source.set_data("x-kde-passwordManagerHint", b"public");
+        assert_eq!(concealed_hint(&mut source), None);
+    }
+}
@@ -14,6 +14,28 @@ pub struct ClipboardItem {
     pub accessed_at: DateTime<Utc>,
     pub access_count: u32,
     pub is_favorite: bool,
+    /// 检测到的敏感内容类型，界面中显示标记并默认隐藏
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sensitive: Option<String>,
+    /// 到期后自动删除
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// 复制内容的程序，尽力获取
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SourceApp>,
+}
+
+/// 剪切板内容的来源程序
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SourceApp {
+    /// X11 的 WM_CLASS，取不到时为进程名
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    /// 复制时的窗口标题，只用于捕获规则，不会保存
+    #[serde(skip)]
+    pub title: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -62,6 +84,9 @@ impl ClipboardItem {
             accessed_at: now,
             access_count: 0,
             is_favorite: false,
+            sensitive: None,
+            expires_at: None,
+            source: None,
         }
     }
 
@@ -80,21 +105,56 @@ impl ClipboardItem {
             accessed_at: now,
             access_count: 0,
             is_favorite: false,
+            sensitive: None,
+            expires_at: None,
+            source: None,
         }
     }
 
+    /// 由外部输入（命令行、控制套接字）的数据和 MIME 类型创建条目
+    pub fn from_bytes(data: Vec<u8>, mime: &str, max_size: usize) -> crate::error::Result<Self> {
+        use crate::error::ClipManagerError;
+
+        if data.is_empty() {
+            return Err(ClipManagerError::Config {
+                message: "Nothing to add, the input was empty".to_string(),
+            });
+        }
+        if data.len() > max_size {
+            return Err(ClipManagerError::ContentTooLarge {
+                size: data.len(),
+                max_size,
+            });
+        }
+
+        if mime.starts_with("text/") {
+            let text = String::from_utf8(data).map_err(|_| ClipManagerError::Config {
+                message: "Text input is not valid UTF-8".to_string(),
+            })?;
+            return Ok(Self::new_text(text));
+        }
+
+        let format =
+            ImageFormat::from_mime_type(mime).ok_or(ClipManagerError::UnsupportedContentType)?;
+        let decoded = image::load_from_memory(&data).map_err(|e| ClipManagerError::Config {
+            message: format!("Input is not a valid {} image: {}", mime, e),
+        })?;
+        let (width, height) = (decoded.width(), decoded.height());
+        Ok(Self::new_image(ImageData::new(data, format, width, height)))
+    }
+
     // 保持向后兼容性
     pub fn new(content: String) -> Self {
         Self::new_text(content)
     }
 
-    fn calculate_hash_text(content: &str) -> String {
+    pub(crate) fn calculate_hash_text(content: &str) -> String {
         let mut hasher = DefaultHasher::new();
         content.hash(&mut hasher);
         format!("{:x}", hasher.finish())
     }
 
-    fn calculate_hash_bytes(data: &[u8]) -> String {
+    pub(crate) fn calculate_hash_bytes(data: &[u8]) -> String {
         let mut hasher = DefaultHasher::new();
         data.hash(&mut hasher);
         format!("{:x}", hasher.finish())
@@ -122,6 +182,19 @@ impl ClipboardItem {
         }
     }
 
+    /// 不泄露敏感内容的摘要，用于托盘、弹出选择器和通知
+    pub fn safe_preview(&self, max_length: usize) -> String {
+        match &self.sensitive {
+            Some(label) => crate::sensitive::concealed_preview(label),
+            None => self.preview(max_length),
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= Utc::now())
+    }
+
     pub fn get_text_content(&self) -> Option<&str> {
         match &self.content {
             ClipboardContent::Text(text) => Some(text),
@@ -155,6 +228,27 @@ impl ImageData {
     pub fn size_mb(&self) -> f64 {
         self.data.len() as f64 / (1024.0 * 1024.0)
     }
+
+    /// 转成 PNG；监控保存的可能是编码后的图片，也可能是原始 RGBA
+    pub fn to_png(&self) -> crate::error::Result<Vec<u8>> {
+        use crate::error::ClipManagerError;
+
+        let decoded = match image::load_from_memory(&self.data) {
+            Ok(_) if self.format == ImageFormat::Png => return Ok(self.data.clone()),
+            Ok(decoded) => decoded,
+            Err(_) => image::RgbaImage::from_raw(self.width, self.height, self.data.clone())
+                .map(image::DynamicImage::ImageRgba8)
+                .ok_or(ClipManagerError::UnsupportedContentType)?,
+        };
+
+        let mut png = Vec::new();
+        decoded
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .map_err(|e| ClipManagerError::Config {
+                message: format!("Failed to encode PNG: {}", e),
+            })?;
+        Ok(png)
+    }
 }
 
 impl ImageFormat {
@@ -187,7 +281,7 @@ impl ImageFormat {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct SearchFilter {
     pub query: String,
     pub content_type: Option<ContentType>,
@@ -195,14 +289,37 @@ pub struct SearchFilter {
     pub date_range: Option<(DateTime<Utc>, DateTime<Utc>)>,
 }
 
-impl Default for SearchFilter {
-    fn default() -> Self {
-        Self {
-            query: String::new(),
-            content_type: None,
-            favorites_only: false,
-            date_range: None,
+impl SearchFilter {
+    /// 判断条目是否满足过滤条件，与数据库查询的语义保持一致
+    pub fn matches(&self, item: &ClipboardItem) -> bool {
+        if !self.query.is_empty() {
+            // SQLite 的 LIKE 只对 ASCII 字符忽略大小写
+            let matched = item.get_text_content().is_some_and(|text| {
+                text.to_ascii_lowercase()
+                    .contains(&self.query.to_ascii_lowercase())
+            });
+            if !matched {
+                return false;
+            }
+        }
+
+        if let Some(content_type) = &self.content_type {
+            if &item.content_type != content_type {
+                return false;
+            }
+        }
+
+        if self.favorites_only && !item.is_favorite {
+            return false;
         }
+
+        if let Some((start, end)) = &self.date_range {
+            if item.created_at < *start || item.created_at > *end {
+                return false;
+            }
+        }
+
+        true
     }
 }
 
@@ -224,6 +341,9 @@ mod tests {
             accessed_at: Utc::now(),
             access_count: 0,
             is_favorite: false,
+            sensitive: None,
+            expires_at: None,
+            source: None,
         };
 
         // 测试不同长度的预览
@@ -237,9 +357,9 @@ mod tests {
         println!("Preview 50: {}", preview_50);
 
         // 验证预览不会panic
-        assert!(preview_10.len() > 0);
-        assert!(preview_25.len() > 0);
-        assert!(preview_50.len() > 0);
+        assert!(!preview_10.is_empty());
+        assert!(!preview_25.is_empty());
+        assert!(!preview_50.is_empty());
 
         // 验证短预览包含省略号
         assert!(preview_10.contains("..."));
@@ -262,6 +382,9 @@ mod tests {
             accessed_at: Utc::now(),
             access_count: 0,
             is_favorite: false,
+            sensitive: None,
+            expires_at: None,
+            source: None,
         };
 
         // 测试各种长度
@@ -270,7 +393,7 @@ mod tests {
             println!("Length {}: {}", length, preview);
 
             // 验证不会panic
-            assert!(preview.len() > 0);
+            assert!(!preview.is_empty());
 
             // 如果原文本比预览长度长，应该包含省略号
             if mixed_text.chars().count() > length {
@@ -296,6 +419,9 @@ mod tests {
             accessed_at: Utc::now(),
             access_count: 0,
             is_favorite: false,
+            sensitive: None,
+            expires_at: None,
+            source: None,
         };
 
         let empty_preview = empty_item.preview(10);
@@ -312,6 +438,9 @@ mod tests {
             accessed_at: Utc::now(),
             access_count: 0,
             is_favorite: false,
+            sensitive: None,
+            expires_at: None,
+            source: None,
         };
 
         let single_preview = single_char_item.preview(1);
@@ -319,4 +448,31 @@ mod tests {
 
         println!("✓ Edge cases preview test passed");
     }
+
+    #[test]
+    fn test_search_filter_matches() {
+        let mut item = ClipboardItem::new_text("Hello Rust World".to_string());
+
+        assert!(SearchFilter::default().matches(&item));
+
+        let query = SearchFilter {
+            query: "rust".to_string(),
+            ..Default::default()
+        };
+        assert!(query.matches(&item));
+
+        let image_only = SearchFilter {
+            content_type: Some(ContentType::Image),
+            ..Default::default()
+        };
+        assert!(!image_only.matches(&item));
+
+        let favorites = SearchFilter {
+            favorites_only: true,
+            ..Default::default()
+        };
+        assert!(!favorites.matches(&item));
+        item.is_favorite = true;
+        assert!(favorites.matches(&item));
+    }
 }
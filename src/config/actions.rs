@@ -0,0 +1,134 @@
+// 用户定义的动作：用外部命令转换条目内容
+
+use crate::config::keybindings::Accelerator;
+use serde::{Deserialize, Serialize};
+
+/// 一个外部命令，条目内容写入标准输入，标准输出作为结果
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommandAction {
+    /// 显示在菜单中的名称
+    pub name: String,
+    /// 可执行文件，不经过 shell
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// 超过此大小的内容不会交给命令
+    #[serde(default = "default_max_bytes")]
+    pub max_input_bytes: usize,
+    /// 输出超过此大小时终止命令
+    #[serde(default = "default_max_bytes")]
+    pub max_output_bytes: usize,
+    /// 主窗口中对选中条目运行的快捷键，为空表示不绑定
+    #[serde(default)]
+    pub shortcut: String,
+    /// 新捕获的文本命中此正则时自动运行，结果保存为新条目并放回剪切板；为空表示不自动运行
+    #[serde(default)]
+    pub auto_pattern: String,
+}
+
+fn default_timeout_secs() -> u64 {
+    5
+}
+
+fn default_max_bytes() -> usize {
+    1024 * 1024
+}
+
+impl CommandAction {
+    pub fn new(name: &str, command: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            command: command.to_string(),
+            args: Vec::new(),
+            timeout_secs: default_timeout_secs(),
+            max_input_bytes: default_max_bytes(),
+            max_output_bytes: default_max_bytes(),
+            shortcut: String::new(),
+            auto_pattern: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct ActionsConfig {
+    pub actions: Vec<CommandAction>,
+}
+
+impl ActionsConfig {
+    /// 检查所有动作，返回第一条错误
+    pub fn validate(&self) -> Result<(), String> {
+        for action in &self.actions {
+            if action.name.trim().is_empty() {
+                return Err("Every action needs a name".to_string());
+            }
+            if action.command.trim().is_empty() {
+                return Err(format!("Action '{}' has no command", action.name));
+            }
+            if action.timeout_secs == 0 {
+                return Err(format!("Action '{}' needs a timeout", action.name));
+            }
+            if action.max_input_bytes == 0 || action.max_output_bytes == 0 {
+                return Err(format!("Action '{}' needs size limits", action.name));
+            }
+            if !action.shortcut.is_empty() {
+                action
+                    .shortcut
+                    .parse::<Accelerator>()
+                    .map_err(|e| format!("Action '{}': {}", action.name, e))?;
+            }
+            if !action.auto_pattern.is_empty() {
+                regex::Regex::new(&action.auto_pattern)
+                    .map_err(|e| format!("Action '{}': {}", action.name, e))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_actions() {
+        let mut config = ActionsConfig {
+            actions: vec![CommandAction::new("Shorten", "shorten-url")],
+        };
+        assert!(config.validate().is_ok());
+
+        config.actions[0].shortcut = "Ctrl+Hyper+U".to_string();
+        assert!(config.validate().is_err());
+        config.actions[0].shortcut = "Ctrl+U".to_string();
+        config.actions[0].auto_pattern = "^https?://(".to_string();
+        assert!(config.validate().is_err());
+        config.actions[0].auto_pattern = "^https?://".to_string();
+        assert!(config.validate().is_ok());
+
+        config.actions[0].timeout_secs = 0;
+        assert!(config.validate().is_err());
+        config.actions[0].timeout_secs = 5;
+        config.actions[0].command = " ".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_defaults_when_missing() {
+        let config: ActionsConfig = toml::from_str(
+            r#"
+[[actions]]
+name = "Format"
+command = "jq"
+args = ["."]
+"#,
+        )
+        .unwrap();
+        let action = &config.actions[0];
+        assert_eq!(action.args, vec!["."]);
+        assert_eq!(action.timeout_secs, 5);
+        assert_eq!(action.max_output_bytes, 1024 * 1024);
+        assert!(action.auto_pattern.is_empty());
+    }
+}
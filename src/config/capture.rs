@@ -0,0 +1,76 @@
+// 按来源程序、窗口标题或内容决定是否记录的捕获规则
+
+use serde::{Deserialize, Serialize};
+
+/// 规则类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleKind {
+    /// 命中时不记录
+    #[default]
+    Deny,
+    /// 存在任意启用的允许规则时，只记录命中允许规则的内容
+    Allow,
+    /// 照常记录，但在 `ttl_secs` 秒后自动删除
+    Expire,
+}
+
+/// 规则匹配的字段
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleField {
+    /// 来源程序（WM_CLASS 或进程名），不区分大小写
+    #[default]
+    App,
+    /// 复制时的窗口标题，不区分大小写
+    Title,
+    /// 文本内容
+    Content,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CaptureRule {
+    /// 显示在设置中的名称
+    pub name: String,
+    #[serde(default)]
+    pub kind: RuleKind,
+    #[serde(default)]
+    pub field: RuleField,
+    /// 正则表达式
+    pub pattern: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 过期规则的存活时间（秒）
+    #[serde(default = "default_ttl_secs")]
+    pub ttl_secs: u64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_ttl_secs() -> u64 {
+    60
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct CaptureConfig {
+    pub rules: Vec<CaptureRule>,
+}
+
+impl CaptureConfig {
+    /// 检查所有规则的表达式，返回第一条错误
+    pub fn validate(&self) -> Result<(), String> {
+        for rule in &self.rules {
+            if rule.pattern.is_empty() {
+                return Err(format!("Rule '{}' has an empty pattern", rule.name));
+            }
+            if rule.kind == RuleKind::Expire && rule.ttl_secs == 0 {
+                return Err(format!("Rule '{}' needs a time to live", rule.name));
+            }
+            regex::Regex::new(&rule.pattern).map_err(|e| format!("Rule '{}': {}", rule.name, e))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,259 @@
+// 快捷键配置
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// 解析后的快捷键，例如 "Ctrl+Shift+V"
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Accelerator {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub super_key: bool,
+    /// 规范化后的按键名称，如 "V"、"Enter"、"F1"、"PageDown"
+    pub key: String,
+}
+
+impl Accelerator {
+    pub fn has_modifiers(&self) -> bool {
+        self.ctrl || self.shift || self.alt || self.super_key
+    }
+
+    /// 转换为 egui 的快捷键
+    ///
+    /// Super 只在 macOS 上对应 Cmd；其他平台 egui 不报告 Super 键，带 Super 的快捷键
+    /// 只能用作全局快捷键，这里返回 None。
+    pub fn to_egui(&self) -> Option<egui::KeyboardShortcut> {
+        if self.super_key && !cfg!(target_os = "macos") {
+            return None;
+        }
+        let key = egui::Key::from_name(&self.key)?;
+        let mut modifiers = egui::Modifiers::NONE;
+        if self.ctrl {
+            modifiers |= egui::Modifiers::CTRL;
+        }
+        if self.shift {
+            modifiers |= egui::Modifiers::SHIFT;
+        }
+        if self.alt {
+            modifiers |= egui::Modifiers::ALT;
+        }
+        if self.super_key {
+            modifiers |= egui::Modifiers::MAC_CMD;
+        }
+        Some(egui::KeyboardShortcut::new(modifiers, key))
+    }
+
+    fn normalize_key(key: &str) -> Option<String> {
+        let lower = key.to_ascii_lowercase();
+        let normalized = match lower.as_str() {
+            "enter" | "return" => "Enter",
+            "esc" | "escape" => "Escape",
+            "del" | "delete" => "Delete",
+            "backspace" => "Backspace",
+            "tab" => "Tab",
+            "space" => "Space",
+            "insert" | "ins" => "Insert",
+            "home" => "Home",
+            "end" => "End",
+            "pageup" | "pgup" | "prior" => "PageUp",
+            "pagedown" | "pgdn" | "next" => "PageDown",
+            "up" | "arrowup" => "ArrowUp",
+            "down" | "arrowdown" => "ArrowDown",
+            "left" | "arrowleft" => "ArrowLeft",
+            "right" | "arrowright" => "ArrowRight",
+            _ => {
+                let mut chars = key.chars();
+                return match (chars.next(), chars.next()) {
+                    // 单个字母或数字
+                    (Some(c), None) if c.is_ascii_alphanumeric() => {
+                        Some(c.to_ascii_uppercase().to_string())
+                    }
+                    // 功能键 F1-F24
+                    (Some('f' | 'F'), Some(_)) => {
+                        let number: u8 = key[1..].parse().ok()?;
+                        (1..=24).contains(&number).then(|| format!("F{}", number))
+                    }
+                    _ => None,
+                };
+            }
+        };
+        Some(normalized.to_string())
+    }
+}
+
+impl FromStr for Accelerator {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut accelerator = Accelerator {
+            ctrl: false,
+            shift: false,
+            alt: false,
+            super_key: false,
+            key: String::new(),
+        };
+
+        let parts: Vec<&str> = s.split('+').map(str::trim).collect();
+        let (key, modifiers) = parts
+            .split_last()
+            .filter(|(key, _)| !key.is_empty())
+            .ok_or_else(|| format!("Empty shortcut: '{}'", s))?;
+
+        for modifier in modifiers {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => accelerator.ctrl = true,
+                "shift" => accelerator.shift = true,
+                "alt" => accelerator.alt = true,
+                "super" | "win" | "meta" | "cmd" => accelerator.super_key = true,
+                other => return Err(format!("Unknown modifier '{}' in '{}'", other, s)),
+            }
+        }
+
+        accelerator.key =
+            Self::normalize_key(key).ok_or_else(|| format!("Unknown key '{}' in '{}'", key, s))?;
+        Ok(accelerator)
+    }
+}
+
+impl fmt::Display for Accelerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        if self.super_key {
+            write!(f, "Super+")?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+/// 主窗口键盘操作的按键绑定，每个操作可以绑定多个快捷键
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub focus_search: Vec<String>,
+    pub select_next: Vec<String>,
+    pub select_previous: Vec<String>,
+    pub page_down: Vec<String>,
+    pub page_up: Vec<String>,
+    pub select_first: Vec<String>,
+    pub select_last: Vec<String>,
+    pub copy_and_close: Vec<String>,
+    pub delete: Vec<String>,
+    pub toggle_favorite: Vec<String>,
+    /// 与数字 1-9 组合，快速复制前九个条目
+    pub quick_paste_modifier: String,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        fn keys(keys: &[&str]) -> Vec<String> {
+            keys.iter().map(|key| key.to_string()).collect()
+        }
+
+        Self {
+            focus_search: keys(&["Ctrl+F"]),
+            select_next: keys(&["Down"]),
+            select_previous: keys(&["Up"]),
+            page_down: keys(&["PageDown"]),
+            page_up: keys(&["PageUp"]),
+            select_first: keys(&["Home"]),
+            select_last: keys(&["End"]),
+            copy_and_close: keys(&["Enter"]),
+            delete: keys(&["Delete"]),
+            toggle_favorite: keys(&["F", "Ctrl+D"]),
+            quick_paste_modifier: "Ctrl".to_string(),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// 校验所有绑定，返回第一个无效或窗口内无法使用的快捷键
+    pub fn validate(&self) -> Result<(), String> {
+        let all = [
+            &self.focus_search,
+            &self.select_next,
+            &self.select_previous,
+            &self.page_down,
+            &self.page_up,
+            &self.select_first,
+            &self.select_last,
+            &self.copy_and_close,
+            &self.delete,
+            &self.toggle_favorite,
+        ];
+        let quick_paste = format!("{}+1", self.quick_paste_modifier);
+        for binding in all.into_iter().flatten().chain([&quick_paste]) {
+            let accelerator: Accelerator = binding.parse()?;
+            if accelerator.to_egui().is_none() {
+                return Err(format!(
+                    "'{}' cannot be used in the window (Super is only supported on macOS)",
+                    binding
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_accelerator() {
+        let accelerator: Accelerator = "Ctrl+Shift+V".parse().unwrap();
+        assert!(accelerator.ctrl);
+        assert!(accelerator.shift);
+        assert!(!accelerator.alt);
+        assert_eq!(accelerator.key, "V");
+        assert_eq!(accelerator.to_string(), "Ctrl+Shift+V");
+
+        let accelerator: Accelerator = "pgdn".parse().unwrap();
+        assert_eq!(accelerator.key, "PageDown");
+        assert!(!accelerator.has_modifiers());
+
+        let accelerator: Accelerator = "alt+f12".parse().unwrap();
+        assert_eq!(accelerator.to_string(), "Alt+F12");
+    }
+
+    #[test]
+    fn test_parse_invalid_accelerator() {
+        assert!("".parse::<Accelerator>().is_err());
+        assert!("Ctrl+".parse::<Accelerator>().is_err());
+        assert!("Hyper+V".parse::<Accelerator>().is_err());
+        assert!("Ctrl+Banana".parse::<Accelerator>().is_err());
+        assert!("F99".parse::<Accelerator>().is_err());
+    }
+
+    #[test]
+    fn test_super_only_in_window_on_macos() {
+        let accelerator: Accelerator = "Super+V".parse().unwrap();
+        assert!(accelerator.super_key);
+        assert_eq!(accelerator.to_egui().is_some(), cfg!(target_os = "macos"));
+
+        let bindings = KeyBindings {
+            delete: vec!["Super+Delete".to_string()],
+            ..KeyBindings::default()
+        };
+        assert_eq!(bindings.validate().is_ok(), cfg!(target_os = "macos"));
+    }
+
+    #[test]
+    fn test_default_bindings_are_valid() {
+        let bindings = KeyBindings::default();
+        assert!(bindings.validate().is_ok());
+        for binding in &bindings.toggle_favorite {
+            let accelerator: Accelerator = binding.parse().unwrap();
+            assert!(accelerator.to_egui().is_some());
+        }
+    }
+}
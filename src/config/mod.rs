@@ -1,3 +1,8 @@
+pub mod actions;
+pub mod capture;
+pub mod keybindings;
+pub mod paste;
+pub mod sensitive;
 pub mod settings;
 
 // 为后续版本预留
@@ -0,0 +1,130 @@
+// 自动粘贴配置
+
+use crate::config::keybindings::Accelerator;
+use serde::{Deserialize, Serialize};
+
+/// 发送粘贴按键的方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum PasteMethod {
+    /// 优先使用 XTest，不可用时执行外部命令
+    #[default]
+    Auto,
+    XTest,
+    /// 执行 `command`，适用于 Wayland（wtype、ydotool 等）
+    Command,
+}
+
+/// 针对特定应用的粘贴按键，例如终端使用 Ctrl+Shift+V
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PasteOverride {
+    /// 匹配窗口 WM_CLASS 中的类名（不区分大小写）
+    pub app: String,
+    pub keystroke: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PasteConfig {
+    /// 选择条目后自动粘贴到之前的窗口
+    pub paste_on_select: bool,
+    pub method: PasteMethod,
+    /// 外部粘贴命令，`{keys}` 会替换为 xdotool 风格的按键，如 `ctrl+shift+v`
+    pub command: String,
+    pub keystroke: String,
+    /// 隐藏窗口并恢复焦点后等待的时间
+    pub delay_ms: u64,
+    pub app_overrides: Vec<PasteOverride>,
+}
+
+impl Default for PasteConfig {
+    fn default() -> Self {
+        let terminal = |app: &str| PasteOverride {
+            app: app.to_string(),
+            keystroke: "Ctrl+Shift+V".to_string(),
+        };
+
+        Self {
+            paste_on_select: false,
+            method: PasteMethod::Auto,
+            command: "xdotool key --clearmodifiers {keys}".to_string(),
+            keystroke: "Ctrl+V".to_string(),
+            delay_ms: 150,
+            app_overrides: vec![
+                terminal("gnome-terminal"),
+                terminal("konsole"),
+                terminal("xterm"),
+                terminal("alacritty"),
+                terminal("kitty"),
+                terminal("tilix"),
+                terminal("xfce4-terminal"),
+            ],
+        }
+    }
+}
+
+impl PasteConfig {
+    /// 根据目标窗口的 WM_CLASS 选择粘贴按键
+    pub fn keystroke_for(&self, app_class: Option<&str>) -> &str {
+        app_class
+            .and_then(|class| {
+                self.app_overrides
+                    .iter()
+                    .find(|rule| rule.app.eq_ignore_ascii_case(class))
+            })
+            .map_or(&self.keystroke, |rule| &rule.keystroke)
+    }
+
+    /// 生成外部命令，按键以 xdotool 的格式代入
+    pub fn command_for(&self, keystroke: &Accelerator) -> String {
+        let mut keys = Vec::new();
+        if keystroke.ctrl {
+            keys.push("ctrl".to_string());
+        }
+        if keystroke.shift {
+            keys.push("shift".to_string());
+        }
+        if keystroke.alt {
+            keys.push("alt".to_string());
+        }
+        if keystroke.super_key {
+            keys.push("super".to_string());
+        }
+        keys.push(keystroke.key.to_ascii_lowercase());
+        self.command.replace("{keys}", &keys.join("+"))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        self.keystroke.parse::<Accelerator>()?;
+        for rule in &self.app_overrides {
+            rule.keystroke.parse::<Accelerator>()?;
+        }
+        if self.method == PasteMethod::Command && self.command.trim().is_empty() {
+            return Err("Paste command must not be empty".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keystroke_override() {
+        let config = PasteConfig::default();
+        assert_eq!(config.keystroke_for(Some("Konsole")), "Ctrl+Shift+V");
+        assert_eq!(config.keystroke_for(Some("firefox")), "Ctrl+V");
+        assert_eq!(config.keystroke_for(None), "Ctrl+V");
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_command_substitution() {
+        let config = PasteConfig {
+            command: "wtype-wrapper {keys}".to_string(),
+            ..Default::default()
+        };
+        let keystroke: Accelerator = "Ctrl+Shift+V".parse().unwrap();
+        assert_eq!(config.command_for(&keystroke), "wtype-wrapper ctrl+shift+v");
+    }
+}
@@ -0,0 +1,65 @@
+// 敏感内容检测配置
+
+use serde::{Deserialize, Serialize};
+
+/// 检测到敏感内容后的处理方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SensitiveAction {
+    /// 不处理，照常保存
+    Ignore,
+    /// 保存原文，到期后自动删除
+    Expire,
+    /// 只保存打码后的内容
+    #[default]
+    Mask,
+    /// 不保存
+    Skip,
+}
+
+/// 用户自定义的检测规则
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SensitivePattern {
+    /// 显示在条目标记上的名称
+    pub name: String,
+    /// 正则表达式
+    pub pattern: String,
+    #[serde(default)]
+    pub action: SensitiveAction,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SensitiveConfig {
+    pub enabled: bool,
+    /// PEM 私钥
+    pub private_keys: SensitiveAction,
+    /// 已知格式的 API 密钥与高熵令牌
+    pub api_keys: SensitiveAction,
+    pub jwts: SensitiveAction,
+    /// 通过 Luhn 校验的银行卡号
+    pub card_numbers: SensitiveAction,
+    /// 看起来像密码的单个短字符串
+    pub passwords: SensitiveAction,
+    /// 密码管理器通过剪切板格式标记为机密的内容，不受 `enabled` 影响
+    pub password_managers: SensitiveAction,
+    /// `Expire` 条目的保留时间（分钟）
+    pub expire_minutes: u64,
+    pub custom_patterns: Vec<SensitivePattern>,
+}
+
+impl Default for SensitiveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            private_keys: SensitiveAction::Skip,
+            api_keys: SensitiveAction::Mask,
+            jwts: SensitiveAction::Mask,
+            card_numbers: SensitiveAction::Mask,
+            passwords: SensitiveAction::Expire,
+            password_managers: SensitiveAction::Skip,
+            expire_minutes: 5,
+            custom_patterns: Vec::new(),
+        }
+    }
+}
@@ -1,5 +1,11 @@
 // 配置管理模块
 
+use crate::clipboard::types::ContentType;
+use crate::config::actions::ActionsConfig;
+use crate::config::capture::CaptureConfig;
+use crate::config::keybindings::KeyBindings;
+use crate::config::paste::PasteConfig;
+use crate::config::sensitive::SensitiveConfig;
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -15,6 +21,30 @@ pub struct AppConfig {
     pub window: WindowConfig,
     pub theme: ThemeConfig,
     pub font: FontConfig,
+    #[serde(default)]
+    pub keybindings: KeyBindings,
+    #[serde(default)]
+    pub paste: PasteConfig,
+    #[serde(default)]
+    pub popup: PopupConfig,
+    #[serde(default)]
+    pub tray: TrayConfig,
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    #[serde(default)]
+    pub http: HttpConfig,
+    #[serde(default)]
+    pub sensitive: SensitiveConfig,
+    #[serde(default)]
+    pub capture: CaptureConfig,
+    #[serde(default)]
+    pub private_mode: PrivateModeConfig,
+    #[serde(default)]
+    pub expiry: ExpiryConfig,
+    #[serde(default)]
+    pub lock: LockConfig,
+    #[serde(default)]
+    pub actions: ActionsConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,10 +52,11 @@ pub struct ThemeConfig {
     pub mode: ThemeMode,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub enum ThemeMode {
     Light,
     Dark,
+    #[default]
     System, // 跟随系统主题
 }
 
@@ -45,6 +76,99 @@ pub struct WindowConfig {
     pub start_minimized: bool,
 }
 
+/// 紧凑弹出选择器
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PopupConfig {
+    /// 全局快捷键打开弹出选择器而不是主窗口
+    pub enabled: bool,
+    /// 显示最近的条目数
+    pub max_items: usize,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// 系统托盘
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct TrayConfig {
+    /// 显示托盘图标，关闭窗口时隐藏到托盘
+    pub enabled: bool,
+    /// 托盘菜单中显示的最近条目数
+    pub recent_items: usize,
+}
+
+/// 桌面通知，总开关为 `show_notifications`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct NotificationConfig {
+    pub item_copied: bool,
+    pub recording_paused: bool,
+    pub sensitive_skipped: bool,
+    /// 保存或备份历史记录失败
+    pub storage_failed: bool,
+    /// 同一类通知的最短间隔（秒）
+    pub throttle_secs: u64,
+}
+
+/// 本地 HTTP 接口，只监听 127.0.0.1，访问令牌保存在配置目录中
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct HttpConfig {
+    pub enabled: bool,
+    pub port: u16,
+    /// 允许跨域访问的网页来源（如 `https://example.com`），默认不允许
+    pub allowed_origin: Option<String>,
+}
+
+/// 按内容类型自动过期，0 表示永不过期；捕获规则可以为命中的内容设置更短的时间
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ExpiryConfig {
+    pub text_minutes: u64,
+    pub image_minutes: u64,
+    /// 过期条目的内容仍在系统剪切板上时一并清除
+    pub clear_clipboard: bool,
+}
+
+impl ExpiryConfig {
+    /// 该类型条目的存活时间
+    pub fn ttl(&self, content_type: &ContentType) -> Option<chrono::Duration> {
+        let minutes = match content_type {
+            ContentType::Text => self.text_minutes,
+            ContentType::Image => self.image_minutes,
+        };
+        (minutes > 0).then(|| chrono::Duration::minutes(minutes as i64))
+    }
+}
+
+/// 隐私模式（暂停记录）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct PrivateModeConfig {
+    /// 重启后恢复暂停状态，“直到重启”的暂停不会保存
+    pub persist: bool,
+}
+
+/// 应用锁，设置口令后查看或搜索历史前需要解锁
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct LockConfig {
+    /// 口令的 Argon2id 哈希（PHC 格式），为空时不启用
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passphrase_hash: Option<String>,
+    /// 空闲多少分钟后自动锁定，0 表示不自动锁定
+    pub idle_minutes: u32,
+    /// 屏幕锁定时一并锁定
+    pub lock_with_screen: bool,
+}
+
+impl LockConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.passphrase_hash.is_some()
+    }
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -56,6 +180,18 @@ impl Default for AppConfig {
             window: WindowConfig::default(),
             theme: ThemeConfig::default(),
             font: FontConfig::default(),
+            keybindings: KeyBindings::default(),
+            paste: PasteConfig::default(),
+            popup: PopupConfig::default(),
+            tray: TrayConfig::default(),
+            notifications: NotificationConfig::default(),
+            http: HttpConfig::default(),
+            sensitive: SensitiveConfig::default(),
+            capture: CaptureConfig::default(),
+            private_mode: PrivateModeConfig::default(),
+            expiry: ExpiryConfig::default(),
+            lock: LockConfig::default(),
+            actions: ActionsConfig::default(),
         }
     }
 }
@@ -68,12 +204,6 @@ impl Default for ThemeConfig {
     }
 }
 
-impl Default for ThemeMode {
-    fn default() -> Self {
-        ThemeMode::System
-    }
-}
-
 impl Default for FontConfig {
     fn default() -> Self {
         Self {
@@ -107,6 +237,68 @@ impl Default for WindowConfig {
     }
 }
 
+impl Default for PopupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_items: 20,
+            width: 360.0,
+            height: 420.0,
+        }
+    }
+}
+
+impl Default for TrayConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            recent_items: 10,
+        }
+    }
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            item_copied: false,
+            recording_paused: true,
+            sensitive_skipped: true,
+            storage_failed: true,
+            throttle_secs: 10,
+        }
+    }
+}
+
+impl Default for ExpiryConfig {
+    fn default() -> Self {
+        Self {
+            text_minutes: 0,
+            image_minutes: 0,
+            clear_clipboard: true,
+        }
+    }
+}
+
+impl Default for LockConfig {
+    fn default() -> Self {
+        Self {
+            passphrase_hash: None,
+            idle_minutes: 5,
+            lock_with_screen: true,
+        }
+    }
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 7878,
+            allowed_origin: None,
+        }
+    }
+}
+
 impl AppConfig {
     pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
         let config_path = Self::config_file_path()?;
@@ -170,7 +362,7 @@ impl AppConfig {
         Ok(())
     }
 
-    fn config_file_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    pub fn config_file_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
         let project_dirs = ProjectDirs::from("com", "clipmanager", "ClipManager")
             .ok_or("Failed to get project directories")?;
 
@@ -208,8 +400,8 @@ impl AppConfig {
             show_notifications: old_config.show_notifications,
             hotkey: old_config.hotkey,
             window: old_config.window,
-            theme: ThemeConfig::default(), // 使用默认主题配置
-            font: FontConfig::default(),   // 使用默认字体配置
+            // 主题、字体等新增配置使用默认值
+            ..AppConfig::default()
         };
 
         log::info!("Migrated config: added default theme configuration");
@@ -255,6 +447,16 @@ mod tests {
         println!("✓ Theme mode variants test passed");
     }
 
+    #[test]
+    fn test_config_without_keybindings_uses_defaults() {
+        let mut value = toml::Value::try_from(AppConfig::default()).unwrap();
+        value.as_table_mut().unwrap().remove("keybindings");
+        let content = toml::to_string(&value).unwrap();
+
+        let config: AppConfig = toml::from_str(&content).unwrap();
+        assert_eq!(config.keybindings, KeyBindings::default());
+    }
+
     #[test]
     fn test_config_migration() {
         // 模拟旧版本的配置文件内容（没有theme字段）
@@ -278,13 +480,13 @@ start_minimized = true
         // 验证迁移后的配置
         assert_eq!(migrated_config.max_items, 200);
         assert_eq!(migrated_config.max_item_size, 2097152);
-        assert_eq!(migrated_config.auto_start, true);
-        assert_eq!(migrated_config.show_notifications, false);
+        assert!(migrated_config.auto_start);
+        assert!(!migrated_config.show_notifications);
         assert_eq!(migrated_config.hotkey, "Ctrl+Alt+V");
         assert_eq!(migrated_config.window.width, 800.0);
         assert_eq!(migrated_config.window.height, 600.0);
-        assert_eq!(migrated_config.window.always_on_top, true);
-        assert_eq!(migrated_config.window.start_minimized, true);
+        assert!(migrated_config.window.always_on_top);
+        assert!(migrated_config.window.start_minimized);
 
         // 验证默认主题配置被添加
         assert_eq!(migrated_config.theme.mode, ThemeMode::System);
@@ -328,13 +530,13 @@ start_minimized = false"#;
         // 验证迁移结果
         assert_eq!(migrated.max_items, 150);
         assert_eq!(migrated.max_item_size, 2048000);
-        assert_eq!(migrated.auto_start, true);
-        assert_eq!(migrated.show_notifications, false);
+        assert!(migrated.auto_start);
+        assert!(!migrated.show_notifications);
         assert_eq!(migrated.hotkey, "Ctrl+Alt+C");
         assert_eq!(migrated.window.width, 700.0);
         assert_eq!(migrated.window.height, 550.0);
-        assert_eq!(migrated.window.always_on_top, true);
-        assert_eq!(migrated.window.start_minimized, false);
+        assert!(migrated.window.always_on_top);
+        assert!(!migrated.window.start_minimized);
         assert_eq!(migrated.theme.mode, ThemeMode::System);
 
         // 清理
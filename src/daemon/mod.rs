@@ -0,0 +1,173 @@
+//! Headless capture daemon
+//!
+//! `clipmanager daemon` records the clipboard into the database without a
+//! window. Only one process may run the monitor for a database: the owner holds
+//! an exclusive lock next to the database file and serves the control socket
+//! and D-Bus interface (see [`crate::ipc`]). A GUI started while the daemon runs attaches to it and
+//! only reads the database. The daemon re-reads the configuration file when it
+//! changes, so settings saved from an attached GUI take effect without a restart.
+
+use crate::clipboard::handler::ClipboardHandler;
+use crate::config::settings::AppConfig;
+use crate::error::{ClipManagerError, Result};
+use crate::i18n::{self, TextKey};
+use crate::notify::{NotificationCategory, Notifier};
+use crate::storage::{Database, StorageEvent};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::{Duration, SystemTime};
+
+/// 检查配置文件是否被修改的间隔
+const CONFIG_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 数据库的监控锁，进程退出时由系统自动释放
+pub struct InstanceLock {
+    _file: File,
+}
+
+impl InstanceLock {
+    /// 获取监控锁；已被其他进程持有时返回 None
+    pub fn acquire(db_path: &Path) -> Result<Option<Self>> {
+        let path = Self::lock_path(db_path);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .read(true)
+            .write(true)
+            .open(&path)?;
+
+        match file.try_lock() {
+            Ok(()) => {
+                // 记录持有者的 PID，便于排查
+                file.set_len(0)?;
+                writeln!(file, "{}", std::process::id())?;
+                Ok(Some(Self { _file: file }))
+            }
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(e)) => Err(e.into()),
+        }
+    }
+
+    fn lock_path(db_path: &Path) -> PathBuf {
+        db_path.with_extension("lock")
+    }
+}
+
+/// 运行无窗口的记录进程，直到进程被结束
+pub fn run(config: &AppConfig, db_path: &Path) -> Result<()> {
+    let Some(_lock) = InstanceLock::acquire(db_path)? else {
+        return Err(ClipManagerError::Config {
+            message: format!(
+                "Another ClipManager instance is already recording to {}",
+                db_path.display()
+            ),
+        });
+    };
+
+    let mut handler = ClipboardHandler::new(Database::new(db_path)?)?;
+    let events = handler.subscribe();
+    apply_config(&handler, config)?;
+    handler.start_monitoring()?;
+    #[cfg(unix)]
+    let service = crate::ipc::Service::new(handler.database(), handler.monitor_control(), None);
+    #[cfg(unix)]
+    let _endpoints = {
+        service.set_limits(config.max_items, config.max_item_size);
+        service.set_capture_filter(handler.capture_filter());
+        crate::ipc::serve(&service, &config.http)
+    };
+    let mut notifier = Notifier::new(config);
+    log::info!("Daemon recording clipboard to {}", db_path.display());
+
+    let config_path = AppConfig::config_file_path().ok();
+    let mut config_modified = config_path.as_deref().and_then(modified_time);
+
+    // 数据库持有事件发送端，这里会一直阻塞
+    loop {
+        let event = match events.recv_timeout(CONFIG_CHECK_INTERVAL) {
+            Ok(event) => event,
+            Err(RecvTimeoutError::Timeout) => {
+                let modified = config_path.as_deref().and_then(modified_time);
+                if modified == config_modified {
+                    continue;
+                }
+                config_modified = modified;
+                match AppConfig::load() {
+                    Ok(config) => {
+                        log::info!("Configuration changed, reloading");
+                        if let Err(e) = apply_config(&handler, &config) {
+                            log::warn!("Failed to apply new configuration: {}", e);
+                        }
+                        #[cfg(unix)]
+                        service.set_limits(config.max_items, config.max_item_size);
+                        notifier = Notifier::new(&config);
+                    }
+                    Err(e) => log::warn!("Failed to reload configuration: {}", e),
+                }
+                continue;
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+        match event {
+            StorageEvent::ItemAdded(item) => {
+                log::debug!("Recorded item {:?}", item.id);
+            }
+            StorageEvent::WriteFailed(message) => {
+                log::error!("Failed to save clipboard item: {}", message);
+                notifier.notify(
+                    NotificationCategory::StorageFailed,
+                    i18n::t(TextKey::NotifyStorageFailed),
+                    &message,
+                );
+            }
+            StorageEvent::CaptureSkipped(label) => {
+                log::info!("Skipped sensitive clipboard item ({})", label);
+                notifier.notify(
+                    NotificationCategory::SensitiveSkipped,
+                    i18n::t(TextKey::NotifySensitiveSkipped),
+                    &label,
+                );
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// 把捕获相关的设置应用到记录进程，启动和配置文件变化时调用
+///
+/// 快捷键、HTTP 接口等需要重启才会生效。
+fn apply_config(handler: &ClipboardHandler, config: &AppConfig) -> Result<()> {
+    handler.set_sensitive_config(&config.sensitive);
+    handler.set_capture_rules(&config.capture);
+    handler.set_expiry_config(&config.expiry);
+    handler.set_actions(&config.actions);
+    handler.set_private_mode_persist(config.private_mode.persist);
+    handler.cleanup_with_config(config.max_items)
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instance_lock_is_exclusive() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("clipmanager.db");
+
+        let lock = InstanceLock::acquire(&db_path).unwrap();
+        assert!(lock.is_some());
+        assert!(InstanceLock::acquire(&db_path).unwrap().is_none());
+
+        drop(lock);
+        assert!(InstanceLock::acquire(&db_path).unwrap().is_some());
+    }
+}
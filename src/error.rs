@@ -17,6 +17,19 @@ pub enum ClipManagerError {
     #[error("Configuration error: {message}")]
     Config { message: String },
 
+    #[cfg(target_os = "linux")]
+    #[error("D-Bus error: {0}")]
+    DBus(#[from] zbus::Error),
+
+    #[error("IPC error {code}: {message}")]
+    Ipc { code: i64, message: String },
+
+    #[error("Platform integration error: {message}")]
+    Platform { message: String },
+
+    #[error("Action '{name}' failed: {message}")]
+    Action { name: String, message: String },
+
     #[error("Content too large: {size} bytes, maximum allowed {max_size} bytes")]
     ContentTooLarge { size: usize, max_size: usize },
 
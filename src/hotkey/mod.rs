@@ -0,0 +1,184 @@
+//! Global hotkey support
+//!
+//! The configured accelerator is grabbed on X11 via `XGrabKey`. On desktops
+//! that do not allow global grabs (Wayland, or when another client already owns
+//! the key) the user can bind `clipmanager toggle` in the desktop's own
+//! shortcut settings; that command reaches the running instance over IPC.
+
+#[cfg(target_os = "linux")]
+pub mod x11;
+
+use crate::config::keybindings::Accelerator;
+use std::sync::mpsc;
+
+/// 快捷键触发事件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HotkeyEvent {
+    /// 切换主窗口显示，附带触发时的鼠标位置（屏幕坐标）
+    Toggle { pointer: Option<(i32, i32)> },
+    /// 显示并聚焦主窗口
+    Show { pointer: Option<(i32, i32)> },
+    /// 打开紧凑弹出选择器
+    Popup { pointer: Option<(i32, i32)> },
+}
+
+/// 当前生效的快捷键后端
+#[derive(Debug, Clone, PartialEq)]
+pub enum HotkeyBackend {
+    /// 已通过 X11 抓取全局快捷键
+    X11Grab,
+    /// 无法注册全局快捷键，需要在桌面环境中绑定 `clipmanager toggle`
+    IpcFallback { reason: String },
+}
+
+/// 解析并校验全局快捷键
+///
+/// 除功能键外，全局快捷键必须带有修饰键，否则会吞掉普通输入。
+pub fn validate(hotkey: &str) -> Result<Accelerator, String> {
+    let accelerator: Accelerator = hotkey.parse()?;
+    let is_function_key = accelerator.key.starts_with('F') && accelerator.key.len() > 1;
+    if !accelerator.has_modifiers() && !is_function_key {
+        return Err(format!(
+            "Global hotkey '{}' needs at least one modifier (Ctrl, Shift, Alt or Super)",
+            hotkey
+        ));
+    }
+    if keysym(&accelerator.key).is_none() {
+        return Err(format!(
+            "Key '{}' cannot be used as a global hotkey",
+            accelerator.key
+        ));
+    }
+    Ok(accelerator)
+}
+
+/// 已注册的全局快捷键，释放后取消注册
+pub struct HotkeyRegistration {
+    backend: HotkeyBackend,
+    #[cfg(target_os = "linux")]
+    _grab: Option<x11::X11Grab>,
+}
+
+impl HotkeyRegistration {
+    pub fn backend(&self) -> &HotkeyBackend {
+        &self.backend
+    }
+
+    fn fallback(reason: String) -> Self {
+        Self {
+            backend: HotkeyBackend::IpcFallback { reason },
+            #[cfg(target_os = "linux")]
+            _grab: None,
+        }
+    }
+}
+
+/// 注册全局快捷键，失败时回退到 IPC 命令
+///
+/// `on_event` 在事件发送后调用，用于唤醒 UI。
+pub fn register(
+    hotkey: &str,
+    sender: mpsc::Sender<HotkeyEvent>,
+    on_event: impl Fn() + Send + 'static,
+) -> HotkeyRegistration {
+    let accelerator = match validate(hotkey) {
+        Ok(accelerator) => accelerator,
+        Err(e) => return HotkeyRegistration::fallback(e),
+    };
+
+    #[cfg(target_os = "linux")]
+    {
+        match x11::grab(&accelerator, sender, on_event) {
+            Ok(grab) => {
+                log::info!("Registered global hotkey {} via XGrabKey", accelerator);
+                HotkeyRegistration {
+                    backend: HotkeyBackend::X11Grab,
+                    _grab: Some(grab),
+                }
+            }
+            Err(reason) => {
+                log::warn!("Global hotkey unavailable: {}", reason);
+                HotkeyRegistration::fallback(reason)
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (accelerator, sender, on_event);
+        HotkeyRegistration::fallback(
+            "Global hotkeys are not supported on this platform".to_string(),
+        )
+    }
+}
+
+/// 当前鼠标位置（屏幕坐标），无法获取时返回 None
+pub fn pointer_position() -> Option<(i32, i32)> {
+    #[cfg(target_os = "linux")]
+    {
+        x11::pointer_position()
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// 将规范化的按键名称映射为 X11 keysym
+pub fn keysym(key: &str) -> Option<u32> {
+    let sym = match key {
+        "Enter" => 0xff0d,
+        "Escape" => 0xff1b,
+        "Delete" => 0xffff,
+        "Backspace" => 0xff08,
+        "Tab" => 0xff09,
+        "Space" => 0x0020,
+        "Insert" => 0xff63,
+        "Home" => 0xff50,
+        "End" => 0xff57,
+        "PageUp" => 0xff55,
+        "PageDown" => 0xff56,
+        "ArrowLeft" => 0xff51,
+        "ArrowUp" => 0xff52,
+        "ArrowRight" => 0xff53,
+        "ArrowDown" => 0xff54,
+        _ => {
+            let mut chars = key.chars();
+            return match (chars.next(), chars.next()) {
+                // 字母使用小写 keysym
+                (Some(c), None) if c.is_ascii_alphabetic() => Some(c.to_ascii_lowercase() as u32),
+                (Some(c), None) if c.is_ascii_digit() => Some(c as u32),
+                (Some('F'), Some(_)) => {
+                    let number: u32 = key[1..].parse().ok()?;
+                    (1..=24).contains(&number).then(|| 0xffbe + number - 1)
+                }
+                _ => None,
+            };
+        }
+    };
+    Some(sym)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_hotkey() {
+        assert!(validate("Ctrl+Shift+V").is_ok());
+        assert!(validate("F12").is_ok());
+        assert!(validate("V").is_err());
+        assert!(validate("Ctrl+Nope").is_err());
+    }
+
+    #[test]
+    fn test_keysym_mapping() {
+        assert_eq!(keysym("V"), Some(0x76));
+        assert_eq!(keysym("7"), Some(0x37));
+        assert_eq!(keysym("F1"), Some(0xffbe));
+        assert_eq!(keysym("F12"), Some(0xffc9));
+        assert_eq!(keysym("PageDown"), Some(0xff56));
+        assert_eq!(keysym("Unknown"), None);
+    }
+}
@@ -0,0 +1,180 @@
+// X11 全局快捷键（XGrabKey）
+
+use super::{keysym, HotkeyEvent};
+use crate::config::keybindings::Accelerator;
+use std::sync::{mpsc, Arc};
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{
+    ClientMessageEvent, ConnectionExt, CreateWindowAux, EventMask, GrabMode, ModMask, WindowClass,
+    CLIENT_MESSAGE_EVENT,
+};
+use x11rb::protocol::Event;
+use x11rb::rust_connection::RustConnection;
+use x11rb::COPY_DEPTH_FROM_PARENT;
+
+/// 已注册的 X11 快捷键，释放时取消抓取并结束监听线程
+pub struct X11Grab {
+    conn: Arc<RustConnection>,
+    root: u32,
+    keycode: u8,
+    modifiers: Vec<ModMask>,
+    /// 用于唤醒监听线程的隐藏窗口
+    wake_window: u32,
+}
+
+/// 在根窗口上抓取快捷键，并在后台线程中等待按键事件
+///
+/// 同时抓取 CapsLock / NumLock 组合，否则开启这些锁定键后快捷键会失效。
+pub fn grab(
+    accelerator: &Accelerator,
+    sender: mpsc::Sender<HotkeyEvent>,
+    on_event: impl Fn() + Send + 'static,
+) -> Result<X11Grab, String> {
+    let (conn, screen_num) =
+        x11rb::connect(None).map_err(|e| format!("Cannot connect to X server: {}", e))?;
+    let conn = Arc::new(conn);
+    let root = conn.setup().roots[screen_num].root;
+
+    let sym =
+        keysym(&accelerator.key).ok_or_else(|| format!("Unsupported key '{}'", accelerator.key))?;
+    let keycode = find_keycode(conn.as_ref(), sym)?.ok_or_else(|| {
+        format!(
+            "Key '{}' is not on the current keyboard layout",
+            accelerator.key
+        )
+    })?;
+
+    let mut base = ModMask::from(0u16);
+    if accelerator.ctrl {
+        base |= ModMask::CONTROL;
+    }
+    if accelerator.shift {
+        base |= ModMask::SHIFT;
+    }
+    if accelerator.alt {
+        base |= ModMask::M1;
+    }
+    if accelerator.super_key {
+        base |= ModMask::M4;
+    }
+    let modifiers: Vec<ModMask> = [
+        ModMask::from(0u16),
+        ModMask::LOCK,
+        ModMask::M2,
+        ModMask::LOCK | ModMask::M2,
+    ]
+    .into_iter()
+    .map(|extra| base | extra)
+    .collect();
+
+    for &modifier in &modifiers {
+        conn.grab_key(
+            false,
+            root,
+            modifier,
+            keycode,
+            GrabMode::ASYNC,
+            GrabMode::ASYNC,
+        )
+        .map_err(|e| e.to_string())?
+        .check()
+        .map_err(|e| {
+            format!(
+                "{} is already grabbed by another application ({:?})",
+                accelerator, e
+            )
+        })?;
+    }
+
+    let wake_window = conn.generate_id().map_err(|e| e.to_string())?;
+    conn.create_window(
+        COPY_DEPTH_FROM_PARENT,
+        wake_window,
+        root,
+        0,
+        0,
+        1,
+        1,
+        0,
+        WindowClass::INPUT_ONLY,
+        0,
+        &CreateWindowAux::new(),
+    )
+    .map_err(|e| e.to_string())?;
+    conn.flush().map_err(|e| e.to_string())?;
+
+    let thread_conn = Arc::clone(&conn);
+    std::thread::spawn(move || loop {
+        match thread_conn.wait_for_event() {
+            Ok(Event::KeyPress(event)) if event.detail == keycode => {
+                let pointer = Some((event.root_x as i32, event.root_y as i32));
+                if sender.send(HotkeyEvent::Toggle { pointer }).is_err() {
+                    break;
+                }
+                on_event();
+            }
+            Ok(Event::ClientMessage(event)) if event.window == wake_window => break,
+            Ok(_) => {}
+            Err(e) => {
+                log::error!("X11 hotkey connection closed: {}", e);
+                break;
+            }
+        }
+    });
+
+    Ok(X11Grab {
+        conn,
+        root,
+        keycode,
+        modifiers,
+        wake_window,
+    })
+}
+
+impl Drop for X11Grab {
+    fn drop(&mut self) {
+        for &modifier in &self.modifiers {
+            let _ = self.conn.ungrab_key(self.keycode, self.root, modifier);
+        }
+        // 发送给自己创建的窗口的事件会回到本连接，借此唤醒阻塞的监听线程
+        let wake = ClientMessageEvent {
+            response_type: CLIENT_MESSAGE_EVENT,
+            format: 32,
+            sequence: 0,
+            window: self.wake_window,
+            type_: 0,
+            data: [0u32; 5].into(),
+        };
+        let _ = self
+            .conn
+            .send_event(false, self.wake_window, EventMask::NO_EVENT, wake);
+        let _ = self.conn.destroy_window(self.wake_window);
+        let _ = self.conn.flush();
+    }
+}
+
+/// 查询鼠标在屏幕上的位置
+pub fn pointer_position() -> Option<(i32, i32)> {
+    let (conn, screen_num) = x11rb::connect(None).ok()?;
+    let root = conn.setup().roots[screen_num].root;
+    let reply = conn.query_pointer(root).ok()?.reply().ok()?;
+    Some((reply.root_x as i32, reply.root_y as i32))
+}
+
+pub(crate) fn find_keycode(conn: &impl Connection, sym: u32) -> Result<Option<u8>, String> {
+    let setup = conn.setup();
+    let min = setup.min_keycode;
+    let count = setup.max_keycode - min + 1;
+    let mapping = conn
+        .get_keyboard_mapping(min, count)
+        .map_err(|e| e.to_string())?
+        .reply()
+        .map_err(|e| e.to_string())?;
+
+    let per_keycode = mapping.keysyms_per_keycode.max(1) as usize;
+    Ok(mapping
+        .keysyms
+        .chunks(per_keycode)
+        .position(|syms| syms.contains(&sym))
+        .map(|index| min + index as u8))
+}
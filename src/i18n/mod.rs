@@ -8,18 +8,13 @@ pub mod texts;
 use std::collections::HashMap;
 
 /// Language identifier
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Language {
+    #[default]
     English,
     Chinese,
 }
 
-impl Default for Language {
-    fn default() -> Self {
-        Language::English
-    }
-}
-
 /// Text key identifier for internationalization
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TextKey {
@@ -38,6 +33,7 @@ pub enum TextKey {
     // Item List
     NoRecords,
     RecordsCount,
+    SkippedCount,
     CharactersCount,
     UsedTimes,
 
@@ -70,6 +66,77 @@ pub enum TextKey {
     // Context Menu
     ContextCopy,
     ContextDelete,
+    ExpireIn,
+    ExpireIn1Minute,
+    ExpireIn10Minutes,
+    ExpireIn1Hour,
+    ExpireIn1Day,
+    ExpireNever,
+    AppLocked,
+    Passphrase,
+    Unlock,
+    WrongPassphrase,
+    VerifyingPassphrase,
+    LockMisconfigured,
+    LockNow,
+    TransformMenu,
+    TransformCopy,
+    TransformSave,
+    TransformTrim,
+    TransformCollapseWhitespace,
+    TransformUppercase,
+    TransformLowercase,
+    TransformTitleCase,
+    TransformSnakeCase,
+    TransformCamelCase,
+    TransformJsonPretty,
+    TransformJsonMinify,
+    TransformBase64Encode,
+    TransformBase64Decode,
+    TransformUrlEncode,
+    TransformUrlDecode,
+    TransformJsonEscape,
+    TransformJsonUnescape,
+    TransformShellEscape,
+    TransformShellUnescape,
+    TransformSortLines,
+    TransformDedupeLines,
+    TransformStripFormatting,
+    ActionsMenu,
+    Snippets,
+    SnippetInsert,
+    Cancel,
+
+    // Preview
+    Preview,
+    NoSelection,
+    SensitiveHidden,
+    RevealSensitive,
+    HideSensitive,
+
+    // Private mode
+    PrivateMode,
+    PrivateModeActive,
+    PrivateModeResumesAt,
+    PrivateModeUntilRestart,
+    PauseUntilResumed,
+    PauseFor5Minutes,
+    PauseFor1Hour,
+    PauseUntilRestart,
+    ResumeRecording,
+
+    // Tray
+    TrayOpenWindow,
+    TrayPauseRecording,
+    TrayQuit,
+    TrayRecentEmpty,
+
+    // Notifications
+    NotifyItemCopied,
+    NotifyRecordingPaused,
+    NotifyRecordingResumed,
+    NotifySensitiveSkipped,
+    NotifyStorageFailed,
 }
 
 /// Internationalization manager
@@ -142,7 +209,7 @@ pub fn t(key: TextKey) -> &'static str {
             init();
         }
 
-        GLOBAL_I18N
+        (*std::ptr::addr_of!(GLOBAL_I18N))
             .as_ref()
             .map(|i18n| i18n.text(key))
             .unwrap_or("[I18N NOT INITIALIZED]")
@@ -169,7 +236,7 @@ pub fn get_language() -> Language {
             init();
         }
 
-        GLOBAL_I18N
+        (*std::ptr::addr_of!(GLOBAL_I18N))
             .as_ref()
             .map(|i18n| i18n.get_language())
             .unwrap_or_default()
@@ -22,6 +22,7 @@ pub fn get_english_texts() -> HashMap<TextKey, &'static str> {
     // Item List
     texts.insert(TextKey::NoRecords, "No clipboard history records");
     texts.insert(TextKey::RecordsCount, "records");
+    texts.insert(TextKey::SkippedCount, "secrets not saved");
     texts.insert(TextKey::CharactersCount, "characters");
     texts.insert(TextKey::UsedTimes, "times used");
 
@@ -58,9 +59,95 @@ pub fn get_english_texts() -> HashMap<TextKey, &'static str> {
     // Context Menu
     texts.insert(TextKey::ContextCopy, "Copy");
     texts.insert(TextKey::ContextDelete, "Delete");
+    texts.insert(TextKey::ExpireIn, "Expire in");
+    texts.insert(TextKey::ExpireIn1Minute, "1 minute");
+    texts.insert(TextKey::ExpireIn10Minutes, "10 minutes");
+    texts.insert(TextKey::ExpireIn1Hour, "1 hour");
+    texts.insert(TextKey::ExpireIn1Day, "1 day");
+    texts.insert(TextKey::ExpireNever, "Never expire");
+    texts.insert(TextKey::AppLocked, "ClipManager is locked");
+    texts.insert(TextKey::Passphrase, "Passphrase");
+    texts.insert(TextKey::Unlock, "Unlock");
+    texts.insert(TextKey::WrongPassphrase, "Wrong passphrase");
+    texts.insert(TextKey::VerifyingPassphrase, "Verifying…");
+    texts.insert(
+        TextKey::LockMisconfigured,
+        "The passphrase hash in the config file is invalid",
+    );
+    texts.insert(TextKey::LockNow, "Lock now");
+    texts.insert(TextKey::TransformMenu, "Transform");
+    texts.insert(TextKey::TransformCopy, "Copy result");
+    texts.insert(TextKey::TransformSave, "Save as new item");
+    texts.insert(TextKey::TransformTrim, "Trim whitespace");
+    texts.insert(TextKey::TransformCollapseWhitespace, "Collapse whitespace");
+    texts.insert(TextKey::TransformUppercase, "UPPERCASE");
+    texts.insert(TextKey::TransformLowercase, "lowercase");
+    texts.insert(TextKey::TransformTitleCase, "Title Case");
+    texts.insert(TextKey::TransformSnakeCase, "snake_case");
+    texts.insert(TextKey::TransformCamelCase, "camelCase");
+    texts.insert(TextKey::TransformJsonPretty, "Format JSON");
+    texts.insert(TextKey::TransformJsonMinify, "Minify JSON");
+    texts.insert(TextKey::TransformBase64Encode, "Base64 encode");
+    texts.insert(TextKey::TransformBase64Decode, "Base64 decode");
+    texts.insert(TextKey::TransformUrlEncode, "URL encode");
+    texts.insert(TextKey::TransformUrlDecode, "URL decode");
+    texts.insert(TextKey::TransformJsonEscape, "JSON escape");
+    texts.insert(TextKey::TransformJsonUnescape, "JSON unescape");
+    texts.insert(TextKey::TransformShellEscape, "Shell escape");
+    texts.insert(TextKey::TransformShellUnescape, "Shell unescape");
+    texts.insert(TextKey::TransformSortLines, "Sort lines");
+    texts.insert(TextKey::TransformDedupeLines, "Remove duplicate lines");
+    texts.insert(TextKey::TransformStripFormatting, "Strip formatting");
+    texts.insert(TextKey::ActionsMenu, "Actions");
+    texts.insert(TextKey::Snippets, "Snippets");
+    texts.insert(TextKey::SnippetInsert, "Insert");
+    texts.insert(TextKey::Cancel, "Cancel");
     texts.insert(TextKey::Favorite, "Favorite");
     texts.insert(TextKey::Unfavorite, "Unfavorite");
 
+    // Preview
+    texts.insert(TextKey::Preview, "Preview");
+    texts.insert(TextKey::NoSelection, "Select an item to preview");
+    texts.insert(
+        TextKey::SensitiveHidden,
+        "This item looks sensitive and is hidden",
+    );
+    texts.insert(TextKey::RevealSensitive, "Reveal");
+    texts.insert(TextKey::HideSensitive, "Hide");
+
+    // Private mode
+    texts.insert(TextKey::PrivateMode, "Private mode");
+    texts.insert(
+        TextKey::PrivateModeActive,
+        "Private mode: the clipboard is not being recorded",
+    );
+    texts.insert(TextKey::PrivateModeResumesAt, "Resumes at");
+    texts.insert(TextKey::PrivateModeUntilRestart, "Until restart");
+    texts.insert(TextKey::PauseUntilResumed, "Pause until resumed");
+    texts.insert(TextKey::PauseFor5Minutes, "Pause for 5 minutes");
+    texts.insert(TextKey::PauseFor1Hour, "Pause for 1 hour");
+    texts.insert(TextKey::PauseUntilRestart, "Pause until restart");
+    texts.insert(TextKey::ResumeRecording, "Resume recording");
+
+    // Tray
+    texts.insert(TextKey::TrayOpenWindow, "Open ClipManager");
+    texts.insert(TextKey::TrayPauseRecording, "Pause recording");
+    texts.insert(TextKey::TrayQuit, "Quit");
+    texts.insert(TextKey::TrayRecentEmpty, "No recent items");
+
+    // Notifications
+    texts.insert(TextKey::NotifyItemCopied, "Copied to clipboard");
+    texts.insert(TextKey::NotifyRecordingPaused, "Clipboard recording paused");
+    texts.insert(
+        TextKey::NotifyRecordingResumed,
+        "Clipboard recording resumed",
+    );
+    texts.insert(TextKey::NotifySensitiveSkipped, "Sensitive item not saved");
+    texts.insert(
+        TextKey::NotifyStorageFailed,
+        "Failed to save clipboard history",
+    );
+
     texts
 }
 
@@ -83,6 +170,7 @@ pub fn get_chinese_texts() -> HashMap<TextKey, &'static str> {
     // Item List
     texts.insert(TextKey::NoRecords, "暂无剪切板历史记录");
     texts.insert(TextKey::RecordsCount, "条记录");
+    texts.insert(TextKey::SkippedCount, "条机密内容未保存");
     texts.insert(TextKey::CharactersCount, "字符");
     texts.insert(TextKey::UsedTimes, "次使用");
 
@@ -118,6 +206,77 @@ pub fn get_chinese_texts() -> HashMap<TextKey, &'static str> {
     // Context Menu
     texts.insert(TextKey::ContextCopy, "复制");
     texts.insert(TextKey::ContextDelete, "删除");
+    texts.insert(TextKey::ExpireIn, "过期时间");
+    texts.insert(TextKey::ExpireIn1Minute, "1 分钟");
+    texts.insert(TextKey::ExpireIn10Minutes, "10 分钟");
+    texts.insert(TextKey::ExpireIn1Hour, "1 小时");
+    texts.insert(TextKey::ExpireIn1Day, "1 天");
+    texts.insert(TextKey::ExpireNever, "永不过期");
+    texts.insert(TextKey::AppLocked, "ClipManager 已锁定");
+    texts.insert(TextKey::Passphrase, "口令");
+    texts.insert(TextKey::Unlock, "解锁");
+    texts.insert(TextKey::WrongPassphrase, "口令错误");
+    texts.insert(TextKey::VerifyingPassphrase, "正在校验…");
+    texts.insert(TextKey::LockMisconfigured, "配置文件中的口令哈希无效");
+    texts.insert(TextKey::LockNow, "立即锁定");
+    texts.insert(TextKey::TransformMenu, "转换");
+    texts.insert(TextKey::TransformCopy, "复制结果");
+    texts.insert(TextKey::TransformSave, "保存为新条目");
+    texts.insert(TextKey::TransformTrim, "去除首尾空白");
+    texts.insert(TextKey::TransformCollapseWhitespace, "合并空白");
+    texts.insert(TextKey::TransformUppercase, "大写");
+    texts.insert(TextKey::TransformLowercase, "小写");
+    texts.insert(TextKey::TransformTitleCase, "首字母大写");
+    texts.insert(TextKey::TransformSnakeCase, "蛇形命名 snake_case");
+    texts.insert(TextKey::TransformCamelCase, "驼峰命名 camelCase");
+    texts.insert(TextKey::TransformJsonPretty, "格式化 JSON");
+    texts.insert(TextKey::TransformJsonMinify, "压缩 JSON");
+    texts.insert(TextKey::TransformBase64Encode, "Base64 编码");
+    texts.insert(TextKey::TransformBase64Decode, "Base64 解码");
+    texts.insert(TextKey::TransformUrlEncode, "URL 编码");
+    texts.insert(TextKey::TransformUrlDecode, "URL 解码");
+    texts.insert(TextKey::TransformJsonEscape, "JSON 转义");
+    texts.insert(TextKey::TransformJsonUnescape, "JSON 反转义");
+    texts.insert(TextKey::TransformShellEscape, "Shell 转义");
+    texts.insert(TextKey::TransformShellUnescape, "Shell 反转义");
+    texts.insert(TextKey::TransformSortLines, "按行排序");
+    texts.insert(TextKey::TransformDedupeLines, "删除重复行");
+    texts.insert(TextKey::TransformStripFormatting, "清除格式");
+    texts.insert(TextKey::ActionsMenu, "动作");
+    texts.insert(TextKey::Snippets, "片段");
+    texts.insert(TextKey::SnippetInsert, "插入");
+    texts.insert(TextKey::Cancel, "取消");
+
+    // Preview
+    texts.insert(TextKey::Preview, "预览");
+    texts.insert(TextKey::NoSelection, "选择条目以预览");
+    texts.insert(TextKey::SensitiveHidden, "该条目可能包含敏感信息，已隐藏");
+    texts.insert(TextKey::RevealSensitive, "显示");
+    texts.insert(TextKey::HideSensitive, "隐藏");
+
+    // Private mode
+    texts.insert(TextKey::PrivateMode, "隐私模式");
+    texts.insert(TextKey::PrivateModeActive, "隐私模式：剪切板暂不记录");
+    texts.insert(TextKey::PrivateModeResumesAt, "恢复时间");
+    texts.insert(TextKey::PrivateModeUntilRestart, "直到重启");
+    texts.insert(TextKey::PauseUntilResumed, "暂停直到手动恢复");
+    texts.insert(TextKey::PauseFor5Minutes, "暂停 5 分钟");
+    texts.insert(TextKey::PauseFor1Hour, "暂停 1 小时");
+    texts.insert(TextKey::PauseUntilRestart, "暂停直到重启");
+    texts.insert(TextKey::ResumeRecording, "恢复记录");
+
+    // Tray
+    texts.insert(TextKey::TrayOpenWindow, "打开 ClipManager");
+    texts.insert(TextKey::TrayPauseRecording, "暂停记录");
+    texts.insert(TextKey::TrayQuit, "退出");
+    texts.insert(TextKey::TrayRecentEmpty, "暂无最近条目");
+
+    // Notifications
+    texts.insert(TextKey::NotifyItemCopied, "已复制到剪切板");
+    texts.insert(TextKey::NotifyRecordingPaused, "已暂停记录剪切板");
+    texts.insert(TextKey::NotifyRecordingResumed, "已恢复记录剪切板");
+    texts.insert(TextKey::NotifySensitiveSkipped, "敏感内容未保存");
+    texts.insert(TextKey::NotifyStorageFailed, "保存剪切板历史失败");
 
     texts
 }
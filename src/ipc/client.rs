@@ -0,0 +1,229 @@
+// 控制套接字客户端，供命令行、编辑器插件和快捷键脚本使用
+
+use super::protocol::{
+    base64_decode, Event, ItemInfo, ListParams, Message, Request, WindowMode, PROTOCOL_VERSION,
+};
+use super::socket_path;
+use crate::clipboard::private_mode::{PrivateDuration, PrivateStatus};
+use crate::error::{ClipManagerError, Result};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::time::Duration;
+
+/// 等待回复的超时时间
+const REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `get` 的结果，图片附带 PNG 数据
+#[derive(Debug, Clone)]
+pub struct ItemContent {
+    pub info: ItemInfo,
+    pub data: Option<Vec<u8>>,
+}
+
+pub struct Client {
+    reader: BufReader<UnixStream>,
+    writer: UnixStream,
+    next_id: u64,
+    /// 等待回复期间收到的事件
+    pending_events: VecDeque<Event>,
+}
+
+impl Client {
+    /// 连接默认控制套接字
+    pub fn connect() -> Result<Self> {
+        Self::connect_to(&socket_path())
+    }
+
+    /// 连接并确认协议版本
+    pub fn connect_to(path: &Path) -> Result<Self> {
+        let stream = UnixStream::connect(path)?;
+        stream.set_read_timeout(Some(REPLY_TIMEOUT))?;
+        let mut client = Self {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+            next_id: 1,
+            pending_events: VecDeque::new(),
+        };
+
+        let hello = client.call("hello", json!({ "version": PROTOCOL_VERSION }))?;
+        let version = hello["version"].as_u64().unwrap_or_default();
+        if version != PROTOCOL_VERSION as u64 {
+            return Err(ClipManagerError::Ipc {
+                code: super::protocol::UNSUPPORTED_VERSION,
+                message: format!(
+                    "Server speaks protocol version {}, expected {}",
+                    version, PROTOCOL_VERSION
+                ),
+            });
+        }
+        Ok(client)
+    }
+
+    /// 发送请求并等待对应的回复
+    pub fn call(&mut self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let mut line = serde_json::to_string(&Request::new(id, method, params))?;
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())?;
+
+        loop {
+            let message = self.read_message()?;
+            if message.method.as_deref() == Some("event") {
+                if let Some(event) = Self::parse_event(message) {
+                    self.pending_events.push_back(event);
+                }
+                continue;
+            }
+            if message.id != Some(Value::from(id)) {
+                continue;
+            }
+            return match message.error {
+                Some(error) => Err(ClipManagerError::Ipc {
+                    code: error.code,
+                    message: error.message,
+                }),
+                None => Ok(message.result.unwrap_or(Value::Null)),
+            };
+        }
+    }
+
+    fn call_as<T: DeserializeOwned>(&mut self, method: &str, params: Value) -> Result<T> {
+        Ok(serde_json::from_value(self.call(method, params)?)?)
+    }
+
+    pub fn list(&mut self, params: &ListParams) -> Result<Vec<ItemInfo>> {
+        self.call_as("list", serde_json::to_value(params)?)
+    }
+
+    pub fn search(&mut self, query: &str, limit: usize) -> Result<Vec<ItemInfo>> {
+        self.call_as("search", json!({ "query": query, "limit": limit }))
+    }
+
+    /// 读取条目，`reveal` 为 true 时包含敏感条目的文本
+    pub fn get(&mut self, id: i64, reveal: bool) -> Result<ItemContent> {
+        let value = self.call("get", json!({ "id": id, "reveal": reveal }))?;
+        let data = match value["data"].as_str() {
+            Some(data) => Some(base64_decode(data).ok_or_else(|| ClipManagerError::Ipc {
+                code: super::protocol::INTERNAL_ERROR,
+                message: "Server sent invalid image data".to_string(),
+            })?),
+            None => None,
+        };
+        Ok(ItemContent {
+            info: serde_json::from_value(value)?,
+            data,
+        })
+    }
+
+    pub fn copy(&mut self, id: i64) -> Result<()> {
+        self.call("copy", json!({ "id": id })).map(|_| ())
+    }
+
+    /// 添加文本条目，返回条目 ID
+    pub fn add_text(&mut self, text: &str) -> Result<i64> {
+        let result = self.call("add", json!({ "text": text }))?;
+        Ok(result["id"].as_i64().unwrap_or_default())
+    }
+
+    /// 添加任意类型的数据，返回条目 ID
+    pub fn add_data(&mut self, data: &[u8], mime: &str) -> Result<i64> {
+        let params = json!({ "data": super::protocol::base64_encode(data), "mime": mime });
+        let result = self.call("add", params)?;
+        Ok(result["id"].as_i64().unwrap_or_default())
+    }
+
+    pub fn delete(&mut self, id: i64) -> Result<()> {
+        self.call("delete", json!({ "id": id })).map(|_| ())
+    }
+
+    pub fn set_favorite(&mut self, id: i64, favorite: bool) -> Result<()> {
+        self.call("favorite", json!({ "id": id, "favorite": favorite }))
+            .map(|_| ())
+    }
+
+    pub fn pause(&mut self) -> Result<()> {
+        self.call("pause", Value::Null).map(|_| ())
+    }
+
+    /// 开启隐私模式，到期后自动恢复记录
+    pub fn pause_for(&mut self, duration: PrivateDuration) -> Result<PrivateStatus> {
+        let params = match duration {
+            // 接口以分钟为单位，不足一分钟按一分钟计算
+            PrivateDuration::For(duration) => {
+                json!({ "minutes": (duration.num_seconds() + 59) / 60 })
+            }
+            PrivateDuration::UntilRestart => json!({ "until_restart": true }),
+            PrivateDuration::UntilResumed => Value::Null,
+        };
+        let result = self.call("pause", params)?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub fn resume(&mut self) -> Result<()> {
+        self.call("resume", Value::Null).map(|_| ())
+    }
+
+    /// 记录是否已暂停
+    pub fn status(&mut self) -> Result<bool> {
+        let result = self.call("status", Value::Null)?;
+        Ok(result["paused"].as_bool().unwrap_or_default())
+    }
+
+    /// 隐私模式的状态，包括自动恢复的时间
+    pub fn private_status(&mut self) -> Result<PrivateStatus> {
+        let result = self.call("status", Value::Null)?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub fn show_window(&mut self, mode: WindowMode) -> Result<()> {
+        self.call("show-window", json!({ "mode": mode }))
+            .map(|_| ())
+    }
+
+    /// 订阅事件，之后用 [`Client::next_event`] 读取
+    ///
+    /// `window` 为 true 时还会收到窗口请求，用于附加到守护进程的 GUI。
+    pub fn subscribe(&mut self, window: bool) -> Result<()> {
+        self.call("subscribe", json!({ "window": window }))?;
+        // 订阅后事件可能长时间没有，不再使用超时
+        self.reader.get_ref().set_read_timeout(None)?;
+        Ok(())
+    }
+
+    /// 阻塞等待下一个事件，连接关闭时返回错误
+    pub fn next_event(&mut self) -> Result<Event> {
+        if let Some(event) = self.pending_events.pop_front() {
+            return Ok(event);
+        }
+        loop {
+            let message = self.read_message()?;
+            if message.method.as_deref() == Some("event") {
+                if let Some(event) = Self::parse_event(message) {
+                    return Ok(event);
+                }
+            }
+        }
+    }
+
+    fn read_message(&mut self) -> Result<Message> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(ClipManagerError::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "Control socket closed",
+            )));
+        }
+        Ok(serde_json::from_str(&line)?)
+    }
+
+    fn parse_event(message: Message) -> Option<Event> {
+        // 忽略新版本服务端增加的未知事件
+        serde_json::from_value(message.params?).ok()
+    }
+}
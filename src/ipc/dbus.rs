@@ -0,0 +1,338 @@
+// 会话总线上的 org.clipmanager.ClipManager 服务，方法转发给控制套接字同一个 Service
+
+use super::protocol::{ItemInfo, RpcError, WindowMode, INVALID_PARAMS, ITEM_NOT_FOUND};
+use super::server::Service;
+use crate::error::Result;
+use crate::storage::StorageEvent;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+pub const BUS_NAME: &str = "org.clipmanager.ClipManager";
+pub const OBJECT_PATH: &str = "/org/clipmanager/ClipManager";
+pub const INTERFACE: &str = "org.clipmanager.ClipManager";
+
+/// 摘要的最大字符数
+const PREVIEW_CHARS: usize = 200;
+
+/// D-Bus 上的条目：ID、类型、摘要、创建时间（Unix 秒）、是否收藏
+pub type DbusItem = (i64, String, String, i64, bool);
+
+struct ClipManagerInterface {
+    service: Service,
+}
+
+fn to_fdo(error: RpcError) -> zbus::fdo::Error {
+    match error.code {
+        INVALID_PARAMS | ITEM_NOT_FOUND => zbus::fdo::Error::InvalidArgs(error.message),
+        _ => zbus::fdo::Error::Failed(error.message),
+    }
+}
+
+fn to_dbus_item(item: &ItemInfo) -> DbusItem {
+    let preview = match (&item.text, &item.image) {
+        (_, Some(image)) => format!("[image {}x{}]", image.width, image.height),
+        (text, None) => crate::sensitive::safe_preview(
+            item.sensitive.as_deref(),
+            text.as_deref().unwrap_or_default(),
+            PREVIEW_CHARS,
+        ),
+    };
+    (
+        item.id.unwrap_or_default(),
+        item.content_type.clone(),
+        preview,
+        item.created_at.timestamp(),
+        item.favorite,
+    )
+}
+
+impl ClipManagerInterface {
+    fn call(&self, method: &str, params: Value) -> zbus::fdo::Result<Value> {
+        self.service.call(method, params).map_err(to_fdo)
+    }
+
+    fn items(&self, method: &str, params: Value) -> zbus::fdo::Result<Vec<DbusItem>> {
+        let items: Vec<ItemInfo> = serde_json::from_value(self.call(method, params)?)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        Ok(items.iter().map(to_dbus_item).collect())
+    }
+}
+
+#[zbus::interface(name = "org.clipmanager.ClipManager")]
+impl ClipManagerInterface {
+    /// 最近的条目，limit 为 0 时返回全部
+    fn get_history(&self, limit: u32) -> zbus::fdo::Result<Vec<DbusItem>> {
+        self.items("list", json!({ "limit": limit }))
+    }
+
+    fn search(&self, query: String, limit: u32) -> zbus::fdo::Result<Vec<DbusItem>> {
+        self.items("search", json!({ "query": query, "limit": limit }))
+    }
+
+    fn copy_item(&self, id: i64) -> zbus::fdo::Result<()> {
+        self.call("copy", json!({ "id": id })).map(|_| ())
+    }
+
+    /// 添加文本条目，返回条目 ID
+    fn add_text(&self, text: String) -> zbus::fdo::Result<i64> {
+        let result = self.call("add", json!({ "text": text }))?;
+        Ok(result["id"].as_i64().unwrap_or_default())
+    }
+
+    fn pause(&self) -> zbus::fdo::Result<()> {
+        self.call("pause", Value::Null).map(|_| ())
+    }
+
+    /// 暂停记录，minutes 分钟后自动恢复
+    fn pause_for(&self, minutes: u32) -> zbus::fdo::Result<()> {
+        self.call("pause", json!({ "minutes": minutes }))
+            .map(|_| ())
+    }
+
+    fn resume(&self) -> zbus::fdo::Result<()> {
+        self.call("resume", Value::Null).map(|_| ())
+    }
+
+    /// mode 为 `toggle`、`show` 或 `popup`，空字符串等同于 `show`
+    fn show_window(&self, mode: String) -> zbus::fdo::Result<()> {
+        let mode = match mode.as_str() {
+            "" | "show" => WindowMode::Show,
+            "toggle" => WindowMode::Toggle,
+            "popup" => WindowMode::Popup,
+            other => {
+                return Err(zbus::fdo::Error::InvalidArgs(format!(
+                    "Unknown window mode '{}'",
+                    other
+                )))
+            }
+        };
+        self.call("show-window", json!({ "mode": mode }))
+            .map(|_| ())
+    }
+
+    #[zbus(signal)]
+    async fn item_added(
+        emitter: &zbus::object_server::SignalEmitter<'_>,
+        id: i64,
+        content_type: &str,
+        preview: &str,
+    ) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn item_removed(
+        emitter: &zbus::object_server::SignalEmitter<'_>,
+        id: i64,
+    ) -> zbus::Result<()>;
+}
+
+pub struct DbusService {
+    connection: zbus::blocking::Connection,
+    stopped: Arc<AtomicBool>,
+}
+
+impl DbusService {
+    /// 在会话总线上注册服务
+    pub fn start(service: &Service) -> Result<Self> {
+        Self::start_with(zbus::blocking::connection::Builder::session()?, service)
+    }
+
+    pub fn start_with(
+        builder: zbus::blocking::connection::Builder<'_>,
+        service: &Service,
+    ) -> Result<Self> {
+        let database = service.database();
+        let events = database.subscribe();
+        let connection = builder
+            .name(BUS_NAME)?
+            .serve_at(
+                OBJECT_PATH,
+                ClipManagerInterface {
+                    service: service.clone(),
+                },
+            )?
+            .build()?;
+        log::info!("D-Bus service registered as {}", BUS_NAME);
+
+        // 把存储事件作为信号发出
+        let interface = connection
+            .object_server()
+            .interface::<_, ClipManagerInterface>(OBJECT_PATH)?;
+        let stopped = Arc::new(AtomicBool::new(false));
+        let signal_stopped = Arc::clone(&stopped);
+        std::thread::spawn(move || {
+            let emitter = interface.signal_emitter();
+            for event in events {
+                if signal_stopped.load(Ordering::Relaxed) {
+                    break;
+                }
+                let result = zbus::block_on(async {
+                    match event {
+                        // 事件只带摘要，预览从数据库中的条目生成
+                        StorageEvent::ItemAdded(summary) => match database.get_item(summary.id) {
+                            Ok(Some(item)) => {
+                                let (id, content_type, preview, _, _) =
+                                    to_dbus_item(&ItemInfo::from(&item));
+                                ClipManagerInterface::item_added(
+                                    emitter,
+                                    id,
+                                    &content_type,
+                                    &preview,
+                                )
+                                .await
+                            }
+                            Ok(None) => Ok(()),
+                            Err(e) => {
+                                log::warn!("Failed to load item {}: {}", summary.id, e);
+                                Ok(())
+                            }
+                        },
+                        StorageEvent::ItemDeleted(id) => {
+                            ClipManagerInterface::item_removed(emitter, id).await
+                        }
+                        StorageEvent::Cleared(ids) => {
+                            for id in ids {
+                                ClipManagerInterface::item_removed(emitter, id).await?;
+                            }
+                            Ok(())
+                        }
+                        _ => Ok(()),
+                    }
+                });
+                if let Err(e) = result {
+                    log::warn!("Failed to emit D-Bus signal: {}", e);
+                }
+            }
+        });
+
+        Ok(Self {
+            connection,
+            stopped,
+        })
+    }
+}
+
+impl Drop for DbusService {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        let _ = self.connection.release_name(BUS_NAME);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Database;
+    use crate::test_support::{FakeClipboard, PrivateBus};
+    use zbus::blocking::{MessageIterator, Proxy};
+    use zbus::MatchRule;
+
+    #[test]
+    #[ignore = "requires dbus-daemon"]
+    fn test_dbus_service() {
+        let bus = PrivateBus::start();
+        let dir = tempfile::tempdir().unwrap();
+        let database = Arc::new(Database::new(dir.path().join("test.db")).unwrap());
+        let control = Arc::new(FakeClipboard::default());
+        let service = Service::new(database, Arc::clone(&control), None);
+        let _dbus = DbusService::start_with(
+            zbus::blocking::connection::Builder::address(bus.address()).unwrap(),
+            &service,
+        )
+        .unwrap();
+
+        let client = bus.connect();
+        let rule = MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .interface(INTERFACE)
+            .unwrap()
+            .build();
+        let mut signals = MessageIterator::for_match_rule(rule, &client, None).unwrap();
+        let proxy = Proxy::new(&client, BUS_NAME, OBJECT_PATH, INTERFACE).unwrap();
+
+        let id: i64 = proxy.call("AddText", &("hello over dbus",)).unwrap();
+        let _: i64 = proxy.call("AddText", &("second",)).unwrap();
+
+        let history: Vec<DbusItem> = proxy.call("GetHistory", &(10u32,)).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].0, id);
+        assert_eq!(history[1].2, "hello over dbus");
+
+        let found: Vec<DbusItem> = proxy.call("Search", &("dbus", 0u32)).unwrap();
+        assert_eq!(found.len(), 1);
+
+        let _: () = proxy.call("CopyItem", &(id,)).unwrap();
+        assert_eq!(*control.copied.lock().unwrap(), vec![id]);
+        assert!(proxy.call::<_, _, ()>("CopyItem", &(9999i64,)).is_err());
+
+        let _: () = proxy.call("Pause", &()).unwrap();
+        assert!(control.private.is_paused());
+        let _: () = proxy.call("Resume", &()).unwrap();
+        assert!(!control.private.is_paused());
+        let _: () = proxy.call("PauseFor", &(5u32,)).unwrap();
+        assert!(control.private.status().resume_at.is_some());
+
+        // 没有窗口时 ShowWindow 失败
+        assert!(proxy.call::<_, _, ()>("ShowWindow", &("popup",)).is_err());
+
+        service.database().delete_item(id).unwrap();
+
+        let message = signals.next().unwrap().unwrap();
+        let header = message.header();
+        assert_eq!(header.member().unwrap().as_str(), "ItemAdded");
+        let (added, content_type, preview): (i64, String, String) =
+            message.body().deserialize().unwrap();
+        assert_eq!(added, id);
+        assert_eq!(content_type, "text");
+        assert_eq!(preview, "hello over dbus");
+
+        let removed = signals
+            .find_map(|message| {
+                let message = message.unwrap();
+                let header = message.header();
+                (header.member().unwrap().as_str() == "ItemRemoved")
+                    .then(|| message.body().deserialize::<(i64,)>().unwrap().0)
+            })
+            .unwrap();
+        assert_eq!(removed, id);
+
+        // 清空历史时逐条发出 ItemRemoved
+        let remaining = history[0].0;
+        service.database().clear_all().unwrap();
+        let removed = signals
+            .find_map(|message| {
+                let message = message.unwrap();
+                let header = message.header();
+                (header.member().unwrap().as_str() == "ItemRemoved")
+                    .then(|| message.body().deserialize::<(i64,)>().unwrap().0)
+            })
+            .unwrap();
+        assert_eq!(removed, remaining);
+
+        // 敏感条目只发出标签
+        let mut item = crate::clipboard::types::ClipboardItem::new_text("hunter2".to_string());
+        item.sensitive = Some(crate::sensitive::CONCEALED_LABEL.to_string());
+        let id = service
+            .database()
+            .insert_item(&item, crate::storage::database::DEFAULT_MAX_ITEMS)
+            .unwrap();
+        let preview = signals
+            .find_map(|message| {
+                let message = message.unwrap();
+                let header = message.header();
+                (header.member().unwrap().as_str() == "ItemAdded").then(|| {
+                    message
+                        .body()
+                        .deserialize::<(i64, String, String)>()
+                        .unwrap()
+                })
+            })
+            .unwrap();
+        assert_eq!(
+            preview,
+            (id, "text".to_string(), "🔒 password manager".to_string())
+        );
+        let history: Vec<DbusItem> = proxy.call("GetHistory", &(1u32,)).unwrap();
+        assert_eq!(history[0].2, "🔒 password manager");
+    }
+}
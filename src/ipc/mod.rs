@@ -0,0 +1,205 @@
+//! Local control socket
+//!
+//! The process that owns the clipboard monitor (the daemon, or the GUI when no
+//! daemon runs) listens on a Unix socket in `$XDG_RUNTIME_DIR`. Requests are
+//! JSON-RPC 2.0 objects, one per line; see [`protocol`] for the methods and
+//! [`client::Client`] for a typed client used by the CLI, editor plugins and
+//! hotkey helpers. The socket is created with mode 0600, so only the owning
+//! user can connect.
+//!
+//! A GUI attached to a daemon subscribes with `window: true` and receives the
+//! `show-window` requests that the daemon cannot handle itself.
+//!
+//! On Linux the same [`Service`] is also exported on the session bus as
+//! `org.clipmanager.ClipManager` (see [`dbus`]) for desktop shortcuts and
+//! applets. When enabled in the config, [`http`] serves a token-protected REST
+//! API on 127.0.0.1 for tools that cannot reach the socket.
+
+use crate::clipboard::private_mode::PrivateDuration;
+#[cfg(unix)]
+use crate::config::settings::HttpConfig;
+use crate::error::Result;
+use std::path::PathBuf;
+
+#[cfg(unix)]
+pub mod client;
+#[cfg(target_os = "linux")]
+pub mod dbus;
+#[cfg(unix)]
+pub mod http;
+pub mod protocol;
+#[cfg(unix)]
+pub mod server;
+
+#[cfg(unix)]
+pub use client::Client;
+#[cfg(unix)]
+pub use server::{RpcServer, Service};
+
+/// 控制命令
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlCommand {
+    /// 显示或隐藏主窗口
+    Toggle,
+    /// 显示主窗口
+    Show,
+    /// 打开紧凑弹出选择器
+    Popup,
+    /// 暂停剪切板记录（隐私模式）
+    Pause(PrivateDuration),
+    /// 恢复剪切板记录
+    Resume,
+    /// 查询记录状态，回复 `recording`、`paused` 或带恢复时间的 `paused until …`
+    Status,
+}
+
+impl ControlCommand {
+    pub fn parse(command: &str) -> Option<Self> {
+        match command.trim() {
+            "toggle" => Some(ControlCommand::Toggle),
+            "show" => Some(ControlCommand::Show),
+            "popup" => Some(ControlCommand::Popup),
+            "pause" => Some(ControlCommand::Pause(PrivateDuration::UntilResumed)),
+            "resume" => Some(ControlCommand::Resume),
+            "status" => Some(ControlCommand::Status),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ControlCommand::Toggle => "toggle",
+            ControlCommand::Show => "show",
+            ControlCommand::Popup => "popup",
+            ControlCommand::Pause(_) => "pause",
+            ControlCommand::Resume => "resume",
+            ControlCommand::Status => "status",
+        }
+    }
+
+    /// 不需要窗口的命令
+    pub fn is_monitor_command(&self) -> bool {
+        matches!(
+            self,
+            ControlCommand::Pause(_) | ControlCommand::Resume | ControlCommand::Status
+        )
+    }
+}
+
+/// 控制套接字路径，优先使用 $XDG_RUNTIME_DIR
+pub fn socket_path() -> PathBuf {
+    runtime_dir().join("clipmanager.sock")
+}
+
+/// 没有 $XDG_RUNTIME_DIR 时使用临时目录下按用户区分的子目录，由服务端以 0700 创建
+fn runtime_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("XDG_RUNTIME_DIR") {
+        return PathBuf::from(dir);
+    }
+    #[cfg(unix)]
+    {
+        // SAFETY: getuid 总是成功，没有副作用
+        let uid = unsafe { libc::getuid() };
+        std::env::temp_dir().join(format!("clipmanager-{}", uid))
+    }
+    #[cfg(not(unix))]
+    {
+        std::env::temp_dir()
+    }
+}
+
+/// 对外提供服务的端点，释放时停止
+#[cfg(unix)]
+pub struct Endpoints {
+    service: Service,
+    _socket: Option<RpcServer>,
+    #[cfg(target_os = "linux")]
+    _dbus: Option<dbus::DbusService>,
+    http: Option<http::HttpServer>,
+}
+
+/// 在默认套接字和会话总线上提供服务，某个端点不可用时只记录警告
+#[cfg(unix)]
+pub fn serve(service: &Service, http_config: &HttpConfig) -> Endpoints {
+    let socket = RpcServer::start(socket_path(), service)
+        .inspect_err(|e| log::warn!("Control socket unavailable: {}", e))
+        .ok();
+    #[cfg(target_os = "linux")]
+    let dbus = dbus::DbusService::start(service)
+        .inspect_err(|e| log::warn!("D-Bus service unavailable: {}", e))
+        .ok();
+    let mut endpoints = Endpoints {
+        service: service.clone(),
+        _socket: socket,
+        #[cfg(target_os = "linux")]
+        _dbus: dbus,
+        http: None,
+    };
+    endpoints.apply_http_config(http_config);
+    endpoints
+}
+
+#[cfg(unix)]
+impl Endpoints {
+    /// 按配置启动、停止，或在端口、允许的来源变化时重启 HTTP 接口
+    pub fn apply_http_config(&mut self, config: &HttpConfig) {
+        let running = self
+            .http
+            .as_ref()
+            .map(|http| (http.port(), http.allowed_origin()));
+        let wanted = config
+            .enabled
+            .then_some((config.port, config.allowed_origin.as_deref()));
+        if running == wanted {
+            return;
+        }
+
+        // 先停止旧的监听，换端口时才能重新绑定
+        self.http = None;
+        if config.enabled {
+            self.http = http::HttpServer::start(config, &self.service)
+                .inspect_err(|e| log::warn!("HTTP API unavailable: {}", e))
+                .ok();
+        }
+    }
+}
+
+/// 向正在运行的实例发送命令
+///
+/// `status` 返回 [`ControlCommand::Status`] 描述的状态，其他命令成功时返回空字符串。
+#[cfg(unix)]
+pub fn send_command(command: ControlCommand) -> Result<String> {
+    use protocol::WindowMode;
+
+    let mut client = Client::connect()?;
+    match command {
+        ControlCommand::Toggle => client.show_window(WindowMode::Toggle)?,
+        ControlCommand::Show => client.show_window(WindowMode::Show)?,
+        ControlCommand::Popup => client.show_window(WindowMode::Popup)?,
+        ControlCommand::Pause(duration) => {
+            client.pause_for(duration)?;
+        }
+        ControlCommand::Resume => client.resume()?,
+        ControlCommand::Status => {
+            let status = client.private_status()?;
+            let reply = match status.resume_at {
+                _ if !status.paused => "recording".to_string(),
+                Some(resume_at) => format!(
+                    "paused until {}",
+                    resume_at.with_timezone(&chrono::Local).format("%H:%M:%S")
+                ),
+                None if status.until_restart => "paused until restart".to_string(),
+                None => "paused".to_string(),
+            };
+            return Ok(reply);
+        }
+    }
+    Ok(String::new())
+}
+
+#[cfg(not(unix))]
+pub fn send_command(_command: ControlCommand) -> Result<String> {
+    Err(crate::error::ClipManagerError::Config {
+        message: "Control socket is not supported on this platform".to_string(),
+    })
+}
@@ -0,0 +1,352 @@
+// 控制套接字的 JSON-RPC 2.0 消息，每行一个 JSON 对象
+
+use crate::clipboard::private_mode::PrivateStatus;
+use crate::clipboard::types::{ClipboardItem, ContentType, SearchFilter};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 协议版本，不兼容的修改时递增
+pub const PROTOCOL_VERSION: u32 = 1;
+
+// JSON-RPC 标准错误码
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+// 应用自定义错误码
+pub const ITEM_NOT_FOUND: i64 = -32000;
+pub const NO_WINDOW: i64 = -32001;
+pub const UNSUPPORTED_VERSION: i64 = -32002;
+/// 内容被捕获规则拦截或因敏感被跳过
+pub const NOT_SAVED: i64 = -32003;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Request {
+    pub jsonrpc: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+impl Request {
+    pub fn new(id: u64, method: &str, params: Value) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: Some(Value::from(id)),
+            method: method.to_string(),
+            params,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<crate::error::ClipManagerError> for RpcError {
+    fn from(error: crate::error::ClipManagerError) -> Self {
+        Self::new(INTERNAL_ERROR, error.to_string())
+    }
+}
+
+/// 服务端发出的消息：请求的回复，或订阅后推送的事件通知
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Message {
+    pub jsonrpc: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+    /// 事件通知固定为 `event`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+impl Message {
+    pub fn response(id: Option<Value>, result: std::result::Result<Value, RpcError>) -> Self {
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: Some(id.unwrap_or(Value::Null)),
+            result,
+            error,
+            method: None,
+            params: None,
+        }
+    }
+
+    pub fn event(event: &Event) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id: None,
+            result: None,
+            error: None,
+            method: Some("event".to_string()),
+            params: serde_json::to_value(event).ok(),
+        }
+    }
+}
+
+impl From<PrivateStatus> for Event {
+    fn from(status: PrivateStatus) -> Self {
+        Event::RecordingPaused {
+            paused: status.paused,
+            resume_at: status.resume_at,
+            until_restart: status.until_restart,
+        }
+    }
+}
+
+/// 窗口操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowMode {
+    Toggle,
+    Show,
+    Popup,
+}
+
+/// 订阅者收到的事件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    ItemAdded {
+        item: ItemInfo,
+    },
+    ItemUpdated {
+        id: i64,
+    },
+    ItemDeleted {
+        id: i64,
+    },
+    Cleared,
+    /// 其他进程修改了数据库，需要重新加载
+    Changed,
+    RecordingPaused {
+        paused: bool,
+        /// 隐私模式自动恢复记录的时间
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        resume_at: Option<DateTime<Utc>>,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        until_restart: bool,
+    },
+    /// 请求显示窗口，只发给以 `window: true` 订阅的 GUI
+    Window {
+        mode: WindowMode,
+    },
+}
+
+/// `list` / `search` 的参数，对应 [`SearchFilter`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ListParams {
+    pub query: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    pub favorites: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<DateTime<Utc>>,
+    /// 0 表示不限制
+    pub limit: usize,
+    pub offset: usize,
+}
+
+impl ListParams {
+    pub fn to_filter(&self) -> std::result::Result<SearchFilter, RpcError> {
+        let content_type = match self.content_type.as_deref() {
+            None => None,
+            Some("text") => Some(ContentType::Text),
+            Some("image") => Some(ContentType::Image),
+            Some(other) => {
+                return Err(RpcError::new(
+                    INVALID_PARAMS,
+                    format!("Unknown item type '{}'", other),
+                ))
+            }
+        };
+        Ok(SearchFilter {
+            query: self.query.clone(),
+            content_type,
+            favorites_only: self.favorites,
+            date_range: (self.since.is_some() || self.until.is_some()).then(|| {
+                (
+                    self.since.unwrap_or(DateTime::<Utc>::MIN_UTC),
+                    self.until.unwrap_or_else(Utc::now),
+                )
+            }),
+        })
+    }
+}
+
+/// 条目的 JSON 表示，图片只包含尺寸信息
+///
+/// 敏感条目默认不包含文本，只有明确请求内容时（[`ItemInfo::with_content`]）才返回。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemInfo {
+    pub id: Option<i64>,
+    #[serde(rename = "type")]
+    pub content_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<ImageInfo>,
+    pub size: usize,
+    pub favorite: bool,
+    pub created_at: DateTime<Utc>,
+    pub accessed_at: DateTime<Utc>,
+    pub access_count: u32,
+    /// 来源程序名称
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_app: Option<String>,
+    /// 敏感内容检测命中的标签
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sensitive: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageInfo {
+    pub width: u32,
+    pub height: u32,
+    pub mime: String,
+}
+
+impl ItemInfo {
+    /// 包含敏感条目文本的表示，只用于明确请求内容的调用
+    pub fn with_content(item: &ClipboardItem) -> Self {
+        Self {
+            text: item.get_text_content().map(str::to_string),
+            ..Self::from(item)
+        }
+    }
+}
+
+impl From<&ClipboardItem> for ItemInfo {
+    fn from(item: &ClipboardItem) -> Self {
+        Self {
+            id: item.id,
+            content_type: match item.content_type {
+                ContentType::Text => "text",
+                ContentType::Image => "image",
+            }
+            .to_string(),
+            text: item
+                .get_text_content()
+                .filter(|_| item.sensitive.is_none())
+                .map(str::to_string),
+            image: item.get_image_data().map(|image| ImageInfo {
+                width: image.width,
+                height: image.height,
+                mime: image.format.to_mime_type().to_string(),
+            }),
+            size: item.content_size,
+            favorite: item.is_favorite,
+            created_at: item.created_at,
+            accessed_at: item.accessed_at,
+            access_count: item.access_count,
+            source_app: item.source.as_ref().and_then(|source| source.name.clone()),
+            sensitive: item.sensitive.clone(),
+            expires_at: item.expires_at,
+        }
+    }
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// 图片数据在 JSON 中以 base64 传输
+pub fn base64_encode(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+pub fn base64_decode(encoded: &str) -> Option<Vec<u8>> {
+    let encoded = encoded.trim_end_matches('=').as_bytes();
+    let mut data = Vec::with_capacity(encoded.len() * 3 / 4);
+    for chunk in encoded.chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+        let mut n = 0u32;
+        for (i, &byte) in chunk.iter().enumerate() {
+            let value = BASE64_ALPHABET.iter().position(|&c| c == byte)? as u32;
+            n |= value << (18 - 6 * i);
+        }
+        for i in 0..chunk.len() - 1 {
+            data.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Some(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_roundtrip() {
+        assert_eq!(base64_encode(b"foob"), "Zm9vYg==");
+        assert_eq!(base64_encode(b"fooba"), "Zm9vYmE=");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        for len in 0..10 {
+            let data: Vec<u8> = (0..len).map(|i| (i * 37) as u8).collect();
+            assert_eq!(base64_decode(&base64_encode(&data)).unwrap(), data);
+        }
+        assert!(base64_decode("a").is_none());
+        assert!(base64_decode("a$==").is_none());
+    }
+
+    #[test]
+    fn test_event_format() {
+        let message = Message::event(&Event::ItemDeleted { id: 3 });
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "event",
+                "params": {"type": "item_deleted", "id": 3}
+            })
+        );
+    }
+}
@@ -0,0 +1,758 @@
+// 控制套接字服务端，由持有剪切板监控的进程运行
+
+use super::protocol::{
+    base64_decode, base64_encode, Event, ItemInfo, ListParams, Message, Request, RpcError,
+    WindowMode, INVALID_PARAMS, INVALID_REQUEST, ITEM_NOT_FOUND, METHOD_NOT_FOUND, NOT_SAVED,
+    NO_WINDOW, PARSE_ERROR, PROTOCOL_VERSION, UNSUPPORTED_VERSION,
+};
+use crate::clipboard::handler::MonitorControl;
+use crate::clipboard::private_mode::{PrivateDuration, PrivateStatus};
+use crate::clipboard::types::{ClipboardContent, ClipboardItem};
+use crate::error::{ClipManagerError, Result};
+use crate::storage::database::BatchOutcome;
+use crate::storage::writer::CaptureFilter;
+use crate::storage::{Database, StorageEvent};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+/// 服务端对剪切板监控的操作，测试中可替换为不访问系统剪切板的实现
+pub trait ClipboardControl: Send + Sync {
+    /// 开启隐私模式，`None` 恢复记录
+    fn set_private_mode(&self, duration: Option<PrivateDuration>) -> PrivateStatus;
+    fn private_status(&self) -> PrivateStatus;
+    fn skipped_count(&self) -> u64;
+    fn copy_item(&self, item: &ClipboardItem) -> Result<()>;
+}
+
+impl ClipboardControl for MonitorControl {
+    fn set_private_mode(&self, duration: Option<PrivateDuration>) -> PrivateStatus {
+        MonitorControl::set_private_mode(self, duration)
+    }
+
+    fn private_status(&self) -> PrivateStatus {
+        MonitorControl::private_status(self)
+    }
+
+    fn skipped_count(&self) -> u64 {
+        MonitorControl::skipped_count(self)
+    }
+
+    fn copy_item(&self, item: &ClipboardItem) -> Result<()> {
+        MonitorControl::copy_item(self, item)
+    }
+}
+
+/// 本进程有窗口时处理 `show-window` 的回调
+pub type WindowHandler = Box<dyn Fn(WindowMode) + Send + Sync>;
+
+type Writer = Arc<Mutex<UnixStream>>;
+
+struct Subscriber {
+    writer: Writer,
+    /// 是否接收窗口请求（附加到守护进程的 GUI）
+    window: bool,
+}
+
+struct Shared {
+    database: Arc<Database>,
+    monitor: Box<dyn ClipboardControl>,
+    window: Option<WindowHandler>,
+    max_items: AtomicUsize,
+    max_item_size: AtomicUsize,
+    /// `add` 写入前的过滤，与剪切板捕获共用同一套规则
+    filter: RwLock<CaptureFilter>,
+    subscribers: Mutex<Vec<Subscriber>>,
+}
+
+#[derive(Deserialize)]
+struct IdParams {
+    id: i64,
+}
+
+/// `get` 默认不返回敏感条目的文本，`reveal` 为 true 时返回
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct RevealParams {
+    reveal: bool,
+}
+
+#[derive(Deserialize)]
+struct FavoriteParams {
+    #[serde(default = "default_favorite")]
+    favorite: bool,
+}
+
+fn default_favorite() -> bool {
+    true
+}
+
+/// `pause` 的参数，都省略时暂停到手动恢复
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct PauseParams {
+    minutes: Option<u32>,
+    until_restart: bool,
+}
+
+impl PauseParams {
+    fn duration(&self) -> PrivateDuration {
+        match self.minutes {
+            Some(minutes) if minutes > 0 => PrivateDuration::minutes(minutes.into()),
+            _ if self.until_restart => PrivateDuration::UntilRestart,
+            _ => PrivateDuration::UntilResumed,
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct HelloParams {
+    version: Option<u32>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct AddParams {
+    text: Option<String>,
+    /// base64 编码的数据
+    data: Option<String>,
+    mime: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct WindowParams {
+    #[serde(default = "default_window_mode")]
+    mode: WindowMode,
+}
+
+fn default_window_mode() -> WindowMode {
+    WindowMode::Show
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct SubscribeParams {
+    window: bool,
+}
+
+/// 处理请求的核心，套接字和 D-Bus 服务共用同一实例
+#[derive(Clone)]
+pub struct Service {
+    shared: Arc<Shared>,
+}
+
+impl Service {
+    pub fn new(
+        database: Arc<Database>,
+        monitor: impl ClipboardControl + 'static,
+        window: Option<WindowHandler>,
+    ) -> Self {
+        let events = database.subscribe();
+        let shared = Arc::new(Shared {
+            database,
+            monitor: Box::new(monitor),
+            window,
+            max_items: AtomicUsize::new(crate::storage::database::DEFAULT_MAX_ITEMS),
+            max_item_size: AtomicUsize::new(usize::MAX),
+            filter: RwLock::new(CaptureFilter::default()),
+            subscribers: Mutex::new(Vec::new()),
+        });
+
+        // 把存储事件转发给订阅者
+        let weak = Arc::downgrade(&shared);
+        std::thread::spawn(move || {
+            for event in events {
+                let Some(shared) = weak.upgrade() else {
+                    break;
+                };
+                let event = match event {
+                    // 事件只带摘要，推送给客户端的元数据从数据库读取
+                    StorageEvent::ItemAdded(summary) => {
+                        match shared.database.get_item(summary.id) {
+                            Ok(Some(item)) => Event::ItemAdded {
+                                item: ItemInfo::from(&item),
+                            },
+                            Ok(None) => continue,
+                            Err(e) => {
+                                log::warn!("Failed to load item {}: {}", summary.id, e);
+                                continue;
+                            }
+                        }
+                    }
+                    StorageEvent::ItemUpdated(id) => Event::ItemUpdated { id },
+                    StorageEvent::ItemDeleted(id) => Event::ItemDeleted { id },
+                    StorageEvent::Cleared(_) => Event::Cleared,
+                    StorageEvent::ExternalChange => Event::Changed,
+                    StorageEvent::RecordingChanged(status) => Event::from(status),
+                    StorageEvent::WriteFailed(_)
+                    | StorageEvent::ContentExpired(_)
+                    | StorageEvent::CaptureSkipped(_)
+                    | StorageEvent::CaptureBlocked { .. }
+                    | StorageEvent::SnippetsChanged => continue,
+                };
+                shared.broadcast(&event);
+            }
+        });
+
+        Self { shared }
+    }
+
+    pub fn database(&self) -> Arc<Database> {
+        Arc::clone(&self.shared.database)
+    }
+
+    /// `add` 使用的条目数量与大小限制
+    pub fn set_limits(&self, max_items: usize, max_item_size: usize) {
+        self.shared.max_items.store(max_items, Ordering::Relaxed);
+        self.shared
+            .max_item_size
+            .store(max_item_size, Ordering::Relaxed);
+    }
+
+    /// `add` 使用的捕获规则、敏感内容检测和过期设置
+    pub fn set_capture_filter(&self, filter: CaptureFilter) {
+        *self.shared.filter.write().unwrap() = filter;
+    }
+
+    /// 向所有订阅者推送事件，例如在本进程内暂停记录后
+    pub fn broadcast(&self, event: &Event) {
+        self.shared.broadcast(event);
+    }
+
+    /// 直接调用某个方法，参数与结果同套接字协议
+    pub fn call(&self, method: &str, params: Value) -> std::result::Result<Value, RpcError> {
+        self.shared.handle(&Request::new(0, method, params))
+    }
+}
+
+pub struct RpcServer {
+    path: PathBuf,
+    stopped: Arc<AtomicBool>,
+}
+
+/// 创建套接字所在的目录，已存在时要求属于当前用户且其他用户无法访问
+fn ensure_private_dir(dir: &Path) -> Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+
+    match std::fs::DirBuilder::new().mode(0o700).create(dir) {
+        Err(e) if e.kind() != std::io::ErrorKind::AlreadyExists => return Err(e.into()),
+        _ => {}
+    }
+    let metadata = std::fs::symlink_metadata(dir)?;
+    // SAFETY: getuid 总是成功，没有副作用
+    let uid = unsafe { libc::getuid() };
+    if !metadata.is_dir() || metadata.uid() != uid || metadata.mode() & 0o077 != 0 {
+        return Err(ClipManagerError::Config {
+            message: format!(
+                "Socket directory {} must be owned by the current user with mode 0700",
+                dir.display()
+            ),
+        });
+    }
+    Ok(())
+}
+
+impl RpcServer {
+    /// 开始监听
+    ///
+    /// 如果套接字文件已存在但无人监听（上次异常退出），会先将其删除。
+    pub fn start(path: PathBuf, service: &Service) -> Result<Self> {
+        use std::os::unix::fs::PermissionsExt;
+
+        if let Some(dir) = path.parent() {
+            ensure_private_dir(dir)?;
+        }
+        if path.exists() {
+            if UnixStream::connect(&path).is_ok() {
+                return Err(ClipManagerError::Config {
+                    message: format!("Another instance is listening on {}", path.display()),
+                });
+            }
+            std::fs::remove_file(&path)?;
+        }
+
+        // 只允许当前用户连接，这是唯一的认证方式。目录已经只有当前用户可以访问，
+        // 绑定之后到修改权限之前的这段时间其他用户也无法连接
+        let listener = UnixListener::bind(&path)?;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+        log::info!("Control socket listening on {}", path.display());
+
+        let stopped = Arc::new(AtomicBool::new(false));
+        let shared = Arc::clone(&service.shared);
+        let accept_stopped = Arc::clone(&stopped);
+        std::thread::spawn(move || Self::accept_loop(listener, shared, accept_stopped));
+
+        Ok(Self { path, stopped })
+    }
+
+    fn accept_loop(listener: UnixListener, shared: Arc<Shared>, stopped: Arc<AtomicBool>) {
+        for stream in listener.incoming() {
+            if stopped.load(Ordering::Relaxed) {
+                break;
+            }
+            match stream {
+                Ok(stream) => {
+                    let shared = Arc::clone(&shared);
+                    std::thread::spawn(move || shared.serve(stream));
+                }
+                Err(e) => log::warn!("Control socket accept failed: {}", e),
+            }
+        }
+    }
+}
+
+impl Drop for RpcServer {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        // 唤醒阻塞在 accept 上的线程
+        let _ = UnixStream::connect(&self.path);
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+impl Shared {
+    fn serve(&self, stream: UnixStream) {
+        let writer: Writer = match stream.try_clone() {
+            Ok(writer) => Arc::new(Mutex::new(writer)),
+            Err(e) => {
+                log::warn!("Control socket connection failed: {}", e);
+                return;
+            }
+        };
+
+        for line in BufReader::new(stream).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let request = match serde_json::from_str::<Request>(&line) {
+                Ok(request) => request,
+                Err(e) => {
+                    let error = RpcError::new(PARSE_ERROR, e.to_string());
+                    Self::send(&writer, &Message::response(None, Err(error)));
+                    continue;
+                }
+            };
+            log::debug!("Control request: {}", request.method);
+
+            let result = if request.jsonrpc != "2.0" {
+                Err(RpcError::new(
+                    INVALID_REQUEST,
+                    "Only JSON-RPC 2.0 is supported",
+                ))
+            } else if request.method == "subscribe" {
+                self.subscribe(&request, &writer)
+            } else {
+                self.handle(&request)
+            };
+            // 没有 id 的请求是通知，成功时不回复
+            if request.id.is_some() || result.is_err() {
+                Self::send(&writer, &Message::response(request.id, result));
+            }
+        }
+    }
+
+    fn send(writer: &Writer, message: &Message) -> bool {
+        let Ok(mut line) = serde_json::to_string(message) else {
+            return false;
+        };
+        line.push('\n');
+        let mut stream = writer.lock().unwrap();
+        stream.write_all(line.as_bytes()).is_ok()
+    }
+
+    fn broadcast(&self, event: &Event) {
+        let message = Message::event(event);
+        let window_event = matches!(event, Event::Window { .. });
+        let mut subscribers = self.subscribers.lock().unwrap();
+        // 写入失败说明客户端已断开
+        subscribers.retain(|subscriber| {
+            (window_event && !subscriber.window) || Self::send(&subscriber.writer, &message)
+        });
+    }
+
+    fn subscribe(
+        &self,
+        request: &Request,
+        writer: &Writer,
+    ) -> std::result::Result<Value, RpcError> {
+        let params: SubscribeParams = parse_params(&request.params)?;
+        // 订阅者读取较慢时不阻塞其他连接
+        writer
+            .lock()
+            .unwrap()
+            .set_write_timeout(Some(Duration::from_secs(1)))
+            .map_err(|e| RpcError::from(ClipManagerError::from(e)))?;
+        // 回复前登记，客户端收到回复后发出的请求一定能找到该订阅者；
+        // 在此之间推送的事件由客户端缓存
+        self.subscribers.lock().unwrap().push(Subscriber {
+            writer: Arc::clone(writer),
+            window: params.window,
+        });
+        Ok(json!({ "subscribed": true }))
+    }
+
+    fn handle(&self, request: &Request) -> std::result::Result<Value, RpcError> {
+        let database = &self.database;
+        match request.method.as_str() {
+            "hello" => {
+                let params: HelloParams = parse_params(&request.params)?;
+                if let Some(version) = params.version {
+                    if version != PROTOCOL_VERSION {
+                        return Err(RpcError::new(
+                            UNSUPPORTED_VERSION,
+                            format!(
+                                "Protocol version {} is not supported, server speaks {}",
+                                version, PROTOCOL_VERSION
+                            ),
+                        ));
+                    }
+                }
+                Ok(json!({
+                    "version": PROTOCOL_VERSION,
+                    "server": format!("clipmanager {}", env!("CARGO_PKG_VERSION")),
+                }))
+            }
+            "list" | "search" => {
+                let params: ListParams = parse_params(&request.params)?;
+                if request.method == "search" && params.query.is_empty() {
+                    return Err(RpcError::new(INVALID_PARAMS, "search needs a query"));
+                }
+                let limit = match params.limit {
+                    0 => i64::MAX as usize,
+                    limit => limit,
+                };
+                let items = database.get_items(&params.to_filter()?, limit, params.offset)?;
+                let items: Vec<ItemInfo> = items.iter().map(ItemInfo::from).collect();
+                Ok(serde_json::to_value(items).unwrap_or_default())
+            }
+            "get" => {
+                let item = self.find_item(&request.params)?;
+                let RevealParams { reveal } = parse_params(&request.params)?;
+                let info = if reveal {
+                    ItemInfo::with_content(&item)
+                } else {
+                    ItemInfo::from(&item)
+                };
+                let mut value = serde_json::to_value(info).unwrap_or_default();
+                if let ClipboardContent::Image(image) = &item.content {
+                    value["data"] = Value::from(base64_encode(&image.to_png()?));
+                    value["mime"] = Value::from("image/png");
+                }
+                Ok(value)
+            }
+            "copy" => {
+                let item = self.find_item(&request.params)?;
+                self.monitor.copy_item(&item)?;
+                if let Some(id) = item.id {
+                    database.update_access(id)?;
+                }
+                Ok(Value::Null)
+            }
+            "add" => {
+                let params: AddParams = parse_params(&request.params)?;
+                let (data, mime) = match (params.text, params.data) {
+                    (Some(text), None) => (text.into_bytes(), "text/plain".to_string()),
+                    (None, Some(data)) => (
+                        base64_decode(&data).ok_or_else(|| {
+                            RpcError::new(INVALID_PARAMS, "data is not valid base64")
+                        })?,
+                        params.mime.unwrap_or_else(|| "text/plain".to_string()),
+                    ),
+                    _ => {
+                        return Err(RpcError::new(
+                            INVALID_PARAMS,
+                            "add needs either text or data",
+                        ))
+                    }
+                };
+                let item = ClipboardItem::from_bytes(
+                    data,
+                    &mime,
+                    self.max_item_size.load(Ordering::Relaxed),
+                )
+                .map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))?;
+                let item = self
+                    .filter
+                    .read()
+                    .unwrap()
+                    .screen_item(database, item)
+                    .map_err(|reason| RpcError::new(NOT_SAVED, format!("Not saved: {}", reason)))?;
+                let outcomes =
+                    database.insert_batch(&[item], self.max_items.load(Ordering::Relaxed))?;
+                let id = match outcomes[0] {
+                    BatchOutcome::Inserted(id) | BatchOutcome::Bumped(id) => id,
+                };
+                Ok(json!({ "id": id }))
+            }
+            "delete" => {
+                let item = self.find_item(&request.params)?;
+                if let Some(id) = item.id {
+                    database.delete_item(id)?;
+                }
+                Ok(Value::Null)
+            }
+            "favorite" => {
+                let params: FavoriteParams = parse_params(&request.params)?;
+                let item = self.find_item(&request.params)?;
+                if let Some(id) = item.id {
+                    database.update_favorite(id, params.favorite)?;
+                }
+                Ok(json!({ "favorite": params.favorite }))
+            }
+            "pause" | "resume" => {
+                let duration = if request.method == "pause" {
+                    let params: PauseParams = parse_params(&request.params)?;
+                    Some(params.duration())
+                } else {
+                    None
+                };
+                // 状态变化由 RecordingChanged 事件广播给订阅者
+                let status = self.monitor.set_private_mode(duration);
+                Ok(json!(status))
+            }
+            "status" => {
+                let mut status = json!(self.monitor.private_status());
+                status["skipped"] = json!(self.monitor.skipped_count());
+                Ok(status)
+            }
+            "show-window" => {
+                let params: WindowParams = parse_params(&request.params)?;
+                if let Some(window) = &self.window {
+                    window(params.mode);
+                    return Ok(Value::Null);
+                }
+
+                // 守护进程没有窗口，转发给附加的 GUI
+                let has_window = self
+                    .subscribers
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .any(|subscriber| subscriber.window);
+                if !has_window {
+                    return Err(RpcError::new(NO_WINDOW, "No ClipManager window is running"));
+                }
+                self.broadcast(&Event::Window { mode: params.mode });
+                Ok(Value::Null)
+            }
+            other => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Unknown method '{}'", other),
+            )),
+        }
+    }
+
+    fn find_item(&self, params: &Value) -> std::result::Result<ClipboardItem, RpcError> {
+        let IdParams { id } = parse_params(params)?;
+        self.database
+            .get_item(id)?
+            .ok_or_else(|| RpcError::new(ITEM_NOT_FOUND, format!("No item with id {}", id)))
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: &Value) -> std::result::Result<T, RpcError> {
+    let params = if params.is_null() {
+        json!({})
+    } else {
+        params.clone()
+    };
+    serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::client::Client;
+    use crate::ipc::protocol::NO_WINDOW;
+    use crate::test_support::FakeClipboard;
+    use std::sync::mpsc;
+
+    fn start_server(
+        dir: &tempfile::TempDir,
+        window: Option<WindowHandler>,
+    ) -> (RpcServer, PathBuf, Arc<FakeClipboard>) {
+        let path = dir.path().join("run").join("control.sock");
+        let database = Arc::new(Database::new(dir.path().join("test.db")).unwrap());
+        let control = Arc::new(FakeClipboard::with_database(&database));
+        let service = Service::new(database, Arc::clone(&control), window);
+        let server = RpcServer::start(path.clone(), &service).unwrap();
+        (server, path, control)
+    }
+
+    #[test]
+    fn test_socket_directory_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let database = Arc::new(Database::new(dir.path().join("test.db")).unwrap());
+        let service = Service::new(database, Arc::new(FakeClipboard::default()), None);
+
+        // 不存在的目录以 0700 创建
+        let private = dir.path().join("runtime");
+        let server = RpcServer::start(private.join("control.sock"), &service).unwrap();
+        let mode = std::fs::metadata(&private).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+        drop(server);
+
+        // 其他用户可以访问的目录被拒绝
+        let shared = dir.path().join("shared");
+        std::fs::create_dir(&shared).unwrap();
+        std::fs::set_permissions(&shared, std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(RpcServer::start(shared.join("control.sock"), &service).is_err());
+    }
+
+    #[test]
+    fn test_client_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_server, path, control) = start_server(&dir, None);
+        let mut client = Client::connect_to(&path).unwrap();
+
+        let id = client.add_text("hello from rpc").unwrap();
+        client.add_text("second").unwrap();
+        let items = client.list(&ListParams::default()).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].text.as_deref(), Some("hello from rpc"));
+
+        let found = client.search("rpc", 0).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, Some(id));
+
+        let content = client.get(id, false).unwrap();
+        assert_eq!(content.info.text.as_deref(), Some("hello from rpc"));
+        assert!(content.data.is_none());
+
+        client.copy(id).unwrap();
+        assert_eq!(*control.copied.lock().unwrap(), vec![id]);
+
+        client.pause().unwrap();
+        assert!(client.status().unwrap());
+        client.resume().unwrap();
+        assert!(!control.private.is_paused());
+        let status = client.pause_for(PrivateDuration::minutes(5)).unwrap();
+        assert!(status.paused);
+        assert_eq!(control.private.status().resume_at, status.resume_at);
+        assert!(status.remaining().unwrap() > chrono::Duration::minutes(4));
+        let status = client.pause_for(PrivateDuration::UntilRestart).unwrap();
+        assert!(status.until_restart && status.resume_at.is_none());
+        assert_eq!(client.private_status().unwrap(), status);
+        client.resume().unwrap();
+        control.skipped.store(3, Ordering::Relaxed);
+        let status = client.call("status", Value::Null).unwrap();
+        assert_eq!(status["skipped"], 3);
+
+        client.delete(id).unwrap();
+        match client.get(id, false) {
+            Err(ClipManagerError::Ipc { code, .. }) => assert_eq!(code, ITEM_NOT_FOUND),
+            other => panic!("unexpected result: {:?}", other.map(|c| c.info)),
+        }
+        match client.call("frobnicate", Value::Null) {
+            Err(ClipManagerError::Ipc { code, .. }) => assert_eq!(code, METHOD_NOT_FOUND),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        // 已有服务端监听时不能再启动
+        let database = Arc::new(Database::new(dir.path().join("other.db")).unwrap());
+        let service = Service::new(database, control, None);
+        assert!(RpcServer::start(path, &service).is_err());
+    }
+
+    #[test]
+    fn test_sensitive_text_needs_reveal() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run").join("control.sock");
+        let database = Arc::new(Database::new(dir.path().join("test.db")).unwrap());
+        let service = Service::new(
+            Arc::clone(&database),
+            Arc::new(FakeClipboard::default()),
+            None,
+        );
+        let _server = RpcServer::start(path.clone(), &service).unwrap();
+
+        // 密码管理器的内容按过期规则保存原文
+        let mut item = ClipboardItem::new_text("hunter2".to_string());
+        item.sensitive = Some(crate::sensitive::CONCEALED_LABEL.to_string());
+        item.expires_at = Some(chrono::Utc::now() + chrono::Duration::minutes(1));
+        let id = database
+            .insert_item(&item, crate::storage::database::DEFAULT_MAX_ITEMS)
+            .unwrap();
+
+        let mut client = Client::connect_to(&path).unwrap();
+        let items = client.list(&ListParams::default()).unwrap();
+        assert_eq!(items[0].text, None);
+        assert_eq!(
+            items[0].sensitive.as_deref(),
+            Some(crate::sensitive::CONCEALED_LABEL)
+        );
+        assert_eq!(items[0].expires_at, item.expires_at);
+
+        assert_eq!(client.get(id, false).unwrap().info.text, None);
+        assert_eq!(
+            client.get(id, true).unwrap().info.text.as_deref(),
+            Some("hunter2")
+        );
+    }
+
+    #[test]
+    fn test_subscribe_and_window_forwarding() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_server, path, _control) = start_server(&dir, None);
+        let mut client = Client::connect_to(&path).unwrap();
+
+        // 没有窗口时 show-window 返回错误
+        match client.show_window(WindowMode::Show) {
+            Err(ClipManagerError::Ipc { code, .. }) => assert_eq!(code, NO_WINDOW),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let mut gui = Client::connect_to(&path).unwrap();
+        gui.subscribe(true).unwrap();
+
+        client.show_window(WindowMode::Popup).unwrap();
+        assert_eq!(
+            gui.next_event().unwrap(),
+            Event::Window {
+                mode: WindowMode::Popup
+            }
+        );
+
+        let id = client.add_text("event").unwrap();
+        match gui.next_event().unwrap() {
+            Event::ItemAdded { item } => assert_eq!(item.id, Some(id)),
+            other => panic!("unexpected event: {:?}", other),
+        }
+        client.pause().unwrap();
+        assert_eq!(
+            gui.next_event().unwrap(),
+            Event::RecordingPaused {
+                paused: true,
+                resume_at: None,
+                until_restart: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_local_window_handler() {
+        let dir = tempfile::tempdir().unwrap();
+        let (sender, receiver) = mpsc::channel();
+        let handler: WindowHandler = Box::new(move |mode| sender.send(mode).unwrap());
+        let (server, path, _control) = start_server(&dir, Some(handler));
+
+        let mut client = Client::connect_to(&path).unwrap();
+        client.show_window(WindowMode::Toggle).unwrap();
+        assert_eq!(receiver.recv().unwrap(), WindowMode::Toggle);
+
+        drop(server);
+        assert!(!path.exists());
+    }
+}
@@ -1,14 +1,28 @@
 //! ClipManager - A cross-platform clipboard manager
-//! 
+//!
 //! This library provides the core functionality for the ClipManager application,
 //! including clipboard monitoring, data storage, and configuration management.
 
 pub mod app;
+pub mod cli;
 pub mod clipboard;
 pub mod config;
+pub mod daemon;
 pub mod error;
+pub mod hotkey;
 pub mod i18n;
+pub mod ipc;
+pub mod lock;
+pub mod notify;
+pub mod paste;
+pub mod sensitive;
+pub mod snippets;
 pub mod storage;
+#[cfg(test)]
+mod test_support;
+pub mod transform;
+#[cfg(target_os = "linux")]
+pub mod tray;
 pub mod ui;
 
 // Re-export commonly used types
@@ -17,7 +31,7 @@ pub use config::settings::AppConfig;
 pub use error::{ClipManagerError, Result};
 
 /// Initialize the ClipManager library
-/// 
+///
 /// This function sets up logging and internationalization.
 /// It should be called before using any other library functions.
 pub fn init() -> Result<()> {
@@ -54,6 +68,7 @@ mod tests {
         // Test that initialization doesn't panic
         // Note: We can't test the actual initialization because
         // env_logger can only be initialized once per process
-        assert!(true);
+        i18n::init();
+        assert!(!i18n::t(i18n::TextKey::AppTitle).is_empty());
     }
 }
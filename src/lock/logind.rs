@@ -0,0 +1,163 @@
+// 通过 systemd-logind 检测屏幕锁定
+//
+// 监听当前会话的 `Lock` 信号（`loginctl lock-session`、空闲锁屏），以及
+// 锁屏程序设置的 `LockedHint` 属性。
+
+use std::collections::HashMap;
+use zbus::blocking::connection::Builder;
+use zbus::blocking::{Connection, MessageIterator};
+use zbus::zvariant::{OwnedObjectPath, OwnedValue};
+use zbus::MatchRule;
+
+const LOGIN1: &str = "org.freedesktop.login1";
+const SESSION_INTERFACE: &str = "org.freedesktop.login1.Session";
+
+/// 在后台线程中监听系统总线，会话锁定时调用 `on_lock`
+pub fn watch_system<F>(on_lock: F)
+where
+    F: Fn() + Send + 'static,
+{
+    watch(Builder::system, on_lock)
+}
+
+pub fn watch<'a, B, F>(builder: B, on_lock: F)
+where
+    B: FnOnce() -> zbus::Result<Builder<'a>> + Send + 'static,
+    F: Fn() + Send + 'static,
+{
+    let result = std::thread::Builder::new()
+        .name("screen-lock".to_string())
+        .spawn(move || {
+            if let Err(e) = builder()
+                .and_then(|builder| builder.build())
+                .and_then(|connection| run(&connection, on_lock))
+            {
+                log::warn!("Screen lock detection unavailable: {}", e);
+            }
+        });
+    if let Err(e) = result {
+        log::warn!("Failed to start screen lock watcher: {}", e);
+    }
+}
+
+fn run(connection: &Connection, on_lock: impl Fn()) -> zbus::Result<()> {
+    let reply = connection.call_method(
+        Some(LOGIN1),
+        "/org/freedesktop/login1",
+        Some("org.freedesktop.login1.Manager"),
+        "GetSession",
+        &("auto",),
+    )?;
+    let session: OwnedObjectPath = reply.body().deserialize()?;
+    let rule = MatchRule::builder()
+        .msg_type(zbus::message::Type::Signal)
+        .sender(LOGIN1)?
+        .path(session.clone())?
+        .build();
+    let signals = MessageIterator::for_match_rule(rule, connection, None)?;
+    log::debug!(
+        "Watching logind session {} for screen locks",
+        session.as_str()
+    );
+
+    for message in signals {
+        let message = message?;
+        let header = message.header();
+        let locked = match header.member().map(|member| member.as_str()) {
+            Some("Lock") => header
+                .interface()
+                .is_some_and(|interface| interface == SESSION_INTERFACE),
+            Some("PropertiesChanged") => {
+                let (interface, changed, _): (String, HashMap<String, OwnedValue>, Vec<String>) =
+                    message.body().deserialize()?;
+                interface == SESSION_INTERFACE
+                    && changed
+                        .get("LockedHint")
+                        .and_then(|value| bool::try_from(value).ok())
+                        .unwrap_or(false)
+            }
+            _ => false,
+        };
+        if locked {
+            log::info!("Desktop session locked");
+            on_lock();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::PrivateBus;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    const SESSION_PATH: &str = "/org/freedesktop/login1/session/c1";
+
+    struct MockManager;
+
+    #[zbus::interface(name = "org.freedesktop.login1.Manager")]
+    impl MockManager {
+        fn get_session(&self, _id: String) -> OwnedObjectPath {
+            OwnedObjectPath::try_from(SESSION_PATH).unwrap()
+        }
+    }
+
+    #[test]
+    #[ignore = "requires dbus-daemon"]
+    fn test_lock_signal() {
+        let bus = PrivateBus::start();
+        let logind = zbus::blocking::connection::Builder::address(bus.address())
+            .unwrap()
+            .name(LOGIN1)
+            .unwrap()
+            .serve_at("/org/freedesktop/login1", MockManager)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let (sender, locked) = mpsc::channel();
+        let address = bus.address().to_string();
+        watch(
+            move || Builder::address(address.as_str()),
+            move || {
+                let _ = sender.send(());
+            },
+        );
+
+        // 监听线程订阅前发出的信号会丢失，重复发送直到收到
+        let emit = |member: &str| {
+            for _ in 0..50 {
+                logind
+                    .emit_signal(None::<&str>, SESSION_PATH, SESSION_INTERFACE, member, &())
+                    .unwrap();
+                if locked.recv_timeout(Duration::from_millis(100)).is_ok() {
+                    return true;
+                }
+            }
+            false
+        };
+        assert!(emit("Lock"));
+        std::thread::sleep(Duration::from_millis(200));
+        while locked.try_recv().is_ok() {}
+        // 其他信号不触发锁定
+        logind
+            .emit_signal(None::<&str>, SESSION_PATH, SESSION_INTERFACE, "Unlock", &())
+            .unwrap();
+        assert!(locked.recv_timeout(Duration::from_millis(200)).is_err());
+
+        let mut changed = HashMap::new();
+        changed.insert("LockedHint", zbus::zvariant::Value::from(true));
+        logind
+            .emit_signal(
+                None::<&str>,
+                SESSION_PATH,
+                "org.freedesktop.DBus.Properties",
+                "PropertiesChanged",
+                &(SESSION_INTERFACE, changed, Vec::<&str>::new()),
+            )
+            .unwrap();
+        assert!(locked.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+}
@@ -0,0 +1,353 @@
+//! App lock
+//!
+//! With a passphrase set in [`LockConfig`], the window hides the history
+//! behind a lock screen until the passphrase is entered. Only an Argon2id hash
+//! of the passphrase is stored, as a PHC string in the config file. The window
+//! locks again after the configured idle time or when the desktop session
+//! locks (see [`logind`]). Locking only affects the GUI: capture, the CLI and
+//! the IPC interfaces keep working.
+
+#[cfg(target_os = "linux")]
+pub mod logind;
+
+use crate::config::settings::LockConfig;
+use crate::error::{ClipManagerError, Result};
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// 新口令使用的参数（OWASP 推荐的 19 MiB、2 次迭代）
+const MEMORY_KIB: u32 = 19 * 1024;
+const ITERATIONS: u32 = 2;
+const LANES: u32 = 1;
+const SALT_LEN: usize = 16;
+
+/// 配置中的哈希允许的参数上限，超出时视为配置错误，避免分配过多内存
+const MAX_MEMORY_KIB: u32 = 1024 * 1024;
+const MAX_ITERATIONS: u32 = 10;
+const MAX_LANES: u32 = 16;
+
+fn misconfigured(message: impl std::fmt::Display) -> ClipManagerError {
+    ClipManagerError::Config {
+        message: format!("Invalid passphrase hash in config: {}", message),
+    }
+}
+
+/// 计算口令的 Argon2id 哈希（PHC 格式），使用随机盐
+pub fn hash_passphrase(passphrase: &str) -> Result<String> {
+    let mut salt = [0u8; SALT_LEN];
+    getrandom::fill(&mut salt).map_err(|e| ClipManagerError::Platform {
+        message: format!("Failed to generate salt: {}", e),
+    })?;
+    let params = Params::new(MEMORY_KIB, ITERATIONS, LANES, None).map_err(misconfigured)?;
+    hash_with(passphrase, &salt, params)
+}
+
+fn hash_with(passphrase: &str, salt: &[u8], params: Params) -> Result<String> {
+    let salt = SaltString::encode_b64(salt).map_err(misconfigured)?;
+    let hash = Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password(passphrase.as_bytes(), &salt)
+        .map_err(misconfigured)?;
+    Ok(hash.to_string())
+}
+
+/// 解析配置中的哈希，拒绝其他算法和超出上限的参数
+fn parse_hash(hash: &str) -> Result<PasswordHash<'_>> {
+    let parsed = PasswordHash::new(hash).map_err(misconfigured)?;
+    if parsed.algorithm != argon2::ARGON2ID_IDENT {
+        return Err(misconfigured(format!(
+            "unsupported algorithm '{}'",
+            parsed.algorithm
+        )));
+    }
+    let params = Params::try_from(&parsed).map_err(misconfigured)?;
+    if params.m_cost() > MAX_MEMORY_KIB
+        || !(1..=MAX_ITERATIONS).contains(&params.t_cost())
+        || !(1..=MAX_LANES).contains(&params.p_cost())
+    {
+        return Err(misconfigured(format!(
+            "parameters m={}, t={}, p={} out of range",
+            params.m_cost(),
+            params.t_cost(),
+            params.p_cost()
+        )));
+    }
+    Ok(parsed)
+}
+
+/// 检查口令是否与哈希匹配；哈希无法解析或参数超出范围时返回错误
+pub fn verify_passphrase(passphrase: &str, hash: &str) -> Result<bool> {
+    let parsed = parse_hash(hash)?;
+    match Argon2::default().verify_password(passphrase.as_bytes(), &parsed) {
+        Ok(()) => Ok(true),
+        Err(argon2::password_hash::Error::Password) => Ok(false),
+        Err(e) => Err(misconfigured(e)),
+    }
+}
+
+/// 后台校验口令的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnlockResult {
+    Unlocked,
+    WrongPassphrase,
+    /// 配置中的哈希无效，无法校验
+    Misconfigured(String),
+}
+
+/// 窗口的锁定状态与空闲计时
+pub struct AppLock {
+    locked: bool,
+    last_activity: Instant,
+    /// 正在后台校验的口令
+    verifying: Option<mpsc::Receiver<Result<bool>>>,
+}
+
+impl AppLock {
+    /// 设置了口令时以锁定状态启动
+    pub fn new(config: &LockConfig) -> Self {
+        Self {
+            locked: config.is_enabled(),
+            last_activity: Instant::now(),
+            verifying: None,
+        }
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// 锁定窗口，返回是否刚刚锁定；未设置口令时无效
+    pub fn lock(&mut self, config: &LockConfig, reason: &str) -> bool {
+        if !config.is_enabled() || self.locked {
+            return false;
+        }
+        log::info!("Locking window ({})", reason);
+        self.locked = true;
+        true
+    }
+
+    /// 在后台线程校验口令，避免阻塞界面；完成后调用 `on_done`，再通过 [`Self::poll_unlock`] 取得结果
+    pub fn start_unlock(
+        &mut self,
+        config: &LockConfig,
+        passphrase: String,
+        on_done: impl FnOnce() + Send + 'static,
+    ) {
+        let (sender, receiver) = mpsc::channel();
+        let hash = config.passphrase_hash.clone();
+        std::thread::spawn(move || {
+            let result = hash.map_or(Ok(true), |hash| verify_passphrase(&passphrase, &hash));
+            let _ = sender.send(result);
+            on_done();
+        });
+        self.verifying = Some(receiver);
+    }
+
+    pub fn is_verifying(&self) -> bool {
+        self.verifying.is_some()
+    }
+
+    /// 取得后台校验的结果，口令正确时解锁；尚未完成时返回 None
+    pub fn poll_unlock(&mut self) -> Option<UnlockResult> {
+        let result = match self.verifying.as_ref()?.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return None,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                Err(misconfigured("verification thread exited"))
+            }
+        };
+        self.verifying = None;
+        Some(match result {
+            Ok(true) => {
+                log::info!("Window unlocked");
+                self.locked = false;
+                self.last_activity = Instant::now();
+                UnlockResult::Unlocked
+            }
+            Ok(false) => {
+                log::warn!("Wrong passphrase entered on lock screen");
+                UnlockResult::WrongPassphrase
+            }
+            Err(e) => {
+                log::error!("Cannot verify passphrase: {}", e);
+                UnlockResult::Misconfigured(e.to_string())
+            }
+        })
+    }
+
+    /// 记录用户操作，重新开始空闲计时，返回是否刚刚锁定
+    ///
+    /// 已超过空闲期限时先锁定：窗口从托盘重新显示时产生的第一个事件不能重置计时。
+    pub fn touch(&mut self, config: &LockConfig, now: Instant) -> bool {
+        if self.check_idle(config, now) {
+            return true;
+        }
+        self.last_activity = now;
+        false
+    }
+
+    /// 配置变化后调用，移除口令时解除锁定
+    pub fn apply_config(&mut self, config: &LockConfig) {
+        if !config.is_enabled() {
+            self.locked = false;
+        }
+    }
+
+    /// 空闲到这个时间将自动锁定
+    pub fn idle_deadline(&self, config: &LockConfig) -> Option<Instant> {
+        (config.is_enabled() && !self.locked && config.idle_minutes > 0)
+            .then(|| self.last_activity + Duration::from_secs(u64::from(config.idle_minutes) * 60))
+    }
+
+    /// 空闲超时则锁定，返回是否刚刚锁定
+    pub fn check_idle(&mut self, config: &LockConfig, now: Instant) -> bool {
+        match self.idle_deadline(config) {
+            Some(deadline) if now >= deadline => self.lock(config, "idle timeout"),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_params() -> Params {
+        // 测试使用很小的代价参数，避免拖慢测试
+        Params::new(64, 1, 1, None).unwrap()
+    }
+
+    fn test_config(passphrase: &str) -> LockConfig {
+        LockConfig {
+            passphrase_hash: Some(
+                hash_with(passphrase, b"0123456789abcdef", test_params()).unwrap(),
+            ),
+            ..LockConfig::default()
+        }
+    }
+
+    /// 同步等待后台校验完成
+    fn unlock(lock: &mut AppLock, config: &LockConfig, passphrase: &str) -> UnlockResult {
+        let (sender, receiver) = mpsc::channel();
+        lock.start_unlock(config, passphrase.to_string(), move || {
+            let _ = sender.send(());
+        });
+        assert!(lock.is_verifying());
+        receiver.recv().unwrap();
+        let result = lock.poll_unlock().unwrap();
+        assert!(!lock.is_verifying());
+        result
+    }
+
+    #[test]
+    fn test_hash_and_verify() {
+        let hash = hash_with("correct horse", b"0123456789abcdef", test_params()).unwrap();
+        assert!(hash.starts_with("$argon2id$v=19$m=64,t=1,p=1$MDEyMzQ1Njc4OWFiY2RlZg$"));
+        assert!(!hash.ends_with('='));
+        assert!(verify_passphrase("correct horse", &hash).unwrap());
+        assert!(!verify_passphrase("correct horsE", &hash).unwrap());
+        assert!(!verify_passphrase("", &hash).unwrap());
+
+        assert!(verify_passphrase("correct horse", "plain text").is_err());
+        assert!(verify_passphrase(
+            "correct horse",
+            "$argon2id$v=19$m=64,t=1$MDEyMzQ1Njc4OWFiY2RlZg$AAAA"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_reject_out_of_range_params() {
+        let hash = hash_with("secret", b"0123456789abcdef", test_params()).unwrap();
+        let tail = hash.rsplitn(3, '$').collect::<Vec<_>>();
+        let (salt, output) = (tail[1], tail[0]);
+        for params in [
+            "m=2097152,t=1,p=1",
+            "m=64,t=0,p=1",
+            "m=64,t=11,p=1",
+            "m=64,t=1,p=17",
+        ] {
+            let hash = format!("$argon2id$v=19${}${}${}", params, salt, output);
+            assert!(verify_passphrase("secret", &hash).is_err(), "{}", params);
+        }
+        let argon2i = hash.replacen("argon2id", "argon2i", 1);
+        assert!(verify_passphrase("secret", &argon2i).is_err());
+    }
+
+    #[test]
+    fn test_lock_and_unlock() {
+        let config = test_config("secret");
+        let mut lock = AppLock::new(&config);
+        assert!(lock.is_locked());
+        assert_eq!(lock.poll_unlock(), None);
+        assert_eq!(
+            unlock(&mut lock, &config, "wrong"),
+            UnlockResult::WrongPassphrase
+        );
+        assert!(lock.is_locked());
+        assert_eq!(unlock(&mut lock, &config, "secret"), UnlockResult::Unlocked);
+        assert!(!lock.is_locked());
+
+        assert!(lock.lock(&config, "screen locked"));
+        assert!(lock.is_locked());
+        assert!(!lock.lock(&config, "screen locked"));
+
+        // 损坏的哈希不会被当作口令错误
+        let broken = LockConfig {
+            passphrase_hash: Some("$argon2id$v=19$m=4194304,t=1,p=1$c29tZXNhbHQ$AAAA".to_string()),
+            ..LockConfig::default()
+        };
+        assert!(matches!(
+            unlock(&mut lock, &broken, "secret"),
+            UnlockResult::Misconfigured(_)
+        ));
+        assert!(lock.is_locked());
+
+        // 移除口令后不再锁定
+        let disabled = LockConfig::default();
+        lock.apply_config(&disabled);
+        assert!(!lock.is_locked());
+        assert!(!lock.lock(&disabled, "screen locked"));
+        assert!(!lock.is_locked());
+        assert!(!AppLock::new(&disabled).is_locked());
+    }
+
+    #[test]
+    fn test_idle_lock() {
+        let config = LockConfig {
+            idle_minutes: 5,
+            ..test_config("secret")
+        };
+        let mut lock = AppLock::new(&config);
+        unlock(&mut lock, &config, "secret");
+        let start = Instant::now();
+        lock.touch(&config, start);
+        assert_eq!(
+            lock.idle_deadline(&config),
+            Some(start + Duration::from_secs(300))
+        );
+
+        assert!(!lock.check_idle(&config, start + Duration::from_secs(299)));
+        assert!(!lock.touch(&config, start + Duration::from_secs(200)));
+        assert!(!lock.check_idle(&config, start + Duration::from_secs(400)));
+        assert!(lock.check_idle(&config, start + Duration::from_secs(500)));
+        assert!(lock.is_locked());
+        assert_eq!(lock.idle_deadline(&config), None);
+
+        // 超过期限后的第一个操作依然锁定
+        unlock(&mut lock, &config, "secret");
+        let start = Instant::now();
+        lock.touch(&config, start);
+        assert!(lock.touch(&config, start + Duration::from_secs(301)));
+        assert!(lock.is_locked());
+
+        // 0 分钟表示不自动锁定
+        let never = LockConfig {
+            idle_minutes: 0,
+            ..config
+        };
+        unlock(&mut lock, &never, "secret");
+        assert_eq!(lock.idle_deadline(&never), None);
+    }
+}
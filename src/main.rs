@@ -4,20 +4,48 @@
 // 注意：如果程序启动失败，用户可能看不到错误信息
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-mod app;
-mod clipboard;
-mod config;
-mod error;
-mod i18n;
-mod storage;
-mod ui;
-
-use app::ClipManagerApp;
-use config::settings::AppConfig;
+use clipmanager::app::ClipManagerApp;
+use clipmanager::cli;
+use clipmanager::config::settings::AppConfig;
+use clipmanager::daemon;
+use clipmanager::i18n;
+use clipmanager::ipc::{self, ControlCommand};
+use clipmanager::ui::FontManager;
 use eframe::egui;
-use ui::FontManager;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let matches = cli::command().get_matches();
+    match matches.subcommand_name() {
+        None => {}
+        // `clipmanager daemon`：不开窗口，只在后台记录剪切板
+        Some("daemon") => {
+            setup_logging()?;
+            let config = AppConfig::load().unwrap_or_default();
+            if let Err(e) =
+                cli::database_path(&matches).and_then(|path| daemon::run(&config, &path))
+            {
+                eprintln!("clipmanager daemon: {}", e);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(name) => match ControlCommand::parse(name) {
+            // `clipmanager toggle` / `show` / `popup`：交给已运行的实例处理，
+            // 可在桌面环境的快捷键设置中绑定；没有运行中的实例时正常启动
+            Some(command) if !command.is_monitor_command() => {
+                if ipc::send_command(command).is_ok() {
+                    return Ok(());
+                }
+            }
+            // 其余子命令操作历史记录或控制记录状态，不启动窗口
+            _ => {
+                env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn"))
+                    .init();
+                std::process::exit(cli::run(&matches));
+            }
+        },
+    }
+
     // 在 Windows 上设置更好的错误处理
     if let Err(e) = run_app() {
         show_error_message(&format!("ClipManager 启动失败: {}", e));
@@ -36,11 +64,16 @@ fn run_app() -> Result<(), Box<dyn std::error::Error>> {
     log::info!("Starting ClipManager");
 
     // 设置应用程序选项
+    let config = AppConfig::load().unwrap_or_default();
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_inner_size([config.window.width, config.window.height])
+        .with_min_inner_size([400.0, 300.0])
+        .with_icon(load_icon());
+    if config.window.always_on_top {
+        viewport = viewport.with_always_on_top();
+    }
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_inner_size([600.0, 500.0])
-            .with_min_inner_size([400.0, 300.0])
-            .with_icon(load_icon()),
+        viewport,
         ..Default::default()
     };
 
@@ -163,16 +196,20 @@ fn setup_logging() -> Result<(), Box<dyn std::error::Error>> {
         let log_file = log_dir.join("clipmanager.log");
 
         // 设置文件日志
-        let target = Box::new(OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(log_file)?);
+        let target = Box::new(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(log_file)?,
+        );
 
         builder
             .target(env_logger::Target::Pipe(target))
             .filter_level(log::LevelFilter::Info)
             .format(|buf, record| {
-                writeln!(buf, "{} [{}] {}",
+                writeln!(
+                    buf,
+                    "{} [{}] {}",
                     chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
                     record.level(),
                     record.args()
@@ -207,7 +244,11 @@ fn show_error_message(message: &str) {
         if let Ok(mut file) = std::fs::File::create(&error_file_path) {
             use std::io::Write;
             let _ = writeln!(file, "ClipManager 错误报告");
-            let _ = writeln!(file, "时间: {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"));
+            let _ = writeln!(
+                file,
+                "时间: {}",
+                chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
+            );
             let _ = writeln!(file, "错误: {}", message);
             let _ = writeln!(file, "\n请将此文件发送给开发者以获取帮助。");
             let _ = writeln!(file, "文件位置: {}", error_file_path.display());
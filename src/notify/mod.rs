@@ -0,0 +1,273 @@
+//! Desktop notifications
+//!
+//! Sends freedesktop notifications (`org.freedesktop.Notifications`) from a
+//! background thread. Every category can be switched off separately and is
+//! throttled on its own, so a burst of copies does not flood the desktop.
+
+use crate::config::settings::{AppConfig, NotificationConfig};
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// 通知类别，各自有独立的开关和节流
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NotificationCategory {
+    ItemCopied,
+    RecordingPaused,
+    SensitiveSkipped,
+    /// 保存或备份历史记录失败
+    StorageFailed,
+}
+
+impl NotificationCategory {
+    fn is_enabled(&self, config: &NotificationConfig) -> bool {
+        match self {
+            NotificationCategory::ItemCopied => config.item_copied,
+            NotificationCategory::RecordingPaused => config.recording_paused,
+            NotificationCategory::SensitiveSkipped => config.sensitive_skipped,
+            NotificationCategory::StorageFailed => config.storage_failed,
+        }
+    }
+
+    fn urgency(&self) -> u8 {
+        match self {
+            // 0 = low, 1 = normal, 2 = critical
+            NotificationCategory::ItemCopied => 0,
+            NotificationCategory::StorageFailed => 2,
+            _ => 1,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Notification {
+    category: NotificationCategory,
+    summary: String,
+    body: String,
+}
+
+pub struct Notifier {
+    sender: Option<mpsc::Sender<Notification>>,
+    show_notifications: bool,
+    config: NotificationConfig,
+    last_sent: HashMap<NotificationCategory, Instant>,
+}
+
+impl Notifier {
+    /// 使用会话总线发送通知
+    pub fn new(config: &AppConfig) -> Self {
+        #[cfg(target_os = "linux")]
+        {
+            Self::with_sender(
+                config,
+                dbus::spawn(zbus::blocking::connection::Builder::session),
+            )
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            Self::with_sender(config, None)
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn with_bus_address(config: &AppConfig, address: &str) -> Self {
+        let address = address.to_string();
+        Self::with_sender(
+            config,
+            dbus::spawn(move || zbus::blocking::connection::Builder::address(address.as_str())),
+        )
+    }
+
+    fn with_sender(config: &AppConfig, sender: Option<mpsc::Sender<Notification>>) -> Self {
+        Self {
+            sender,
+            show_notifications: config.show_notifications,
+            config: config.notifications.clone(),
+            last_sent: HashMap::new(),
+        }
+    }
+
+    pub fn set_config(&mut self, config: &AppConfig) {
+        self.show_notifications = config.show_notifications;
+        self.config = config.notifications.clone();
+    }
+
+    /// 发送通知；被关闭或处于节流间隔内时返回 false
+    pub fn notify(&mut self, category: NotificationCategory, summary: &str, body: &str) -> bool {
+        if !self.show_notifications || !category.is_enabled(&self.config) {
+            return false;
+        }
+
+        let now = Instant::now();
+        let throttle = Duration::from_secs(self.config.throttle_secs);
+        if let Some(last) = self.last_sent.get(&category) {
+            if now.duration_since(*last) < throttle {
+                log::debug!("Notification {:?} throttled", category);
+                return false;
+            }
+        }
+        self.last_sent.insert(category, now);
+
+        let Some(sender) = &self.sender else {
+            return false;
+        };
+        sender
+            .send(Notification {
+                category,
+                summary: summary.to_string(),
+                body: body.to_string(),
+            })
+            .is_ok()
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod dbus {
+    use super::{Notification, NotificationCategory};
+    use std::collections::HashMap;
+    use std::sync::mpsc;
+    use zbus::zvariant::Value;
+
+    /// 启动发送线程；连接在线程中建立，避免阻塞 UI
+    pub(super) fn spawn<'a, F>(builder: F) -> Option<mpsc::Sender<Notification>>
+    where
+        F: FnOnce() -> zbus::Result<zbus::blocking::connection::Builder<'a>> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel::<Notification>();
+        let result = std::thread::Builder::new()
+            .name("notifications".to_string())
+            .spawn(move || {
+                let connection = match builder().and_then(|builder| builder.build()) {
+                    Ok(connection) => connection,
+                    Err(e) => {
+                        log::warn!("Desktop notifications unavailable: {}", e);
+                        return;
+                    }
+                };
+                // 同一类别的新通知替换旧通知
+                let mut replaces: HashMap<NotificationCategory, u32> = HashMap::new();
+
+                while let Ok(notification) = receiver.recv() {
+                    let replaces_id = replaces.get(&notification.category).copied().unwrap_or(0);
+                    let mut hints = HashMap::new();
+                    hints.insert("urgency", Value::from(notification.category.urgency()));
+
+                    let reply = connection.call_method(
+                        Some("org.freedesktop.Notifications"),
+                        "/org/freedesktop/Notifications",
+                        Some("org.freedesktop.Notifications"),
+                        "Notify",
+                        &(
+                            "ClipManager",
+                            replaces_id,
+                            "edit-paste",
+                            notification.summary.as_str(),
+                            notification.body.as_str(),
+                            Vec::<&str>::new(),
+                            hints,
+                            -1i32,
+                        ),
+                    );
+                    match reply.and_then(|message| message.body().deserialize::<u32>()) {
+                        Ok(id) => {
+                            replaces.insert(notification.category, id);
+                        }
+                        Err(e) => log::warn!("Failed to send notification: {}", e),
+                    }
+                }
+            });
+
+        match result {
+            Ok(_) => Some(sender),
+            Err(e) => {
+                log::warn!("Failed to start notification thread: {}", e);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use crate::test_support::PrivateBus;
+    use std::sync::Mutex;
+    use zbus::zvariant::OwnedValue;
+
+    /// 记录收到的通知的通知服务
+    struct MockDaemon {
+        received: Mutex<mpsc::Sender<(u32, String, String)>>,
+        next_id: Mutex<u32>,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.Notifications")]
+    impl MockDaemon {
+        #[allow(clippy::too_many_arguments)]
+        fn notify(
+            &self,
+            _app_name: String,
+            replaces_id: u32,
+            _app_icon: String,
+            summary: String,
+            body: String,
+            _actions: Vec<String>,
+            _hints: HashMap<String, OwnedValue>,
+            _expire_timeout: i32,
+        ) -> u32 {
+            let mut next_id = self.next_id.lock().unwrap();
+            *next_id += 1;
+            let _ = self
+                .received
+                .lock()
+                .unwrap()
+                .send((replaces_id, summary, body));
+            *next_id
+        }
+    }
+
+    #[test]
+    #[ignore = "requires dbus-daemon"]
+    fn test_notifications_on_mock_daemon() {
+        let bus = PrivateBus::start();
+        let (sender, received) = mpsc::channel();
+        let _daemon = zbus::blocking::connection::Builder::address(bus.address())
+            .unwrap()
+            .name("org.freedesktop.Notifications")
+            .unwrap()
+            .serve_at(
+                "/org/freedesktop/Notifications",
+                MockDaemon {
+                    received: Mutex::new(sender),
+                    next_id: Mutex::new(0),
+                },
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut config = AppConfig::default();
+        config.notifications.item_copied = true;
+        config.notifications.sensitive_skipped = false;
+        config.notifications.throttle_secs = 60;
+        let mut notifier = Notifier::with_bus_address(&config, bus.address());
+        let timeout = Duration::from_secs(5);
+
+        assert!(notifier.notify(NotificationCategory::ItemCopied, "Copied", "first"));
+        assert_eq!(
+            received.recv_timeout(timeout).unwrap(),
+            (0, "Copied".to_string(), "first".to_string())
+        );
+
+        // 同类通知在节流间隔内被丢弃，其他类别不受影响
+        assert!(!notifier.notify(NotificationCategory::ItemCopied, "Copied", "second"));
+        assert!(!notifier.notify(NotificationCategory::SensitiveSkipped, "Skipped", ""));
+        assert!(notifier.notify(NotificationCategory::RecordingPaused, "Paused", ""));
+        assert_eq!(received.recv_timeout(timeout).unwrap().1, "Paused");
+
+        config.show_notifications = false;
+        notifier.set_config(&config);
+        assert!(!notifier.notify(NotificationCategory::StorageFailed, "Failed", ""));
+        assert!(received.recv_timeout(Duration::from_millis(200)).is_err());
+    }
+}
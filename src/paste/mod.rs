@@ -0,0 +1,153 @@
+//! Paste on select
+//!
+//! Remembers the window that had focus before ClipManager was summoned and,
+//! after an item is picked, restores focus to it and sends a synthetic paste
+//! keystroke — via XTest on X11 or an external helper command (`wtype`,
+//! `ydotool`, `xdotool`) configured in [`PasteConfig`].
+
+#[cfg(target_os = "linux")]
+mod x11;
+
+use crate::config::keybindings::Accelerator;
+use crate::config::paste::{PasteConfig, PasteMethod};
+use crate::error::{ClipManagerError, Result};
+use std::process::Command;
+use std::time::Duration;
+
+/// 唤起 ClipManager 之前获得焦点的窗口
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasteTarget {
+    pub window: u32,
+    /// WM_CLASS 中的类名，用于匹配按应用的粘贴按键
+    pub app_class: Option<String>,
+}
+
+/// 记录当前获得焦点的窗口；如果焦点已经在本程序窗口上则返回 None
+pub fn capture_target() -> Option<PasteTarget> {
+    #[cfg(target_os = "linux")]
+    {
+        x11::active_window()
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// 恢复目标窗口的焦点并发送粘贴按键
+///
+/// 无法恢复焦点时不发送按键，避免粘贴到错误的窗口。会阻塞 `delay_ms`，
+/// 等待窗口管理器完成焦点切换，应在后台线程中调用。
+pub fn paste_into(target: &PasteTarget, config: &PasteConfig) -> Result<()> {
+    #[cfg(target_os = "linux")]
+    x11::activate(target.window).map_err(|e| ClipManagerError::Platform {
+        message: format!(
+            "Failed to restore focus to window {:#x}: {}",
+            target.window, e
+        ),
+    })?;
+    std::thread::sleep(Duration::from_millis(config.delay_ms));
+    send_paste(target.app_class.as_deref(), config)
+}
+
+/// 向当前获得焦点的窗口发送粘贴按键
+fn send_paste(app_class: Option<&str>, config: &PasteConfig) -> Result<()> {
+    let keystroke: Accelerator = config
+        .keystroke_for(app_class)
+        .parse()
+        .map_err(|message| ClipManagerError::Config { message })?;
+
+    log::info!(
+        "Pasting into {} with {}",
+        app_class.unwrap_or("previous window"),
+        keystroke
+    );
+    match config.method {
+        PasteMethod::XTest => send_xtest(&keystroke),
+        PasteMethod::Command => run_command(&config.command_for(&keystroke)),
+        PasteMethod::Auto => send_xtest(&keystroke).or_else(|e| {
+            log::debug!("XTest unavailable ({}), falling back to paste command", e);
+            run_command(&config.command_for(&keystroke))
+        }),
+    }
+}
+
+/// 粘贴后把光标左移 `count` 个字符，用于片段中的 `{cursor}`
+///
+/// 只支持 XTest：粘贴命令的按键名称因工具而异，无法可靠地表示方向键。
+pub fn move_cursor_left(count: usize, config: &PasteConfig) -> Result<()> {
+    if count == 0 {
+        return Ok(());
+    }
+    if config.method == PasteMethod::Command {
+        return Err(ClipManagerError::Platform {
+            message: "Moving the cursor needs the XTest paste method".to_string(),
+        });
+    }
+    let left: Accelerator = "Left"
+        .parse()
+        .map_err(|message| ClipManagerError::Config { message })?;
+    for _ in 0..count {
+        send_xtest(&left)?;
+    }
+    Ok(())
+}
+
+fn send_xtest(keystroke: &Accelerator) -> Result<()> {
+    #[cfg(target_os = "linux")]
+    {
+        x11::send_keystroke(keystroke).map_err(|message| ClipManagerError::Platform { message })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = keystroke;
+        Err(ClipManagerError::Platform {
+            message: "XTest is only available on X11".to_string(),
+        })
+    }
+}
+
+fn run_command(command: &str) -> Result<()> {
+    if command.trim().is_empty() {
+        return Err(ClipManagerError::Config {
+            message: "No paste command configured".to_string(),
+        });
+    }
+
+    let status = Command::new("sh").arg("-c").arg(command).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(ClipManagerError::Platform {
+            message: format!("Paste command '{}' exited with {}", command, status),
+        })
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paste_via_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("keys");
+        let config = PasteConfig {
+            method: PasteMethod::Command,
+            command: format!("echo {{keys}} > {}", output.display()),
+            delay_ms: 0,
+            ..Default::default()
+        };
+        send_paste(Some("kitty"), &config).unwrap();
+        let keys = std::fs::read_to_string(&output).unwrap();
+        assert_eq!(keys.trim(), "ctrl+shift+v");
+
+        let failing = PasteConfig {
+            command: "exit 3".to_string(),
+            ..config
+        };
+        assert!(send_paste(None, &failing).is_err());
+    }
+}
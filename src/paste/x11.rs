@@ -0,0 +1,146 @@
+// X11 焦点恢复与 XTest 按键模拟
+
+use super::PasteTarget;
+use crate::config::keybindings::Accelerator;
+use crate::hotkey::keysym;
+use crate::hotkey::x11::find_keycode;
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{
+    AtomEnum, ClientMessageEvent, ConnectionExt, EventMask, KEY_PRESS_EVENT, KEY_RELEASE_EVENT,
+};
+use x11rb::protocol::xtest::ConnectionExt as _;
+use x11rb::rust_connection::RustConnection;
+use x11rb::CURRENT_TIME;
+
+// 修饰键的 keysym（左侧按键）
+const XK_CONTROL_L: u32 = 0xffe3;
+const XK_SHIFT_L: u32 = 0xffe1;
+const XK_ALT_L: u32 = 0xffe9;
+const XK_SUPER_L: u32 = 0xffeb;
+
+fn connect() -> Result<(RustConnection, u32), String> {
+    let (conn, screen_num) =
+        x11rb::connect(None).map_err(|e| format!("Cannot connect to X server: {}", e))?;
+    let root = conn.setup().roots[screen_num].root;
+    Ok((conn, root))
+}
+
+fn intern(conn: &RustConnection, name: &str) -> Result<u32, String> {
+    Ok(conn
+        .intern_atom(false, name.as_bytes())
+        .map_err(|e| e.to_string())?
+        .reply()
+        .map_err(|e| e.to_string())?
+        .atom)
+}
+
+/// 读取 _NET_ACTIVE_WINDOW 以及该窗口的 WM_CLASS
+pub fn active_window() -> Option<PasteTarget> {
+    let (conn, root) = connect().ok()?;
+    let active_atom = intern(&conn, "_NET_ACTIVE_WINDOW").ok()?;
+    let window = conn
+        .get_property(false, root, active_atom, AtomEnum::WINDOW, 0, 1)
+        .ok()?
+        .reply()
+        .ok()?
+        .value32()?
+        .next()
+        .filter(|&window| window != 0)?;
+
+    // 焦点已经在本程序上时没有可以恢复的目标
+    let pid_atom = intern(&conn, "_NET_WM_PID").ok()?;
+    let pid = conn
+        .get_property(false, window, pid_atom, AtomEnum::CARDINAL, 0, 1)
+        .ok()?
+        .reply()
+        .ok()
+        .and_then(|reply| reply.value32().and_then(|mut values| values.next()));
+    if pid == Some(std::process::id()) {
+        return None;
+    }
+
+    // WM_CLASS 由 "instance\0class\0" 组成
+    let app_class = conn
+        .get_property(false, window, AtomEnum::WM_CLASS, AtomEnum::STRING, 0, 256)
+        .ok()?
+        .reply()
+        .ok()
+        .and_then(|reply| {
+            let value = String::from_utf8_lossy(&reply.value).into_owned();
+            value
+                .split('\0')
+                .rfind(|part| !part.is_empty())
+                .map(str::to_string)
+        });
+
+    Some(PasteTarget { window, app_class })
+}
+
+/// 通过 _NET_ACTIVE_WINDOW 请求窗口管理器激活窗口
+pub fn activate(window: u32) -> Result<(), String> {
+    let (conn, root) = connect()?;
+    let active_atom = intern(&conn, "_NET_ACTIVE_WINDOW")?;
+    let event = ClientMessageEvent::new(32, window, active_atom, [2, CURRENT_TIME, 0, 0, 0]);
+    conn.send_event(
+        false,
+        root,
+        EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+        event,
+    )
+    .map_err(|e| e.to_string())?
+    .check()
+    .map_err(|e| format!("{:?}", e))?;
+    Ok(())
+}
+
+/// 使用 XTest 依次按下修饰键和按键，再按相反顺序释放
+pub fn send_keystroke(keystroke: &Accelerator) -> Result<(), String> {
+    let (conn, root) = connect()?;
+    conn.xtest_get_version(2, 2)
+        .map_err(|e| e.to_string())?
+        .reply()
+        .map_err(|e| format!("XTest extension unavailable: {}", e))?;
+
+    let mut syms = Vec::new();
+    if keystroke.ctrl {
+        syms.push(XK_CONTROL_L);
+    }
+    if keystroke.shift {
+        syms.push(XK_SHIFT_L);
+    }
+    if keystroke.alt {
+        syms.push(XK_ALT_L);
+    }
+    if keystroke.super_key {
+        syms.push(XK_SUPER_L);
+    }
+    syms.push(
+        keysym(&keystroke.key).ok_or_else(|| format!("Unsupported key '{}'", keystroke.key))?,
+    );
+
+    let keycodes = syms
+        .into_iter()
+        .map(|sym| {
+            find_keycode(&conn, sym)?.ok_or_else(|| format!("No keycode for keysym {:#x}", sym))
+        })
+        .collect::<Result<Vec<u8>, String>>()?;
+
+    let send = |type_: u8, keycode: u8| {
+        conn.xtest_fake_input(type_, keycode, CURRENT_TIME, root, 0, 0, 0)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    };
+    for &keycode in &keycodes {
+        send(KEY_PRESS_EVENT, keycode)?;
+    }
+    for &keycode in keycodes.iter().rev() {
+        send(KEY_RELEASE_EVENT, keycode)?;
+    }
+
+    // 等待服务器处理完所有请求
+    conn.get_input_focus()
+        .map_err(|e| e.to_string())?
+        .reply()
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
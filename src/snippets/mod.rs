@@ -0,0 +1,200 @@
+//! Snippet library
+//!
+//! Snippets are reusable texts kept apart from the captured history: canned
+//! responses, signatures, boilerplate. Each has a name, an optional
+//! abbreviation to find it quickly, a folder and a body template whose
+//! placeholders (see [`template`]) are filled in when the snippet is used from
+//! the picker or `clipmanager snippet expand`. Snippets live in the history
+//! database and can be exported to or imported from TOML or JSON files.
+
+pub mod template;
+
+use crate::error::{ClipManagerError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use template::Template;
+
+/// 导出文件格式版本
+const SNIPPET_FILE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snippet {
+    /// 数据库中的 ID，导出时省略
+    #[serde(skip)]
+    pub id: Option<i64>,
+    pub name: String,
+    /// 搜索时优先精确匹配的缩写
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub abbreviation: String,
+    /// 所属文件夹，为空表示顶层
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub folder: String,
+    pub body: String,
+    #[serde(skip, default = "Utc::now")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Snippet {
+    pub fn new(name: &str, body: &str) -> Self {
+        Self {
+            id: None,
+            name: name.to_string(),
+            abbreviation: String::new(),
+            folder: String::new(),
+            body: body.to_string(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// 带文件夹的完整名称，如 `mail/thanks`
+    pub fn path(&self) -> String {
+        if self.folder.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}/{}", self.folder, self.name)
+        }
+    }
+
+    pub fn template(&self) -> Template {
+        Template::parse(&self.body)
+    }
+
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Every snippet needs a name".to_string());
+        }
+        if self.name.contains('/') {
+            return Err(format!("Snippet name '{}' cannot contain '/'", self.name));
+        }
+        if self.abbreviation.chars().any(char::is_whitespace) {
+            return Err(format!(
+                "Abbreviation '{}' cannot contain spaces",
+                self.abbreviation
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 片段导入导出文件的格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Toml,
+    Json,
+}
+
+impl FileFormat {
+    /// 按扩展名判断，`.toml` 以外都视为 JSON
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("toml") => Self::Toml,
+            _ => Self::Json,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SnippetFile {
+    #[serde(default = "default_version")]
+    version: u32,
+    #[serde(default)]
+    snippets: Vec<Snippet>,
+}
+
+fn default_version() -> u32 {
+    SNIPPET_FILE_VERSION
+}
+
+fn format_error(message: impl std::fmt::Display) -> ClipManagerError {
+    ClipManagerError::Config {
+        message: format!("Invalid snippet file: {}", message),
+    }
+}
+
+pub fn export(snippets: &[Snippet], format: FileFormat) -> Result<String> {
+    let file = SnippetFile {
+        version: SNIPPET_FILE_VERSION,
+        snippets: snippets.to_vec(),
+    };
+    match format {
+        FileFormat::Toml => toml::to_string_pretty(&file).map_err(format_error),
+        FileFormat::Json => Ok(serde_json::to_string_pretty(&file)? + "\n"),
+    }
+}
+
+/// 解析导出文件，检查每个片段
+pub fn import(content: &str, format: FileFormat) -> Result<Vec<Snippet>> {
+    let file: SnippetFile = match format {
+        FileFormat::Toml => toml::from_str(content).map_err(format_error)?,
+        FileFormat::Json => serde_json::from_str(content).map_err(format_error)?,
+    };
+    if file.version > SNIPPET_FILE_VERSION {
+        return Err(format_error(format!(
+            "unsupported version {}",
+            file.version
+        )));
+    }
+    for snippet in &file.snippets {
+        snippet.validate().map_err(format_error)?;
+    }
+    Ok(file.snippets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples() -> Vec<Snippet> {
+        let mut thanks = Snippet::new("thanks", "Thanks {field:Name}!\n\n{cursor}");
+        thanks.abbreviation = "ty".to_string();
+        thanks.folder = "mail".to_string();
+        vec![thanks, Snippet::new("today", "{date}")]
+    }
+
+    #[test]
+    fn test_export_import_roundtrip() {
+        for format in [FileFormat::Toml, FileFormat::Json] {
+            let exported = export(&samples(), format).unwrap();
+            let imported = import(&exported, format).unwrap();
+            assert_eq!(imported.len(), 2);
+            assert_eq!(imported[0].path(), "mail/thanks");
+            assert_eq!(imported[0].abbreviation, "ty");
+            assert_eq!(imported[0].body, samples()[0].body);
+            assert_eq!(imported[1].path(), "today");
+        }
+    }
+
+    #[test]
+    fn test_import_hand_written_toml() {
+        let snippets = import(
+            r#"
+[[snippets]]
+name = "sig"
+body = """
+Best regards,
+Ann"""
+"#,
+            FileFormat::Toml,
+        )
+        .unwrap();
+        assert_eq!(snippets[0].body, "Best regards,\nAnn");
+        assert!(snippets[0].folder.is_empty());
+
+        assert!(import(
+            r#"{"snippets": [{"name": "", "body": "x"}]}"#,
+            FileFormat::Json
+        )
+        .is_err());
+        assert!(import(r#"{"version": 9, "snippets": []}"#, FileFormat::Json).is_err());
+        assert!(import("not toml [", FileFormat::Toml).is_err());
+        assert_eq!(
+            FileFormat::from_path(Path::new("snippets.TOML")),
+            FileFormat::Toml
+        );
+        assert_eq!(
+            FileFormat::from_path(Path::new("snippets.json")),
+            FileFormat::Json
+        );
+    }
+}
@@ -0,0 +1,249 @@
+// 片段正文中的占位符解析与展开
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
+
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+const DEFAULT_TIME_FORMAT: &str = "%H:%M";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Placeholder {
+    /// 当前日期或时间，使用 strftime 格式
+    Now(String),
+    Clipboard,
+    Cursor,
+    Field(Field),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Text(String),
+    Placeholder(Placeholder),
+}
+
+/// 展开前需要用户填写的字段，`{field:名称=默认值}`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub default: String,
+}
+
+/// 展开占位符时使用的值
+pub struct Values<'a> {
+    pub now: DateTime<Local>,
+    /// `{clipboard}` 的内容
+    pub clipboard: &'a str,
+    /// 用户填写的字段，缺少的字段使用默认值
+    pub fields: &'a HashMap<String, String>,
+}
+
+/// 展开结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expansion {
+    pub text: String,
+    /// `{cursor}` 在文本中的字节位置
+    pub cursor: Option<usize>,
+}
+
+impl Expansion {
+    /// 粘贴后需要向左移动光标的字符数
+    pub fn chars_after_cursor(&self) -> usize {
+        self.cursor
+            .map_or(0, |cursor| self.text[cursor..].chars().count())
+    }
+}
+
+/// 解析后的片段正文
+///
+/// `{date}`、`{time}` 可以带 strftime 格式（`{date:%d.%m.%Y}`），`{{` 表示字面的
+/// `{`。无法识别的 `{...}` 原样保留，因此正文中的代码或 JSON 通常无需转义。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    pub fn parse(body: &str) -> Self {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut rest = body;
+
+        while let Some(position) = rest.find('{') {
+            text.push_str(&rest[..position]);
+            let tail = &rest[position..];
+            if let Some(after) = tail.strip_prefix("{{") {
+                text.push('{');
+                rest = after;
+                continue;
+            }
+
+            // 到下一个右花括号之前没有左花括号时才可能是占位符
+            let placeholder = tail[1..]
+                .find(['{', '}'])
+                .filter(|&end| tail[1 + end..].starts_with('}'))
+                .and_then(|end| Some((Self::placeholder(&tail[1..1 + end])?, end)));
+            match placeholder {
+                Some((placeholder, end)) => {
+                    if !text.is_empty() {
+                        segments.push(Segment::Text(std::mem::take(&mut text)));
+                    }
+                    segments.push(Segment::Placeholder(placeholder));
+                    rest = &tail[end + 2..];
+                }
+                None => {
+                    text.push('{');
+                    rest = &tail[1..];
+                }
+            }
+        }
+        text.push_str(rest);
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+        Self { segments }
+    }
+
+    fn placeholder(inner: &str) -> Option<Placeholder> {
+        let (name, argument) = match inner.split_once(':') {
+            Some((name, argument)) => (name, Some(argument)),
+            None => (inner, None),
+        };
+        let placeholder = match (name, argument) {
+            ("date", None) => Placeholder::Now(DEFAULT_DATE_FORMAT.to_string()),
+            ("time", None) => Placeholder::Now(DEFAULT_TIME_FORMAT.to_string()),
+            ("date" | "time", Some(format)) => {
+                // 无效的格式在展开时会 panic，视为普通文本
+                if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+                    return None;
+                }
+                Placeholder::Now(format.to_string())
+            }
+            ("clipboard", None) => Placeholder::Clipboard,
+            ("cursor", None) => Placeholder::Cursor,
+            ("field", Some(field)) => {
+                let (name, default) = field.split_once('=').unwrap_or((field, ""));
+                let name = name.trim();
+                if name.is_empty() {
+                    return None;
+                }
+                Placeholder::Field(Field {
+                    name: name.to_string(),
+                    default: default.to_string(),
+                })
+            }
+            _ => return None,
+        };
+        Some(placeholder)
+    }
+
+    /// 需要填写的字段，同名字段只出现一次
+    pub fn fields(&self) -> Vec<Field> {
+        let mut fields: Vec<Field> = Vec::new();
+        for segment in &self.segments {
+            if let Segment::Placeholder(Placeholder::Field(field)) = segment {
+                if !fields.iter().any(|existing| existing.name == field.name) {
+                    fields.push(field.clone());
+                }
+            }
+        }
+        fields
+    }
+
+    /// 展开所有占位符，只有第一个 `{cursor}` 记录光标位置
+    pub fn expand(&self, values: &Values<'_>) -> Expansion {
+        let mut text = String::new();
+        let mut cursor = None;
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(literal) => text.push_str(literal),
+                Segment::Placeholder(Placeholder::Now(format)) => {
+                    text.push_str(&values.now.format(format).to_string());
+                }
+                Segment::Placeholder(Placeholder::Clipboard) => text.push_str(values.clipboard),
+                Segment::Placeholder(Placeholder::Cursor) => {
+                    cursor.get_or_insert(text.len());
+                }
+                Segment::Placeholder(Placeholder::Field(field)) => {
+                    text.push_str(values.fields.get(&field.name).unwrap_or(&field.default))
+                }
+            }
+        }
+        Expansion { text, cursor }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn expand(body: &str, fields: &[(&str, &str)]) -> Expansion {
+        let fields = fields
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        Template::parse(body).expand(&Values {
+            now: Local.with_ymd_and_hms(2024, 3, 9, 14, 5, 0).unwrap(),
+            clipboard: "copied",
+            fields: &fields,
+        })
+    }
+
+    #[test]
+    fn test_expand_placeholders() {
+        assert_eq!(
+            expand("On {date} at {time}: {clipboard}", &[]).text,
+            "On 2024-03-09 at 14:05: copied"
+        );
+        assert_eq!(expand("{date:%d.%m.%Y}", &[]).text, "09.03.2024");
+        assert_eq!(
+            expand(
+                "Hi {field:Name}, {field:Greeting=thanks}! Bye {field:Name}",
+                &[("Name", "Ann")]
+            )
+            .text,
+            "Hi Ann, thanks! Bye Ann"
+        );
+
+        let expansion = expand("<b>{cursor}</b>{cursor}", &[]);
+        assert_eq!(expansion.text, "<b></b>");
+        assert_eq!(expansion.cursor, Some(3));
+        assert_eq!(expansion.chars_after_cursor(), 4);
+        assert_eq!(expand("plain", &[]).chars_after_cursor(), 0);
+    }
+
+    #[test]
+    fn test_literal_braces() {
+        assert_eq!(expand("{{date} {}", &[]).text, "{date} {}");
+        assert_eq!(
+            expand(r#"fn main() { println!("{}", x); }"#, &[]).text,
+            r#"fn main() { println!("{}", x); }"#
+        );
+        assert_eq!(expand(r#"{"a": {"b": 1}}"#, &[]).text, r#"{"a": {"b": 1}}"#);
+        assert_eq!(
+            expand("{unknown} {field:} {date:%Q}", &[]).text,
+            "{unknown} {field:} {date:%Q}"
+        );
+        assert_eq!(expand("{{{date}}", &[]).text, "{2024-03-09}");
+    }
+
+    #[test]
+    fn test_fields() {
+        let template = Template::parse("{field:To} {field:Subject=Hello} {field:To=ignored}");
+        assert_eq!(
+            template.fields(),
+            vec![
+                Field {
+                    name: "To".to_string(),
+                    default: String::new(),
+                },
+                Field {
+                    name: "Subject".to_string(),
+                    default: "Hello".to_string(),
+                },
+            ]
+        );
+        assert!(Template::parse("{date} {cursor}").fields().is_empty());
+    }
+}
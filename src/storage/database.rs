@@ -1,12 +1,47 @@
 use crate::clipboard::types::{ClipboardItem, SearchFilter};
 use crate::error::{ClipManagerError, Result};
-use chrono::Utc;
+use crate::snippets::Snippet;
+use crate::storage::events::{EventBus, ItemSummary, StorageEvent};
+use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, OptionalExtension};
 use std::path::Path;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+
+/// 默认最大条目数
+pub const DEFAULT_MAX_ITEMS: usize = 1000;
+
+/// 批量写入中单个条目的处理结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchOutcome {
+    /// 新插入的条目
+    Inserted(i64),
+    /// 内容已存在，已将原条目置顶
+    Bumped(i64),
+}
+
+/// 键集分页游标：指向已加载的最后一个条目
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: i64,
+}
+
+impl PageCursor {
+    pub fn after(item: &ClipboardItem) -> Option<Self> {
+        item.id.map(|id| Self {
+            created_at: item.created_at,
+            id,
+        })
+    }
+}
 
 pub struct Database {
     conn: Arc<Mutex<Connection>>,
+    events: EventBus,
+    /// 外部写入检查已处理到的 `data_version`
+    seen_version: AtomicI64,
 }
 
 impl Database {
@@ -14,13 +49,15 @@ impl Database {
         let conn = Connection::open(db_path)?;
         let db = Self {
             conn: Arc::new(Mutex::new(conn)),
+            events: EventBus::new(),
+            seen_version: AtomicI64::new(i64::MIN),
         };
         db.initialize()?;
         Ok(db)
     }
 
     fn initialize(&self) -> Result<()> {
-        let conn = self.conn.lock().unwrap();
+        let mut conn = self.conn.lock().unwrap();
 
         // 创建用户版本表来跟踪数据库版本
         conn.execute(
@@ -40,12 +77,12 @@ impl Database {
             .unwrap_or(0);
 
         // 执行迁移
-        self.migrate(&conn, current_version)?;
+        self.migrate(&mut conn, current_version)?;
 
         Ok(())
     }
 
-    fn migrate(&self, conn: &Connection, current_version: i64) -> Result<()> {
+    fn migrate(&self, conn: &mut Connection, current_version: i64) -> Result<()> {
         // 检查是否存在旧表结构
         let table_exists: bool = conn
             .query_row(
@@ -58,12 +95,14 @@ impl Database {
             )
             .unwrap_or(false);
 
+        // 每个版本的迁移在单独的事务中执行，失败时不会留下只完成一半的结构
         if current_version == 0 && table_exists {
             // 情况1: 存在旧表，需要迁移
+            let tx = conn.transaction()?;
             log::info!("Migrating existing database from old schema");
 
             // 检查是否有旧的 content 列
-            let has_old_content: bool = conn
+            let has_old_content: bool = tx
                 .prepare("SELECT content FROM clipboard_items LIMIT 1")
                 .is_ok();
 
@@ -71,34 +110,34 @@ impl Database {
                 log::info!("Found old content column, performing migration");
 
                 // 添加新列
-                conn.execute(
+                tx.execute(
                     "ALTER TABLE clipboard_items ADD COLUMN content_text TEXT",
                     [],
                 )
                 .ok();
-                conn.execute(
+                tx.execute(
                     "ALTER TABLE clipboard_items ADD COLUMN content_data BLOB",
                     [],
                 )
                 .ok();
-                conn.execute(
+                tx.execute(
                     "ALTER TABLE clipboard_items ADD COLUMN image_width INTEGER",
                     [],
                 )
                 .ok();
-                conn.execute(
+                tx.execute(
                     "ALTER TABLE clipboard_items ADD COLUMN image_height INTEGER",
                     [],
                 )
                 .ok();
-                conn.execute(
+                tx.execute(
                     "ALTER TABLE clipboard_items ADD COLUMN image_format TEXT",
                     [],
                 )
                 .ok();
 
                 // 迁移数据：将旧的 content 复制到 content_text
-                conn.execute(
+                tx.execute(
                     "UPDATE clipboard_items SET content_text = content WHERE content_text IS NULL",
                     [],
                 )?;
@@ -107,29 +146,31 @@ impl Database {
             }
 
             // 创建缺失的索引
-            conn.execute(
+            tx.execute(
                 "CREATE INDEX IF NOT EXISTS idx_created_at ON clipboard_items(created_at DESC)",
                 [],
             )?;
-            conn.execute(
+            tx.execute(
                 "CREATE INDEX IF NOT EXISTS idx_content_hash ON clipboard_items(content_hash)",
                 [],
             )?;
-            conn.execute(
+            tx.execute(
                 "CREATE INDEX IF NOT EXISTS idx_content_type ON clipboard_items(content_type)",
                 [],
             )?;
 
             // 更新版本
-            conn.execute(
+            tx.execute(
                 "INSERT OR REPLACE INTO schema_version (version) VALUES (1)",
                 [],
             )?;
+            tx.commit()?;
         } else if current_version == 0 && !table_exists {
             // 情况2: 全新安装，创建新表
+            let tx = conn.transaction()?;
             log::info!("Creating new database with current schema");
 
-            conn.execute(
+            tx.execute(
                 "CREATE TABLE clipboard_items (
                     id INTEGER PRIMARY KEY AUTOINCREMENT,
                     content_type TEXT NOT NULL,
@@ -149,35 +190,210 @@ impl Database {
             )?;
 
             // 创建索引
-            conn.execute(
+            tx.execute(
                 "CREATE INDEX IF NOT EXISTS idx_created_at ON clipboard_items(created_at DESC)",
                 [],
             )?;
-            conn.execute(
+            tx.execute(
                 "CREATE INDEX IF NOT EXISTS idx_content_hash ON clipboard_items(content_hash)",
                 [],
             )?;
-            conn.execute(
+            tx.execute(
                 "CREATE INDEX IF NOT EXISTS idx_content_type ON clipboard_items(content_type)",
                 [],
             )?;
 
             // 更新版本
-            conn.execute(
+            tx.execute(
                 "INSERT OR REPLACE INTO schema_version (version) VALUES (1)",
                 [],
             )?;
+            tx.commit()?;
+        }
+
+        if current_version < 2 {
+            let tx = conn.transaction()?;
+            // 版本2: 为键集分页添加复合索引
+            tx.execute(
+                "CREATE INDEX IF NOT EXISTS idx_created_at_id ON clipboard_items(created_at DESC, id DESC)",
+                [],
+            )?;
+            tx.execute(
+                "INSERT OR REPLACE INTO schema_version (version) VALUES (2)",
+                [],
+            )?;
+            tx.commit()?;
+        }
+
+        if current_version < 3 {
+            let tx = conn.transaction()?;
+            // 版本3: 缩略图持久化缓存，条目删除时自动清理
+            tx.execute(
+                "CREATE TABLE IF NOT EXISTS thumbnails (
+                    content_hash TEXT PRIMARY KEY,
+                    data BLOB NOT NULL
+                )",
+                [],
+            )?;
+            tx.execute(
+                "CREATE TRIGGER IF NOT EXISTS trg_delete_thumbnail
+                 AFTER DELETE ON clipboard_items
+                 BEGIN
+                     DELETE FROM thumbnails WHERE content_hash = OLD.content_hash;
+                 END",
+                [],
+            )?;
+            tx.execute(
+                "INSERT OR REPLACE INTO schema_version (version) VALUES (3)",
+                [],
+            )?;
+            tx.commit()?;
+        }
+
+        if current_version < 4 {
+            let tx = conn.transaction()?;
+            // 版本4: 敏感内容标记与自动过期
+            tx.execute("ALTER TABLE clipboard_items ADD COLUMN sensitive TEXT", [])?;
+            tx.execute(
+                "ALTER TABLE clipboard_items ADD COLUMN expires_at DATETIME",
+                [],
+            )?;
+            tx.execute(
+                "CREATE INDEX IF NOT EXISTS idx_expires_at ON clipboard_items(expires_at)
+                 WHERE expires_at IS NOT NULL",
+                [],
+            )?;
+            tx.execute(
+                "INSERT OR REPLACE INTO schema_version (version) VALUES (4)",
+                [],
+            )?;
+            tx.commit()?;
+        }
+
+        if current_version < 5 {
+            let tx = conn.transaction()?;
+            // 版本5: 来源程序
+            tx.execute("ALTER TABLE clipboard_items ADD COLUMN source_app TEXT", [])?;
+            tx.execute(
+                "ALTER TABLE clipboard_items ADD COLUMN source_pid INTEGER",
+                [],
+            )?;
+            tx.execute(
+                "INSERT OR REPLACE INTO schema_version (version) VALUES (5)",
+                [],
+            )?;
+            tx.commit()?;
+        }
+
+        if current_version < 6 {
+            let tx = conn.transaction()?;
+            // 版本6: 需要跨重启保存的运行状态（如隐私模式）
+            tx.execute(
+                "CREATE TABLE IF NOT EXISTS app_state (
+                    key TEXT PRIMARY KEY,
+                    value TEXT NOT NULL
+                )",
+                [],
+            )?;
+            tx.execute(
+                "INSERT OR REPLACE INTO schema_version (version) VALUES (6)",
+                [],
+            )?;
+            tx.commit()?;
+        }
+
+        if current_version < 7 {
+            let tx = conn.transaction()?;
+            // 版本7: 片段库，与捕获的条目分开保存
+            tx.execute(
+                "CREATE TABLE IF NOT EXISTS snippets (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    name TEXT NOT NULL,
+                    abbreviation TEXT NOT NULL DEFAULT '',
+                    folder TEXT NOT NULL DEFAULT '',
+                    body TEXT NOT NULL,
+                    updated_at DATETIME NOT NULL,
+                    UNIQUE (folder, name)
+                )",
+                [],
+            )?;
+            tx.execute(
+                "CREATE UNIQUE INDEX IF NOT EXISTS idx_snippet_abbreviation
+                 ON snippets(abbreviation) WHERE abbreviation != ''",
+                [],
+            )?;
+            tx.execute(
+                "INSERT OR REPLACE INTO schema_version (version) VALUES (7)",
+                [],
+            )?;
+            tx.commit()?;
         }
 
         // 未来版本的迁移可以在这里添加
-        // if current_version < 2 { ... }
+        // if current_version < 8 { ... }
 
         Ok(())
     }
 
-    pub fn insert_item(&self, item: &ClipboardItem) -> Result<i64> {
-        use crate::clipboard::types::ClipboardContent;
+    /// 订阅存储变更事件
+    pub fn subscribe(&self) -> mpsc::Receiver<StorageEvent> {
+        self.events.subscribe()
+    }
+
+    pub(crate) fn publish(&self, event: StorageEvent) {
+        self.events.publish(event);
+    }
+
+    /// SQLite 的 `data_version`：只在其他连接提交写入后变化
+    fn data_version(&self) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn.query_row("PRAGMA data_version", [], |row| row.get(0))?)
+    }
 
+    /// 定期检查其他进程的写入，发现后发布 [`StorageEvent::ExternalChange`]
+    ///
+    /// 线程只持有弱引用，数据库释放后自动退出。
+    pub fn watch_external_changes(self: &Arc<Self>, interval: Duration) {
+        self.acknowledge_external_changes();
+        let database = Arc::downgrade(self);
+        std::thread::spawn(move || loop {
+            std::thread::sleep(interval);
+            let Some(database) = database.upgrade() else {
+                break;
+            };
+            database.check_external_changes();
+        });
+    }
+
+    /// 数据库自上次检查或确认后被其他进程修改时发布 [`StorageEvent::ExternalChange`]
+    fn check_external_changes(&self) -> bool {
+        match self.data_version() {
+            Ok(version) => {
+                let last = self.seen_version.swap(version, Ordering::Relaxed);
+                let changed = last != i64::MIN && last != version;
+                if changed {
+                    log::debug!("Database changed by another process");
+                    self.publish(StorageEvent::ExternalChange);
+                }
+                changed
+            }
+            Err(e) => {
+                log::warn!("Failed to check for external changes: {}", e);
+                false
+            }
+        }
+    }
+
+    /// 其他进程的写入已通过事件单独应用时调用，避免轮询再触发一次整表刷新
+    pub fn acknowledge_external_changes(&self) {
+        match self.data_version() {
+            Ok(version) => self.seen_version.store(version, Ordering::Relaxed),
+            Err(e) => log::warn!("Failed to read data version: {}", e),
+        }
+    }
+
+    /// 写入单个条目，已存在相同内容时返回错误，写入后按 `max_items` 清理旧条目
+    pub fn insert_item(&self, item: &ClipboardItem, max_items: usize) -> Result<i64> {
         log::debug!("Attempting to insert item with hash: {}", item.content_hash);
 
         // 检查是否已存在相同内容
@@ -192,6 +408,86 @@ impl Database {
         }
 
         let conn = self.conn.lock().unwrap();
+        let id = Self::insert_row(&conn, item)?;
+        drop(conn);
+
+        self.publish(StorageEvent::ItemAdded(ItemSummary::new(id, item)));
+
+        // 保持最大条目数限制
+        self.cleanup_old_items(max_items)?;
+
+        Ok(id)
+    }
+
+    /// 在单个事务中批量写入条目
+    ///
+    /// 整个批次只获取一次连接锁：已存在的内容会被置顶而不是报错，
+    /// 写入完成后按 `max_items` 清理旧条目，提交成功后再发布事件。
+    pub fn insert_batch(
+        &self,
+        items: &[ClipboardItem],
+        max_items: usize,
+    ) -> Result<Vec<BatchOutcome>> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        let mut outcomes = Vec::with_capacity(items.len());
+        for item in items {
+            let existing: Option<i64> = tx
+                .query_row(
+                    "SELECT id FROM clipboard_items WHERE content_hash = ?",
+                    params![&item.content_hash],
+                    |row| row.get(0),
+                )
+                .optional()?;
+
+            match existing {
+                Some(id) => {
+                    // 重新检测的结果覆盖旧值：新的过期时间、敏感标签和遮盖后的文本
+                    tx.execute(
+                        "UPDATE clipboard_items
+                         SET created_at = ?, accessed_at = ?, expires_at = ?, sensitive = ?,
+                             content_text = COALESCE(?, content_text)
+                         WHERE id = ?",
+                        params![
+                            &item.created_at,
+                            &item.accessed_at,
+                            &item.expires_at,
+                            &item.sensitive,
+                            item.get_text_content(),
+                            id
+                        ],
+                    )?;
+                    outcomes.push(BatchOutcome::Bumped(id));
+                }
+                None => {
+                    let id = Self::insert_row(&tx, item)?;
+                    outcomes.push(BatchOutcome::Inserted(id));
+                }
+            }
+        }
+
+        let removed = Self::delete_overflow(&tx, max_items)?;
+        tx.commit()?;
+        drop(conn);
+
+        for (item, outcome) in items.iter().zip(&outcomes) {
+            match *outcome {
+                BatchOutcome::Inserted(id) => {
+                    self.publish(StorageEvent::ItemAdded(ItemSummary::new(id, item)))
+                }
+                BatchOutcome::Bumped(id) => self.publish(StorageEvent::ItemUpdated(id)),
+            }
+        }
+        for id in removed {
+            self.publish(StorageEvent::ItemDeleted(id));
+        }
+
+        Ok(outcomes)
+    }
+
+    fn insert_row(conn: &Connection, item: &ClipboardItem) -> Result<i64> {
+        use crate::clipboard::types::ClipboardContent;
 
         let (content_type_str, content_text, content_data, image_width, image_height, image_format) =
             match &item.content {
@@ -216,12 +512,13 @@ impl Database {
                 }
             };
 
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare_cached(
             "INSERT INTO clipboard_items
              (content_type, content_text, content_data, content_hash, content_size,
               created_at, accessed_at, access_count, is_favorite,
-              image_width, image_height, image_format)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+              image_width, image_height, image_format, sensitive, expires_at,
+              source_app, source_pid)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
         )?;
 
         let id = stmt.insert(params![
@@ -237,11 +534,14 @@ impl Database {
             image_width,
             image_height,
             image_format,
+            &item.sensitive,
+            &item.expires_at,
+            item.source
+                .as_ref()
+                .and_then(|source| source.name.as_deref()),
+            item.source.as_ref().and_then(|source| source.pid),
         ])?;
 
-        drop(stmt);
-        drop(conn);
-
         log::info!(
             "Successfully inserted item with ID: {} (type: {}, size: {} bytes)",
             id,
@@ -249,23 +549,69 @@ impl Database {
             item.content_size
         );
 
-        // 保持最大条目数限制
-        self.cleanup_old_items(1000)?; // 默认限制 1000 条
-
         Ok(id)
     }
 
+    /// 查询单个条目
+    pub fn get_item(&self, id: i64) -> Result<Option<ClipboardItem>> {
+        let conn = self.conn.lock().unwrap();
+        let item = conn
+            .query_row(
+                "SELECT * FROM clipboard_items WHERE id = ?",
+                params![id],
+                ClipboardItem::from_row,
+            )
+            .optional()?;
+        Ok(item)
+    }
+
     pub fn get_items(
         &self,
         filter: &SearchFilter,
         limit: usize,
         offset: usize,
     ) -> Result<Vec<ClipboardItem>> {
-        let conn = self.conn.lock().unwrap();
+        let mut query = "SELECT * FROM clipboard_items WHERE 1=1".to_string();
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+        Self::push_filter(filter, &mut query, &mut params);
+
+        // 添加排序和分页
+        query.push_str(" ORDER BY created_at DESC, id DESC LIMIT ? OFFSET ?");
+        params.push(Box::new(limit as i64));
+        params.push(Box::new(offset as i64));
+
+        self.query_items(&query, &params)
+    }
 
+    /// 键集分页：获取游标之后（更旧）的一页条目
+    pub fn get_items_page(
+        &self,
+        filter: &SearchFilter,
+        before: Option<&PageCursor>,
+        limit: usize,
+    ) -> Result<Vec<ClipboardItem>> {
         let mut query = "SELECT * FROM clipboard_items WHERE 1=1".to_string();
         let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+        Self::push_filter(filter, &mut query, &mut params);
+
+        if let Some(cursor) = before {
+            query.push_str(" AND (created_at < ? OR (created_at = ? AND id < ?))");
+            params.push(Box::new(cursor.created_at));
+            params.push(Box::new(cursor.created_at));
+            params.push(Box::new(cursor.id));
+        }
 
+        query.push_str(" ORDER BY created_at DESC, id DESC LIMIT ?");
+        params.push(Box::new(limit as i64));
+
+        self.query_items(&query, &params)
+    }
+
+    fn push_filter(
+        filter: &SearchFilter,
+        query: &mut String,
+        params: &mut Vec<Box<dyn rusqlite::ToSql>>,
+    ) {
         // 添加搜索条件
         if !filter.query.is_empty() {
             query.push_str(" AND content_text LIKE ?");
@@ -287,12 +633,25 @@ impl Database {
             query.push_str(" AND is_favorite = 1");
         }
 
-        // 添加排序和分页
-        query.push_str(" ORDER BY created_at DESC LIMIT ? OFFSET ?");
-        params.push(Box::new(limit as i64));
-        params.push(Box::new(offset as i64));
+        // 添加时间范围过滤
+        if let Some((start, end)) = &filter.date_range {
+            query.push_str(" AND created_at BETWEEN ? AND ?");
+            params.push(Box::new(*start));
+            params.push(Box::new(*end));
+        }
+
+        // 已过期但尚未清理的条目不再显示
+        query.push_str(" AND (expires_at IS NULL OR expires_at > ?)");
+        params.push(Box::new(Utc::now()));
+    }
 
-        let mut stmt = conn.prepare(&query)?;
+    fn query_items(
+        &self,
+        query: &str,
+        params: &[Box<dyn rusqlite::ToSql>],
+    ) -> Result<Vec<ClipboardItem>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(query)?;
         let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
         let rows = stmt.query_map(&param_refs[..], ClipboardItem::from_row)?;
 
@@ -305,34 +664,106 @@ impl Database {
 
     pub fn delete_item(&self, id: i64) -> Result<()> {
         let conn = self.conn.lock().unwrap();
-        conn.execute("DELETE FROM clipboard_items WHERE id = ?", params![id])?;
+        let changed = conn.execute("DELETE FROM clipboard_items WHERE id = ?", params![id])?;
+        drop(conn);
+        if changed > 0 {
+            self.publish(StorageEvent::ItemDeleted(id));
+        }
         Ok(())
     }
 
     pub fn clear_all(&self) -> Result<()> {
         let conn = self.conn.lock().unwrap();
-        conn.execute("DELETE FROM clipboard_items", [])?;
+        let ids = conn
+            .prepare("DELETE FROM clipboard_items RETURNING id")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<i64>>>()?;
+        drop(conn);
+        self.publish(StorageEvent::Cleared(ids));
         Ok(())
     }
 
     pub fn update_access(&self, id: i64) -> Result<()> {
         let conn = self.conn.lock().unwrap();
-        conn.execute(
+        let changed = conn.execute(
             "UPDATE clipboard_items SET accessed_at = ?, access_count = access_count + 1 WHERE id = ?",
             params![Utc::now(), id],
         )?;
+        drop(conn);
+        if changed > 0 {
+            self.publish(StorageEvent::ItemUpdated(id));
+        }
         Ok(())
     }
 
+    /// 收藏的条目不再自动过期
     pub fn update_favorite(&self, id: i64, is_favorite: bool) -> Result<()> {
         let conn = self.conn.lock().unwrap();
-        conn.execute(
-            "UPDATE clipboard_items SET is_favorite = ? WHERE id = ?",
+        let changed = conn.execute(
+            "UPDATE clipboard_items
+             SET is_favorite = ?1, expires_at = CASE WHEN ?1 THEN NULL ELSE expires_at END
+             WHERE id = ?2",
             params![is_favorite, id],
         )?;
+        drop(conn);
+        if changed > 0 {
+            self.publish(StorageEvent::ItemUpdated(id));
+        }
         Ok(())
     }
 
+    /// 设置或取消条目的过期时间
+    pub fn set_expiry(&self, id: i64, expires_at: Option<DateTime<Utc>>) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let changed = conn.execute(
+            "UPDATE clipboard_items SET expires_at = ? WHERE id = ?",
+            params![expires_at, id],
+        )?;
+        drop(conn);
+        if changed > 0 {
+            self.publish(StorageEvent::ItemUpdated(id));
+        }
+        Ok(())
+    }
+
+    /// 最早的过期时间，供后台清理安排下一次检查
+    pub fn next_expiry(&self) -> Result<Option<DateTime<Utc>>> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn.query_row(
+            "SELECT MIN(expires_at) FROM clipboard_items WHERE expires_at IS NOT NULL",
+            [],
+            |row| row.get(0),
+        )?)
+    }
+
+    /// 删除已过期的条目，返回被删除的 ID
+    ///
+    /// 除了逐条发布删除事件，还会发布 [`StorageEvent::ContentExpired`]，
+    /// 以便清除仍留在系统剪切板上的内容。
+    pub fn delete_expired(&self) -> Result<Vec<i64>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(
+            "DELETE FROM clipboard_items WHERE expires_at IS NOT NULL AND expires_at <= ?
+             RETURNING id, content_hash",
+        )?;
+        let expired = stmt
+            .query_map(params![Utc::now()], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        drop(stmt);
+        drop(conn);
+        if expired.is_empty() {
+            return Ok(Vec::new());
+        }
+        let (ids, hashes): (Vec<i64>, Vec<String>) = expired.into_iter().unzip();
+        for id in &ids {
+            self.publish(StorageEvent::ItemDeleted(*id));
+        }
+        self.publish(StorageEvent::ContentExpired(hashes));
+        Ok(ids)
+    }
+
     pub fn cleanup_with_limit(&self, max_items: usize) -> Result<()> {
         self.cleanup_old_items(max_items)
     }
@@ -351,26 +782,284 @@ impl Database {
 
     fn cleanup_old_items(&self, max_items: usize) -> Result<()> {
         let conn = self.conn.lock().unwrap();
-        conn.execute(
-            "DELETE FROM clipboard_items WHERE id NOT IN (
+        let removed = Self::delete_overflow(&conn, max_items)?;
+        drop(conn);
+        for id in removed {
+            self.publish(StorageEvent::ItemDeleted(id));
+        }
+        Ok(())
+    }
+
+    /// 删除超出数量上限的旧条目，返回被删除的 ID
+    fn delete_overflow(conn: &Connection, max_items: usize) -> Result<Vec<i64>> {
+        let mut stmt = conn.prepare_cached(
+            "SELECT id FROM clipboard_items WHERE id NOT IN (
                 SELECT id FROM clipboard_items ORDER BY created_at DESC LIMIT ?
             )",
-            params![max_items as i64],
         )?;
+        let ids = stmt
+            .query_map(params![max_items as i64], |row| row.get::<_, i64>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        for id in &ids {
+            conn.execute("DELETE FROM clipboard_items WHERE id = ?", params![id])?;
+        }
+        Ok(ids)
+    }
+
+    /// 读取已缓存的缩略图（PNG 编码）
+    pub fn get_thumbnail(&self, content_hash: &str) -> Result<Option<Vec<u8>>> {
+        let conn = self.conn.lock().unwrap();
+        let data = conn
+            .query_row(
+                "SELECT data FROM thumbnails WHERE content_hash = ?",
+                params![content_hash],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(data)
+    }
+
+    pub fn put_thumbnail(&self, content_hash: &str, data: &[u8]) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO thumbnails (content_hash, data) VALUES (?, ?)",
+            params![content_hash, data],
+        )?;
+        Ok(())
+    }
+
+    /// 读取保存的运行状态
+    pub fn get_state(&self, key: &str) -> Result<Option<String>> {
+        let conn = self.conn.lock().unwrap();
+        let value = conn
+            .query_row(
+                "SELECT value FROM app_state WHERE key = ?",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(value)
+    }
+
+    /// 保存运行状态，`None` 删除
+    pub fn set_state(&self, key: &str, value: Option<&str>) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        match value {
+            Some(value) => conn.execute(
+                "INSERT OR REPLACE INTO app_state (key, value) VALUES (?, ?)",
+                params![key, value],
+            )?,
+            None => conn.execute("DELETE FROM app_state WHERE key = ?", params![key])?,
+        };
+        Ok(())
+    }
+
+    /// 保存新片段，返回其 ID
+    pub fn insert_snippet(&self, snippet: &Snippet) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        let id = Self::insert_snippet_row(&conn, snippet)?;
+        drop(conn);
+        self.publish(StorageEvent::SnippetsChanged);
+        Ok(id)
+    }
+
+    fn insert_snippet_row(conn: &Connection, snippet: &Snippet) -> Result<i64> {
+        conn.execute(
+            "INSERT INTO snippets (name, abbreviation, folder, body, updated_at)
+             VALUES (?, ?, ?, ?, ?)",
+            params![
+                &snippet.name,
+                &snippet.abbreviation,
+                &snippet.folder,
+                &snippet.body,
+                Utc::now()
+            ],
+        )
+        .map_err(|e| Self::snippet_conflict(e, snippet))?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn update_snippet(&self, snippet: &Snippet) -> Result<()> {
+        let id = snippet.id.ok_or_else(|| ClipManagerError::Config {
+            message: format!("Snippet '{}' has not been saved", snippet.path()),
+        })?;
+        let conn = self.conn.lock().unwrap();
+        Self::update_snippet_row(&conn, id, snippet)?;
+        drop(conn);
+        self.publish(StorageEvent::SnippetsChanged);
+        Ok(())
+    }
+
+    fn update_snippet_row(conn: &Connection, id: i64, snippet: &Snippet) -> Result<()> {
+        conn.execute(
+            "UPDATE snippets SET name = ?, abbreviation = ?, folder = ?, body = ?, updated_at = ?
+             WHERE id = ?",
+            params![
+                &snippet.name,
+                &snippet.abbreviation,
+                &snippet.folder,
+                &snippet.body,
+                Utc::now(),
+                id
+            ],
+        )
+        .map_err(|e| Self::snippet_conflict(e, snippet))?;
         Ok(())
     }
 
+    /// 名称或缩写重复时给出可读的错误
+    fn snippet_conflict(error: rusqlite::Error, snippet: &Snippet) -> ClipManagerError {
+        match error.sqlite_error_code() {
+            Some(rusqlite::ErrorCode::ConstraintViolation) => ClipManagerError::Config {
+                message: format!(
+                    "Snippet '{}' has the same name or abbreviation as an existing one",
+                    snippet.path()
+                ),
+            },
+            _ => error.into(),
+        }
+    }
+
+    pub fn delete_snippet(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM snippets WHERE id = ?", params![id])?;
+        drop(conn);
+        self.publish(StorageEvent::SnippetsChanged);
+        Ok(())
+    }
+
+    pub fn get_snippet(&self, id: i64) -> Result<Option<Snippet>> {
+        let conn = self.conn.lock().unwrap();
+        let snippet = conn
+            .query_row(
+                "SELECT * FROM snippets WHERE id = ?",
+                params![id],
+                Snippet::from_row,
+            )
+            .optional()?;
+        Ok(snippet)
+    }
+
+    /// 按缩写、名称或 `文件夹/名称` 查找片段，缩写优先
+    pub fn find_snippet(&self, key: &str) -> Result<Option<Snippet>> {
+        let conn = self.conn.lock().unwrap();
+        let snippet = conn
+            .query_row(
+                "SELECT * FROM snippets
+                 WHERE abbreviation = ?1 OR name = ?1 OR folder || '/' || name = ?1
+                 ORDER BY abbreviation = ?1 DESC, folder || '/' || name = ?1 DESC, folder = '' DESC
+                 LIMIT 1",
+                params![key],
+                Snippet::from_row,
+            )
+            .optional()?;
+        Ok(snippet)
+    }
+
+    /// 搜索片段的名称、缩写、文件夹和正文；缩写完全匹配的排在最前
+    pub fn get_snippets(&self, query: &str, limit: usize) -> Result<Vec<Snippet>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(
+            "SELECT * FROM snippets
+             WHERE ?1 = '' OR name LIKE ?2 OR abbreviation LIKE ?2 OR folder LIKE ?2 OR body LIKE ?2
+             ORDER BY ?1 != '' AND abbreviation = ?1 COLLATE NOCASE DESC, folder, name
+             LIMIT ?3",
+        )?;
+        let snippets = stmt
+            .query_map(
+                params![query, format!("%{}%", query), limit as i64],
+                Snippet::from_row,
+            )?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(snippets)
+    }
+
+    /// 在单个事务中导入片段，同一文件夹中同名的片段会被覆盖
+    ///
+    /// 返回新增和覆盖的数量；任何一个片段冲突时整个导入回滚。
+    pub fn import_snippets(&self, snippets: &[Snippet]) -> Result<(usize, usize)> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let (mut inserted, mut updated) = (0, 0);
+        for snippet in snippets {
+            let existing: Option<i64> = tx
+                .query_row(
+                    "SELECT id FROM snippets WHERE folder = ? AND name = ?",
+                    params![&snippet.folder, &snippet.name],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            match existing {
+                Some(id) => {
+                    Self::update_snippet_row(&tx, id, snippet)?;
+                    updated += 1;
+                }
+                None => {
+                    Self::insert_snippet_row(&tx, snippet)?;
+                    inserted += 1;
+                }
+            }
+        }
+        tx.commit()?;
+        drop(conn);
+        self.publish(StorageEvent::SnippetsChanged);
+        Ok((inserted, updated))
+    }
+
     pub fn get_item_count(&self) -> Result<usize> {
         let conn = self.conn.lock().unwrap();
         let count: i64 =
             conn.query_row("SELECT COUNT(*) FROM clipboard_items", [], |row| row.get(0))?;
         Ok(count as usize)
     }
+
+    /// 汇总历史记录的条目数、大小和时间范围
+    pub fn get_stats(&self) -> Result<HistoryStats> {
+        let conn = self.conn.lock().unwrap();
+        let stats = conn.query_row(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(content_type = 'text'), 0),
+                    COALESCE(SUM(content_type = 'image'), 0),
+                    COALESCE(SUM(is_favorite), 0),
+                    COALESCE(SUM(content_size), 0),
+                    MIN(created_at),
+                    MAX(created_at)
+             FROM clipboard_items",
+            [],
+            |row| {
+                Ok(HistoryStats {
+                    total_items: row.get::<_, i64>(0)? as usize,
+                    text_items: row.get::<_, i64>(1)? as usize,
+                    image_items: row.get::<_, i64>(2)? as usize,
+                    favorite_items: row.get::<_, i64>(3)? as usize,
+                    total_size: row.get::<_, i64>(4)? as u64,
+                    oldest: row.get(5)?,
+                    newest: row.get(6)?,
+                })
+            },
+        )?;
+        Ok(stats)
+    }
+}
+
+/// 历史记录统计
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct HistoryStats {
+    pub total_items: usize,
+    pub text_items: usize,
+    pub image_items: usize,
+    pub favorite_items: usize,
+    /// 所有条目内容的总字节数
+    pub total_size: u64,
+    pub oldest: Option<DateTime<Utc>>,
+    pub newest: Option<DateTime<Utc>>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clipboard::types::{ImageData, ImageFormat};
 
     fn create_test_db() -> Database {
         // 使用内存数据库进行测试
@@ -383,7 +1072,7 @@ mod tests {
         let item = ClipboardItem::new("测试内容".to_string());
 
         // 插入条目
-        let id = db.insert_item(&item).unwrap();
+        let id = db.insert_item(&item, DEFAULT_MAX_ITEMS).unwrap();
         assert!(id > 0);
 
         // 获取条目
@@ -402,8 +1091,13 @@ mod tests {
         let db = create_test_db();
 
         // Test text item
-        let text_item = ClipboardItem::new_text("New schema test".to_string());
-        let id = db.insert_item(&text_item).unwrap();
+        let mut text_item = ClipboardItem::new_text("New schema test".to_string());
+        text_item.source = Some(crate::clipboard::types::SourceApp {
+            name: Some("kate".to_string()),
+            pid: Some(1234),
+            title: Some("notes.txt".to_string()),
+        });
+        let id = db.insert_item(&text_item, DEFAULT_MAX_ITEMS).unwrap();
         assert!(id > 0);
         println!("Inserted text item with ID: {}", id);
 
@@ -414,6 +1108,11 @@ mod tests {
 
         let retrieved_item = &items[0];
         assert_eq!(retrieved_item.id, Some(id));
+        // 窗口标题只用于捕获规则，不会保存
+        let source = retrieved_item.source.as_ref().unwrap();
+        assert_eq!(source.name.as_deref(), Some("kate"));
+        assert_eq!(source.pid, Some(1234));
+        assert!(source.title.is_none());
         if let Some(text) = retrieved_item.get_text_content() {
             assert_eq!(text, "New schema test");
         } else {
@@ -429,14 +1128,16 @@ mod tests {
 
         // Insert test item
         let item = ClipboardItem::new_text("Favorite test".to_string());
-        let id = db.insert_item(&item).unwrap();
+        let id = db.insert_item(&item, DEFAULT_MAX_ITEMS).unwrap();
 
         // Update as favorite
         db.update_favorite(id, true).unwrap();
 
         // Search for favorites
-        let mut filter = SearchFilter::default();
-        filter.favorites_only = true;
+        let filter = SearchFilter {
+            favorites_only: true,
+            ..Default::default()
+        };
         let favorites = db.get_items(&filter, 10, 0).unwrap();
 
         assert_eq!(favorites.len(), 1);
@@ -452,10 +1153,10 @@ mod tests {
         let item2 = ClipboardItem::new("重复内容".to_string());
 
         // 第一次插入应该成功
-        assert!(db.insert_item(&item1).is_ok());
+        assert!(db.insert_item(&item1, DEFAULT_MAX_ITEMS).is_ok());
 
         // 第二次插入相同内容应该失败
-        assert!(db.insert_item(&item2).is_err());
+        assert!(db.insert_item(&item2, DEFAULT_MAX_ITEMS).is_err());
     }
 
     #[test]
@@ -470,12 +1171,14 @@ mod tests {
         ];
 
         for item in items {
-            db.insert_item(&item).unwrap();
+            db.insert_item(&item, DEFAULT_MAX_ITEMS).unwrap();
         }
 
         // 测试搜索
-        let mut filter = SearchFilter::default();
-        filter.query = "Rust".to_string();
+        let filter = SearchFilter {
+            query: "Rust".to_string(),
+            ..Default::default()
+        };
 
         let results = db.get_items(&filter, 10, 0).unwrap();
         assert_eq!(results.len(), 1);
@@ -493,7 +1196,7 @@ mod tests {
         // 插入超过限制的条目
         for i in 0..105 {
             let item = ClipboardItem::new(format!("内容 {}", i));
-            db.insert_item(&item).unwrap();
+            db.insert_item(&item, DEFAULT_MAX_ITEMS).unwrap();
         }
 
         // 手动执行清理，限制为100条
@@ -503,4 +1206,411 @@ mod tests {
         let count = db.get_item_count().unwrap();
         assert_eq!(count, 100);
     }
+
+    #[test]
+    fn test_insert_batch_bumps_duplicates() {
+        let db = create_test_db();
+        let events = db.subscribe();
+
+        let first = ClipboardItem::new_text("batch a".to_string());
+        let existing_id = db.insert_item(&first, DEFAULT_MAX_ITEMS).unwrap();
+        let _ = events.try_recv();
+
+        let batch = vec![
+            ClipboardItem::new_text("batch b".to_string()),
+            ClipboardItem::new_text("batch a".to_string()),
+        ];
+        let outcomes = db.insert_batch(&batch, DEFAULT_MAX_ITEMS).unwrap();
+
+        assert!(matches!(outcomes[0], BatchOutcome::Inserted(_)));
+        assert_eq!(outcomes[1], BatchOutcome::Bumped(existing_id));
+        assert_eq!(db.get_item_count().unwrap(), 2);
+
+        // 被置顶的条目排在最前面
+        let items = db.get_items(&SearchFilter::default(), 10, 0).unwrap();
+        assert_eq!(items[0].id, Some(existing_id));
+
+        let Ok(StorageEvent::ItemAdded(summary)) = events.try_recv() else {
+            panic!("Expected ItemAdded");
+        };
+        assert_eq!(summary, ItemSummary::new(summary.id, &batch[0]));
+        assert!(matches!(outcomes[0], BatchOutcome::Inserted(id) if id == summary.id));
+        assert!(matches!(
+            events.try_recv(),
+            Ok(StorageEvent::ItemUpdated(id)) if id == existing_id
+        ));
+    }
+
+    #[test]
+    fn test_insert_batch_bump_updates_screening() {
+        let db = create_test_db();
+        let id = db
+            .insert_item(
+                &ClipboardItem::new_text("token 4f9c2e".to_string()),
+                DEFAULT_MAX_ITEMS,
+            )
+            .unwrap();
+
+        // 再次复制时命中了带过期时间的规则
+        let expires_at = Utc::now() + chrono::Duration::minutes(5);
+        let mut again = ClipboardItem::new_text("token 4f9c2e".to_string());
+        again.sensitive = Some("token".to_string());
+        again.expires_at = Some(expires_at);
+        let outcomes = db.insert_batch(&[again], DEFAULT_MAX_ITEMS).unwrap();
+        assert_eq!(outcomes, vec![BatchOutcome::Bumped(id)]);
+
+        let item = db.get_item(id).unwrap().unwrap();
+        assert_eq!(item.sensitive.as_deref(), Some("token"));
+        assert_eq!(
+            item.expires_at.map(|t| t.timestamp()),
+            Some(expires_at.timestamp())
+        );
+        assert_eq!(item.get_text_content(), Some("token 4f9c2e"));
+        assert_eq!(
+            db.next_expiry().unwrap().map(|t| t.timestamp()),
+            Some(expires_at.timestamp())
+        );
+    }
+
+    #[test]
+    fn test_insert_item_applies_limit() {
+        let db = create_test_db();
+        for i in 0..5 {
+            db.insert_item(&ClipboardItem::new_text(format!("single {}", i)), 3)
+                .unwrap();
+        }
+        assert_eq!(db.get_item_count().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_insert_batch_applies_limit() {
+        let db = create_test_db();
+        let events = db.subscribe();
+
+        let batch: Vec<_> = (0..5)
+            .map(|i| ClipboardItem::new_text(format!("limit {}", i)))
+            .collect();
+        db.insert_batch(&batch, 3).unwrap();
+
+        assert_eq!(db.get_item_count().unwrap(), 3);
+        let deleted = events
+            .try_iter()
+            .filter(|event| matches!(event, StorageEvent::ItemDeleted(_)))
+            .count();
+        assert_eq!(deleted, 2);
+    }
+
+    #[test]
+    fn test_mutations_publish_events() {
+        let db = create_test_db();
+        let id = db
+            .insert_item(
+                &ClipboardItem::new_text("events".to_string()),
+                DEFAULT_MAX_ITEMS,
+            )
+            .unwrap();
+        let events = db.subscribe();
+
+        db.update_favorite(id, true).unwrap();
+        db.delete_item(id).unwrap();
+        let remaining = db
+            .insert_item(
+                &ClipboardItem::new_text("remaining".to_string()),
+                DEFAULT_MAX_ITEMS,
+            )
+            .unwrap();
+        db.clear_all().unwrap();
+
+        assert!(matches!(events.try_recv(), Ok(StorageEvent::ItemUpdated(i)) if i == id));
+        assert!(matches!(events.try_recv(), Ok(StorageEvent::ItemDeleted(i)) if i == id));
+        assert!(matches!(events.try_recv(), Ok(StorageEvent::ItemAdded(_))));
+        assert!(matches!(events.try_recv(), Ok(StorageEvent::Cleared(ids)) if ids == [remaining]));
+    }
+
+    #[test]
+    fn test_stats() {
+        let db = create_test_db();
+        assert_eq!(db.get_stats().unwrap(), HistoryStats::default());
+
+        let id = db
+            .insert_item(
+                &ClipboardItem::new_text("abc".to_string()),
+                DEFAULT_MAX_ITEMS,
+            )
+            .unwrap();
+        db.insert_item(
+            &ClipboardItem::new_image(ImageData::new(vec![0; 16], ImageFormat::Png, 2, 2)),
+            DEFAULT_MAX_ITEMS,
+        )
+        .unwrap();
+        db.update_favorite(id, true).unwrap();
+
+        let stats = db.get_stats().unwrap();
+        assert_eq!(stats.total_items, 2);
+        assert_eq!(stats.text_items, 1);
+        assert_eq!(stats.image_items, 1);
+        assert_eq!(stats.favorite_items, 1);
+        assert_eq!(stats.total_size, 19);
+        assert!(stats.oldest <= stats.newest && stats.newest.is_some());
+    }
+
+    #[test]
+    fn test_external_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("shared.db");
+        let db = Database::new(&path).unwrap();
+        let other = Database::new(&path).unwrap();
+        let events = db.subscribe();
+        db.acknowledge_external_changes();
+
+        // 自己的写入不算外部变更
+        db.insert_item(
+            &ClipboardItem::new_text("local".to_string()),
+            DEFAULT_MAX_ITEMS,
+        )
+        .unwrap();
+        assert!(matches!(events.try_recv(), Ok(StorageEvent::ItemAdded(_))));
+        assert!(!db.check_external_changes());
+
+        // 已通过事件应用的写入不再触发刷新
+        other
+            .insert_item(
+                &ClipboardItem::new_text("announced".to_string()),
+                DEFAULT_MAX_ITEMS,
+            )
+            .unwrap();
+        db.acknowledge_external_changes();
+        assert!(!db.check_external_changes());
+        assert!(events.try_recv().is_err());
+
+        other
+            .insert_item(
+                &ClipboardItem::new_text("from daemon".to_string()),
+                DEFAULT_MAX_ITEMS,
+            )
+            .unwrap();
+        assert!(db.check_external_changes());
+        assert!(matches!(
+            events.try_recv(),
+            Ok(StorageEvent::ExternalChange)
+        ));
+        assert!(!db.check_external_changes());
+    }
+
+    #[test]
+    fn test_watch_external_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("shared.db");
+        let db = Arc::new(Database::new(&path).unwrap());
+        let other = Database::new(&path).unwrap();
+        let events = db.subscribe();
+        db.watch_external_changes(Duration::from_millis(20));
+
+        other
+            .insert_item(
+                &ClipboardItem::new_text("from daemon".to_string()),
+                DEFAULT_MAX_ITEMS,
+            )
+            .unwrap();
+        assert!(matches!(
+            events.recv_timeout(Duration::from_secs(2)),
+            Ok(StorageEvent::ExternalChange)
+        ));
+    }
+
+    #[test]
+    fn test_thumbnail_removed_with_item() {
+        let db = create_test_db();
+        let item = ClipboardItem::new_text("thumbnail owner".to_string());
+        let id = db.insert_item(&item, DEFAULT_MAX_ITEMS).unwrap();
+
+        db.put_thumbnail(&item.content_hash, &[1, 2, 3]).unwrap();
+        assert_eq!(
+            db.get_thumbnail(&item.content_hash).unwrap(),
+            Some(vec![1, 2, 3])
+        );
+
+        db.delete_item(id).unwrap();
+        assert_eq!(db.get_thumbnail(&item.content_hash).unwrap(), None);
+    }
+
+    #[test]
+    fn test_keyset_pagination() {
+        let db = create_test_db();
+        for i in 0..25 {
+            db.insert_item(
+                &ClipboardItem::new_text(format!("page {}", i)),
+                DEFAULT_MAX_ITEMS,
+            )
+            .unwrap();
+        }
+
+        let filter = SearchFilter::default();
+        let mut cursor = None;
+        let mut seen = Vec::new();
+        loop {
+            let page = db.get_items_page(&filter, cursor.as_ref(), 10).unwrap();
+            if page.is_empty() {
+                break;
+            }
+            cursor = page.last().and_then(PageCursor::after);
+            seen.extend(page.into_iter().map(|item| item.id.unwrap()));
+        }
+
+        assert_eq!(seen.len(), 25);
+        // 按时间倒序且没有重复
+        let mut sorted = seen.clone();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(seen, sorted);
+    }
+
+    #[test]
+    fn test_expired_items_hidden_and_deleted() {
+        let db = Database::new(":memory:").unwrap();
+        let events = db.subscribe();
+
+        let mut expired = ClipboardItem::new_text("old password".to_string());
+        expired.sensitive = Some("password".to_string());
+        expired.expires_at = Some(Utc::now() - chrono::Duration::minutes(1));
+        let expired_id = db.insert_item(&expired, DEFAULT_MAX_ITEMS).unwrap();
+
+        let mut pending = ClipboardItem::new_text("fresh password".to_string());
+        pending.expires_at = Some(Utc::now() + chrono::Duration::minutes(5));
+        let pending_id = db.insert_item(&pending, DEFAULT_MAX_ITEMS).unwrap();
+        db.insert_item(
+            &ClipboardItem::new_text("plain".to_string()),
+            DEFAULT_MAX_ITEMS,
+        )
+        .unwrap();
+
+        let visible = db.get_items(&SearchFilter::default(), 10, 0).unwrap();
+        assert_eq!(visible.len(), 2);
+        assert!(visible.iter().all(|item| item.id != Some(expired_id)));
+
+        let stored = db.get_item(pending_id).unwrap().unwrap();
+        assert!(stored.expires_at.is_some());
+        assert!(!stored.is_expired());
+
+        assert_eq!(db.next_expiry().unwrap(), expired.expires_at);
+        assert_eq!(db.delete_expired().unwrap(), vec![expired_id]);
+        assert!(db.get_item(expired_id).unwrap().is_none());
+        let events: Vec<_> = events.try_iter().collect();
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, StorageEvent::ItemDeleted(id) if *id == expired_id)));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            StorageEvent::ContentExpired(hashes) if *hashes == vec![expired.content_hash.clone()]
+        )));
+        assert_eq!(db.next_expiry().unwrap(), pending.expires_at);
+
+        // 手动设置过期时间，收藏后取消过期
+        db.set_expiry(pending_id, None).unwrap();
+        assert_eq!(db.next_expiry().unwrap(), None);
+        let soon = Utc::now() + chrono::Duration::seconds(30);
+        db.set_expiry(pending_id, Some(soon)).unwrap();
+        assert_eq!(
+            db.get_item(pending_id).unwrap().unwrap().expires_at,
+            Some(soon)
+        );
+        db.update_favorite(pending_id, true).unwrap();
+        assert_eq!(db.next_expiry().unwrap(), None);
+    }
+
+    #[test]
+    fn test_failed_migration_rolls_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("partial.db");
+        drop(Database::new(&path).unwrap());
+
+        // 模拟版本 5 的第二条 ALTER 失败：source_pid 已存在而 source_app 不存在
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(
+            "ALTER TABLE clipboard_items DROP COLUMN source_app;
+             DELETE FROM schema_version WHERE version > 4;",
+        )
+        .unwrap();
+        drop(conn);
+
+        assert!(Database::new(&path).is_err());
+
+        let conn = Connection::open(&path).unwrap();
+        let version: i64 = conn
+            .query_row("SELECT MAX(version) FROM schema_version", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(version, 4);
+        assert!(conn
+            .prepare("SELECT source_app FROM clipboard_items")
+            .is_err());
+    }
+
+    #[test]
+    fn test_snippets() {
+        let db = create_test_db();
+        let events = db.subscribe();
+        let mut thanks = Snippet::new("thanks", "Thanks {field:Name}!");
+        thanks.abbreviation = "ty".to_string();
+        thanks.folder = "mail".to_string();
+        let id = db.insert_snippet(&thanks).unwrap();
+        db.insert_snippet(&Snippet::new("today", "{date}")).unwrap();
+        assert!(matches!(
+            events.try_recv(),
+            Ok(StorageEvent::SnippetsChanged)
+        ));
+
+        // 片段不属于历史记录
+        assert_eq!(db.get_item_count().unwrap(), 0);
+
+        assert_eq!(db.find_snippet("ty").unwrap().unwrap().id, Some(id));
+        assert_eq!(db.find_snippet("thanks").unwrap().unwrap().id, Some(id));
+        assert_eq!(
+            db.find_snippet("mail/thanks").unwrap().unwrap().id,
+            Some(id)
+        );
+        assert!(db.find_snippet("mail").unwrap().is_none());
+
+        assert_eq!(db.get_snippets("", 10).unwrap().len(), 2);
+        let found = db.get_snippets("name", 10).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path(), "mail/thanks");
+        // 缩写完全匹配的排在最前
+        let mut tidy = Snippet::new("a-tidy", "ty");
+        tidy.abbreviation = "tidy".to_string();
+        db.insert_snippet(&tidy).unwrap();
+        assert_eq!(db.get_snippets("TY", 10).unwrap()[0].name, "thanks");
+
+        // 同一文件夹中的名称和缩写不能重复
+        assert!(db.insert_snippet(&thanks).is_err());
+        let mut other = Snippet::new("other", "x");
+        other.abbreviation = "ty".to_string();
+        assert!(db.insert_snippet(&other).is_err());
+
+        let mut edited = db.get_snippet(id).unwrap().unwrap();
+        edited.body = "Cheers".to_string();
+        db.update_snippet(&edited).unwrap();
+        assert_eq!(db.get_snippet(id).unwrap().unwrap().body, "Cheers");
+        db.delete_snippet(id).unwrap();
+        assert!(db.get_snippet(id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_import_snippets() {
+        let db = create_test_db();
+        db.insert_snippet(&Snippet::new("today", "old")).unwrap();
+
+        let imported = [Snippet::new("today", "{date}"), Snippet::new("sig", "Ann")];
+        assert_eq!(db.import_snippets(&imported).unwrap(), (1, 1));
+        assert_eq!(db.find_snippet("today").unwrap().unwrap().body, "{date}");
+
+        // 冲突时整个导入回滚
+        let mut first = Snippet::new("first", "x");
+        first.abbreviation = "dup".to_string();
+        let mut second = Snippet::new("second", "y");
+        second.abbreviation = "dup".to_string();
+        assert!(db.import_snippets(&[first, second]).is_err());
+        assert!(db.find_snippet("first").unwrap().is_none());
+        assert_eq!(db.get_snippets("", 10).unwrap().len(), 2);
+    }
 }
@@ -0,0 +1,113 @@
+use crate::clipboard::private_mode::PrivateStatus;
+use crate::clipboard::types::{ClipboardItem, ContentType};
+use std::sync::mpsc;
+use std::sync::Mutex;
+
+/// 存储层变更事件
+///
+/// 每次写入成功后由 [`crate::storage::Database`] 发布，订阅者据此增量更新，无需轮询。
+#[derive(Debug, Clone)]
+pub enum StorageEvent {
+    /// 新条目已写入，只带摘要，需要内容的订阅者按 ID 从数据库读取
+    ItemAdded(ItemSummary),
+    /// 已有条目被修改（收藏、访问记录、重复复制时置顶等）
+    ItemUpdated(i64),
+    /// 条目被删除（手动删除或超出数量上限被清理）
+    ItemDeleted(i64),
+    /// 所有条目被清空，附带被删除条目的 ID
+    Cleared(Vec<i64>),
+    /// 过期条目已删除，附带其内容哈希，仍在系统剪切板上的内容需要清除
+    ContentExpired(Vec<String>),
+    /// 后台写入失败
+    WriteFailed(String),
+    /// 捕获的内容因敏感被跳过，附带命中的规则名称
+    CaptureSkipped(String),
+    /// 捕获规则拦截了来自某个程序的内容
+    CaptureBlocked { rule: String, app: Option<String> },
+    /// 隐私模式开启、关闭或到期自动恢复
+    RecordingChanged(PrivateStatus),
+    /// 片段被添加、修改、删除或导入
+    SnippetsChanged,
+    /// 其他进程（守护进程、命令行）修改了数据库，订阅者应重新加载
+    ExternalChange,
+}
+
+/// 新条目的摘要
+///
+/// 事件会复制给每个订阅者并在其队列中等待处理，因此不携带文本和图片内容。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemSummary {
+    pub id: i64,
+    pub content_type: ContentType,
+    pub content_hash: String,
+    /// 敏感内容检测命中的标签
+    pub sensitive: Option<String>,
+}
+
+impl ItemSummary {
+    pub fn new(id: i64, item: &ClipboardItem) -> Self {
+        Self {
+            id,
+            content_type: item.content_type.clone(),
+            content_hash: item.content_hash.clone(),
+            sensitive: item.sensitive.clone(),
+        }
+    }
+}
+
+/// 简单的一对多事件分发器
+#[derive(Default)]
+pub struct EventBus {
+    subscribers: Mutex<Vec<mpsc::Sender<StorageEvent>>>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe(&self) -> mpsc::Receiver<StorageEvent> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+
+    pub fn publish(&self, event: StorageEvent) {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        // 接收端已关闭的订阅者直接移除
+        subscribers.retain(|sender| sender.send(event.clone()).is_ok());
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.lock().unwrap().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_publish_to_all_subscribers() {
+        let bus = EventBus::new();
+        let rx1 = bus.subscribe();
+        let rx2 = bus.subscribe();
+
+        bus.publish(StorageEvent::ItemDeleted(7));
+
+        assert!(matches!(rx1.try_recv(), Ok(StorageEvent::ItemDeleted(7))));
+        assert!(matches!(rx2.try_recv(), Ok(StorageEvent::ItemDeleted(7))));
+    }
+
+    #[test]
+    fn test_dropped_subscriber_is_removed() {
+        let bus = EventBus::new();
+        let rx = bus.subscribe();
+        drop(bus.subscribe());
+
+        bus.publish(StorageEvent::Cleared(vec![1]));
+
+        assert_eq!(bus.subscriber_count(), 1);
+        assert!(matches!(rx.try_recv(), Ok(StorageEvent::Cleared(_))));
+    }
+}
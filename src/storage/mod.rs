@@ -1,4 +1,26 @@
 pub mod database;
+pub mod events;
 pub mod models;
+pub mod writer;
 
 pub use database::Database;
+pub use events::{ItemSummary, StorageEvent};
+pub use writer::StorageWriter;
+
+use crate::error::{ClipManagerError, Result};
+use directories::ProjectDirs;
+use std::path::PathBuf;
+
+/// 默认数据库位置（用户数据目录），GUI 与守护进程共用
+pub fn default_database_path() -> Result<PathBuf> {
+    let proj_dirs = ProjectDirs::from("com", "clipmanager", "ClipManager").ok_or_else(|| {
+        ClipManagerError::Config {
+            message: "Unable to determine data directory".to_string(),
+        }
+    })?;
+
+    let data_dir = proj_dirs.data_dir();
+    std::fs::create_dir_all(data_dir)?;
+
+    Ok(data_dir.join("clipmanager.db"))
+}
@@ -1,6 +1,7 @@
 use crate::clipboard::types::{
-    ClipboardContent, ClipboardItem, ContentType, ImageData, ImageFormat,
+    ClipboardContent, ClipboardItem, ContentType, ImageData, ImageFormat, SourceApp,
 };
+use crate::snippets::Snippet;
 use rusqlite::Row;
 
 impl ClipboardItem {
@@ -42,6 +43,14 @@ impl ClipboardItem {
             }
         };
 
+        let source_name: Option<String> = row.get("source_app")?;
+        let source_pid: Option<u32> = row.get("source_pid")?;
+        let source = (source_name.is_some() || source_pid.is_some()).then_some(SourceApp {
+            name: source_name,
+            pid: source_pid,
+            title: None,
+        });
+
         Ok(Self {
             id: Some(row.get("id")?),
             content_type,
@@ -52,6 +61,22 @@ impl ClipboardItem {
             accessed_at: row.get("accessed_at")?,
             access_count: row.get::<_, i64>("access_count")? as u32,
             is_favorite: row.get("is_favorite")?,
+            sensitive: row.get("sensitive")?,
+            expires_at: row.get("expires_at")?,
+            source,
+        })
+    }
+}
+
+impl Snippet {
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: Some(row.get("id")?),
+            name: row.get("name")?,
+            abbreviation: row.get("abbreviation")?,
+            folder: row.get("folder")?,
+            body: row.get("body")?,
+            updated_at: row.get("updated_at")?,
         })
     }
 }
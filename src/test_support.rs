@@ -0,0 +1,203 @@
+// 测试辅助：私有 D-Bus 会话总线，以及不访问系统剪切板的监控与剪切板替身
+
+#[cfg(unix)]
+use crate::clipboard::private_mode::{PrivateDuration, PrivateMode, PrivateStatus};
+use crate::clipboard::source::{ClipboardSource, RawImage};
+use crate::clipboard::types::{ClipboardItem, SourceApp};
+use crate::error::Result;
+#[cfg(unix)]
+use crate::ipc::server::ClipboardControl;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// 为单个测试启动的私有 dbus-daemon，释放时结束进程
+pub struct PrivateBus {
+    daemon: Child,
+    address: String,
+    _dir: tempfile::TempDir,
+}
+
+impl PrivateBus {
+    /// 启动私有总线，系统中没有 dbus-daemon 时失败
+    ///
+    /// 使用它的测试标记为 `#[ignore]`，通过 `cargo test -- --ignored` 运行。
+    pub fn start() -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("bus.conf");
+        std::fs::write(
+            &config,
+            format!(
+                r#"<!DOCTYPE busconfig PUBLIC "-//freedesktop//DTD D-Bus Bus Configuration 1.0//EN"
+ "http://www.freedesktop.org/standards/dbus/1.0/busconfig.dtd">
+<busconfig>
+  <type>session</type>
+  <listen>unix:dir={}</listen>
+  <auth>EXTERNAL</auth>
+  <policy context="default">
+    <allow send_destination="*" eavesdrop="true"/>
+    <allow eavesdrop="true"/>
+    <allow own="*"/>
+  </policy>
+</busconfig>"#,
+                dir.path().display()
+            ),
+        )
+        .unwrap();
+
+        let mut daemon = Command::new("dbus-daemon")
+            .arg(format!("--config-file={}", config.display()))
+            .arg("--nofork")
+            .arg("--print-address=1")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap_or_else(|e| panic!("Failed to start dbus-daemon: {}", e));
+
+        let mut address = String::new();
+        let stdout = daemon.stdout.take().unwrap();
+        BufReader::new(stdout).read_line(&mut address).unwrap();
+        let address = address.trim().to_string();
+        if address.is_empty() {
+            let _ = daemon.kill();
+            panic!("dbus-daemon did not print its address");
+        }
+
+        Self {
+            daemon,
+            address,
+            _dir: dir,
+        }
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn connect(&self) -> zbus::blocking::Connection {
+        zbus::blocking::connection::Builder::address(self.address())
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+}
+
+impl Drop for PrivateBus {
+    fn drop(&mut self) {
+        let _ = self.daemon.kill();
+        let _ = self.daemon.wait();
+    }
+}
+
+/// 记录复制请求的剪切板监控替身
+#[cfg(unix)]
+#[derive(Default)]
+pub struct FakeClipboard {
+    pub private: PrivateMode,
+    pub skipped: AtomicU64,
+    pub copied: Mutex<Vec<i64>>,
+}
+
+#[cfg(unix)]
+impl FakeClipboard {
+    /// 隐私模式变化通过数据库发布，与真实监控一致
+    pub fn with_database(database: &Arc<crate::storage::Database>) -> Self {
+        Self {
+            private: PrivateMode::with_database(Arc::clone(database)),
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(unix)]
+impl ClipboardControl for Arc<FakeClipboard> {
+    fn set_private_mode(&self, duration: Option<PrivateDuration>) -> PrivateStatus {
+        self.private.set(duration)
+    }
+
+    fn private_status(&self) -> PrivateStatus {
+        self.private.status()
+    }
+
+    fn skipped_count(&self) -> u64 {
+        self.skipped.load(Ordering::Relaxed)
+    }
+
+    fn copy_item(&self, item: &ClipboardItem) -> Result<()> {
+        self.copied
+            .lock()
+            .unwrap()
+            .push(item.id.unwrap_or_default());
+        Ok(())
+    }
+}
+
+/// 内存中的剪切板后端，模拟所有者提供的内容和格式列表
+#[derive(Default)]
+pub struct FakeSource {
+    text: Option<String>,
+    image: Option<(usize, usize, Vec<u8>)>,
+    targets: Vec<String>,
+    data: HashMap<String, Vec<u8>>,
+    owner: Option<SourceApp>,
+}
+
+impl FakeSource {
+    /// 模拟新的所有者写入文本，之前的格式数据一并清除
+    pub fn set_text(&mut self, text: &str, targets: &[&str]) {
+        self.text = Some(text.to_string());
+        self.image = None;
+        self.set_targets(targets);
+    }
+
+    pub fn set_image(&mut self, width: usize, height: usize, bytes: &[u8], targets: &[&str]) {
+        self.text = None;
+        self.image = Some((width, height, bytes.to_vec()));
+        self.set_targets(targets);
+    }
+
+    pub fn set_owner(&mut self, name: &str, pid: u32, title: &str) {
+        self.owner = Some(SourceApp {
+            name: Some(name.to_string()),
+            pid: Some(pid),
+            title: Some(title.to_string()),
+        });
+    }
+
+    pub fn set_data(&mut self, target: &str, data: &[u8]) {
+        self.data.insert(target.to_string(), data.to_vec());
+    }
+
+    fn set_targets(&mut self, targets: &[&str]) {
+        self.targets = targets.iter().map(|target| target.to_string()).collect();
+        self.data.clear();
+    }
+}
+
+impl ClipboardSource for FakeSource {
+    fn text(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn image(&mut self) -> Option<RawImage> {
+        self.image.as_ref().map(|(width, height, bytes)| RawImage {
+            width: *width,
+            height: *height,
+            bytes: bytes.clone(),
+        })
+    }
+
+    fn targets(&mut self) -> Vec<String> {
+        self.targets.clone()
+    }
+
+    fn read(&mut self, target: &str) -> Option<Vec<u8>> {
+        self.data.get(target).cloned()
+    }
+
+    fn owner(&mut self) -> Option<SourceApp> {
+        self.owner.clone()
+    }
+}
@@ -0,0 +1,230 @@
+// 运行用户定义的外部命令动作
+
+use crate::config::actions::{ActionsConfig, CommandAction};
+use crate::error::{ClipManagerError, Result};
+use regex::Regex;
+use std::io::{Read, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// 错误信息中保留的标准错误输出长度
+const STDERR_LIMIT: u64 = 4096;
+
+fn error(action: &CommandAction, message: impl Into<String>) -> ClipManagerError {
+    ClipManagerError::Action {
+        name: action.name.clone(),
+        message: message.into(),
+    }
+}
+
+/// 在后台线程中读取管道，最多读取 `limit + 1` 字节以便判断是否超限
+fn read_pipe(pipe: Option<impl Read + Send + 'static>, limit: u64) -> mpsc::Receiver<Vec<u8>> {
+    let (sender, receiver) = mpsc::channel();
+    if let Some(pipe) = pipe {
+        std::thread::spawn(move || {
+            let mut data = Vec::new();
+            let _ = pipe.take(limit + 1).read_to_end(&mut data);
+            let _ = sender.send(data);
+        });
+    }
+    receiver
+}
+
+fn kill(child: &mut Child) {
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// 把文本交给命令处理并返回其输出
+///
+/// 输入没有以换行结尾时去掉输出末尾的一个换行，大多数命令行工具都会多输出一个。
+pub fn run(action: &CommandAction, input: &str) -> Result<String> {
+    if input.len() > action.max_input_bytes {
+        return Err(error(
+            action,
+            format!(
+                "input is {} bytes, limit is {}",
+                input.len(),
+                action.max_input_bytes
+            ),
+        ));
+    }
+
+    let mut child = Command::new(&action.command)
+        .args(&action.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| error(action, format!("cannot start '{}': {}", action.command, e)))?;
+    let deadline = Instant::now() + Duration::from_secs(action.timeout_secs);
+
+    // 写入与读取放在不同线程，避免双方的管道缓冲区写满后互相等待
+    if let Some(mut stdin) = child.stdin.take() {
+        let input = input.to_string();
+        std::thread::spawn(move || {
+            // 命令不读取输入就退出时会写入失败，这不是错误
+            let _ = stdin.write_all(input.as_bytes());
+        });
+    }
+    let limit = action.max_output_bytes as u64;
+    let stdout = read_pipe(child.stdout.take(), limit);
+    let stderr = read_pipe(child.stderr.take(), STDERR_LIMIT);
+
+    let output = match stdout.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+        Ok(output) => output,
+        Err(_) => {
+            kill(&mut child);
+            return Err(error(
+                action,
+                format!("timed out after {} s", action.timeout_secs),
+            ));
+        }
+    };
+    if output.len() as u64 > limit {
+        kill(&mut child);
+        return Err(error(
+            action,
+            format!("output exceeds {} bytes", action.max_output_bytes),
+        ));
+    }
+
+    // 输出已经读完，等待进程退出
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            kill(&mut child);
+            return Err(error(
+                action,
+                format!("timed out after {} s", action.timeout_secs),
+            ));
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    };
+    if !status.success() {
+        let stderr = stderr
+            .recv_timeout(Duration::from_millis(100))
+            .unwrap_or_default();
+        let stderr = String::from_utf8_lossy(&stderr);
+        let message = match stderr.trim() {
+            "" => format!("exited with {}", status),
+            stderr => format!("exited with {}: {}", status, stderr),
+        };
+        return Err(error(action, message));
+    }
+
+    let mut output =
+        String::from_utf8(output).map_err(|_| error(action, "output is not UTF-8 text"))?;
+    if !input.ends_with('\n') && output.ends_with('\n') {
+        output.pop();
+        if output.ends_with('\r') {
+            output.pop();
+        }
+    }
+    if output.is_empty() {
+        return Err(error(action, "produced no output"));
+    }
+    Ok(output)
+}
+
+/// 捕获时自动运行的动作及其编译后的表达式
+#[derive(Default)]
+pub struct AutoActions {
+    actions: Vec<(CommandAction, Regex)>,
+}
+
+impl AutoActions {
+    /// 无效的表达式记录警告后跳过
+    pub fn new(config: &ActionsConfig) -> Self {
+        let actions = config
+            .actions
+            .iter()
+            .filter(|action| !action.auto_pattern.is_empty())
+            .filter_map(|action| match Regex::new(&action.auto_pattern) {
+                Ok(regex) => Some((action.clone(), regex)),
+                Err(e) => {
+                    log::warn!(
+                        "Ignoring auto-run pattern of action '{}': {}",
+                        action.name,
+                        e
+                    );
+                    None
+                }
+            })
+            .collect();
+        Self { actions }
+    }
+
+    /// 第一个匹配文本的动作
+    pub fn find(&self, text: &str) -> Option<&CommandAction> {
+        self.actions
+            .iter()
+            .find(|(_, regex)| regex.is_match(text))
+            .map(|(action, _)| action)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn sh(script: &str) -> CommandAction {
+        CommandAction {
+            args: vec!["-c".to_string(), script.to_string()],
+            ..CommandAction::new("test", "sh")
+        }
+    }
+
+    #[test]
+    fn test_run_command() {
+        assert_eq!(run(&sh("tr a-z A-Z"), "hello").unwrap(), "HELLO");
+        // 输入以换行结尾时保留输出的换行
+        assert_eq!(run(&sh("cat"), "line\n").unwrap(), "line\n");
+        assert_eq!(run(&sh("echo done"), "ignored").unwrap(), "done");
+
+        let error = run(&sh("echo broken >&2; exit 3"), "x").unwrap_err();
+        assert!(error.to_string().contains("broken"), "{}", error);
+        assert!(run(&sh("true"), "x").is_err());
+        assert!(run(&CommandAction::new("missing", "/nonexistent/command"), "x").is_err());
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut action = sh("sleep 5");
+        action.timeout_secs = 1;
+        let started = Instant::now();
+        let error = run(&action, "x").unwrap_err();
+        assert!(error.to_string().contains("timed out"), "{}", error);
+        assert!(started.elapsed() < Duration::from_secs(3));
+
+        let mut action = sh("yes");
+        action.max_output_bytes = 1000;
+        let error = run(&action, "x").unwrap_err();
+        assert!(error.to_string().contains("exceeds"), "{}", error);
+
+        let mut action = sh("cat");
+        action.max_input_bytes = 3;
+        assert!(run(&action, "four").is_err());
+
+        // 大量输入输出不会因管道缓冲区写满而卡住
+        let input = "x".repeat(512 * 1024);
+        assert_eq!(run(&sh("cat"), &input).unwrap(), input);
+    }
+
+    #[test]
+    fn test_auto_actions() {
+        let mut url = CommandAction::new("Shorten", "shorten");
+        url.auto_pattern = "^https?://".to_string();
+        let mut broken = CommandAction::new("Broken", "broken");
+        broken.auto_pattern = "(".to_string();
+        let auto = AutoActions::new(&ActionsConfig {
+            actions: vec![CommandAction::new("Manual", "manual"), broken, url],
+        });
+
+        assert_eq!(auto.find("https://example.com").unwrap().name, "Shorten");
+        assert!(auto.find("plain text").is_none());
+    }
+}
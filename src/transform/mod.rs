@@ -0,0 +1,601 @@
+//! Text transformations
+//!
+//! Pure functions that reformat the text of a history item: whitespace, case
+//! conversion, JSON formatting, encodings, escaping, line operations and
+//! stripping formatting left over from terminals or web pages. The window
+//! offers them in each text item's context menu; the result is either copied
+//! or saved as a new item.
+//!
+//! User-defined actions in [`command`] pipe the text through an external
+//! command instead, either on demand or automatically on capture.
+
+pub mod command;
+
+use crate::i18n::TextKey;
+use crate::ipc::protocol::{base64_decode, base64_encode};
+use regex::Regex;
+
+/// 对文本的一种变换
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transform {
+    Trim,
+    CollapseWhitespace,
+    Uppercase,
+    Lowercase,
+    TitleCase,
+    SnakeCase,
+    CamelCase,
+    JsonPretty,
+    JsonMinify,
+    Base64Encode,
+    Base64Decode,
+    UrlEncode,
+    UrlDecode,
+    JsonEscape,
+    JsonUnescape,
+    ShellEscape,
+    ShellUnescape,
+    SortLines,
+    DedupeLines,
+    StripFormatting,
+}
+
+impl Transform {
+    /// 按类别分组，菜单中组之间用分隔线隔开
+    pub const GROUPS: [&'static [Transform]; 6] = [
+        &[
+            Transform::Trim,
+            Transform::CollapseWhitespace,
+            Transform::StripFormatting,
+        ],
+        &[
+            Transform::Uppercase,
+            Transform::Lowercase,
+            Transform::TitleCase,
+            Transform::SnakeCase,
+            Transform::CamelCase,
+        ],
+        &[Transform::JsonPretty, Transform::JsonMinify],
+        &[
+            Transform::Base64Encode,
+            Transform::Base64Decode,
+            Transform::UrlEncode,
+            Transform::UrlDecode,
+        ],
+        &[
+            Transform::JsonEscape,
+            Transform::JsonUnescape,
+            Transform::ShellEscape,
+            Transform::ShellUnescape,
+        ],
+        &[Transform::SortLines, Transform::DedupeLines],
+    ];
+
+    pub fn text_key(&self) -> TextKey {
+        match self {
+            Transform::Trim => TextKey::TransformTrim,
+            Transform::CollapseWhitespace => TextKey::TransformCollapseWhitespace,
+            Transform::Uppercase => TextKey::TransformUppercase,
+            Transform::Lowercase => TextKey::TransformLowercase,
+            Transform::TitleCase => TextKey::TransformTitleCase,
+            Transform::SnakeCase => TextKey::TransformSnakeCase,
+            Transform::CamelCase => TextKey::TransformCamelCase,
+            Transform::JsonPretty => TextKey::TransformJsonPretty,
+            Transform::JsonMinify => TextKey::TransformJsonMinify,
+            Transform::Base64Encode => TextKey::TransformBase64Encode,
+            Transform::Base64Decode => TextKey::TransformBase64Decode,
+            Transform::UrlEncode => TextKey::TransformUrlEncode,
+            Transform::UrlDecode => TextKey::TransformUrlDecode,
+            Transform::JsonEscape => TextKey::TransformJsonEscape,
+            Transform::JsonUnescape => TextKey::TransformJsonUnescape,
+            Transform::ShellEscape => TextKey::TransformShellEscape,
+            Transform::ShellUnescape => TextKey::TransformShellUnescape,
+            Transform::SortLines => TextKey::TransformSortLines,
+            Transform::DedupeLines => TextKey::TransformDedupeLines,
+            Transform::StripFormatting => TextKey::TransformStripFormatting,
+        }
+    }
+
+    /// 变换文本，输入格式不正确时返回错误说明
+    pub fn apply(&self, text: &str) -> Result<String, String> {
+        Ok(match self {
+            Transform::Trim => trim(text),
+            Transform::CollapseWhitespace => text.split_whitespace().collect::<Vec<_>>().join(" "),
+            Transform::Uppercase => text.to_uppercase(),
+            Transform::Lowercase => text.to_lowercase(),
+            Transform::TitleCase => title_case(text),
+            Transform::SnakeCase => map_lines(text, snake_case),
+            Transform::CamelCase => map_lines(text, camel_case),
+            Transform::JsonPretty => reformat_json(text, true)?,
+            Transform::JsonMinify => reformat_json(text, false)?,
+            Transform::Base64Encode => base64_encode(text.as_bytes()),
+            Transform::Base64Decode => decode_base64(text)?,
+            Transform::UrlEncode => url_encode(text),
+            Transform::UrlDecode => url_decode(text)?,
+            Transform::JsonEscape => json_escape(text),
+            Transform::JsonUnescape => json_unescape(text)?,
+            Transform::ShellEscape => shell_escape(text),
+            Transform::ShellUnescape => shell_unescape(text)?,
+            Transform::SortLines => sort_lines(text, false),
+            Transform::DedupeLines => sort_lines(text, true),
+            Transform::StripFormatting => strip_formatting(text),
+        })
+    }
+}
+
+/// 去掉首尾空白和每行行尾的空白
+fn trim(text: &str) -> String {
+    text.lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// 逐行变换，保留末尾的换行
+fn map_lines(text: &str, f: impl Fn(&str) -> String) -> String {
+    let mut result = text.lines().map(f).collect::<Vec<_>>().join("\n");
+    if text.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+/// 每个单词首字母大写，其余小写
+fn title_case(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut word_start = true;
+    for c in text.chars() {
+        if word_start {
+            result.extend(c.to_uppercase());
+        } else {
+            result.extend(c.to_lowercase());
+        }
+        word_start = c.is_whitespace() || c == '-' || c == '_';
+    }
+    result
+}
+
+/// 按非字母数字字符和驼峰边界拆分单词，`HTTPServer` 拆为 `HTTP`、`Server`
+fn split_words(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if c.is_uppercase() && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_lower) {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn snake_case(text: &str) -> String {
+    split_words(text)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+fn camel_case(text: &str) -> String {
+    split_words(text)
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+            let word = word.to_lowercase();
+            if i == 0 {
+                return word;
+            }
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// 重新排版 JSON，保留键的顺序和数字的原始写法
+fn reformat_json(text: &str, pretty: bool) -> Result<String, String> {
+    serde_json::from_str::<serde::de::IgnoredAny>(text)
+        .map_err(|e| format!("Invalid JSON: {}", e))?;
+
+    let newline = |out: &mut String, indent: usize| {
+        if pretty {
+            out.push('\n');
+            out.push_str(&"  ".repeat(indent));
+        }
+    };
+    let mut out = String::with_capacity(text.len());
+    let mut indent = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = text.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '{' | '[' => {
+                out.push(c);
+                while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                    chars.next();
+                }
+                // 空对象和空数组保持在一行
+                let close = if c == '{' { '}' } else { ']' };
+                if chars.peek() == Some(&close) {
+                    out.extend(chars.next());
+                } else {
+                    indent += 1;
+                    newline(&mut out, indent);
+                }
+            }
+            '}' | ']' => {
+                indent -= 1;
+                newline(&mut out, indent);
+                out.push(c);
+            }
+            ',' => {
+                out.push(c);
+                newline(&mut out, indent);
+            }
+            ':' => {
+                out.push(c);
+                if pretty {
+                    out.push(' ');
+                }
+            }
+            c if c.is_whitespace() => {}
+            c => out.push(c),
+        }
+    }
+    Ok(out)
+}
+
+/// 解码 base64，也接受 URL 安全字母表和换行
+fn decode_base64(text: &str) -> Result<String, String> {
+    let normalized: String = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            '-' => '+',
+            '_' => '/',
+            c => c,
+        })
+        .collect();
+    let data = base64_decode(&normalized).ok_or("Invalid base64")?;
+    String::from_utf8(data).map_err(|_| "Decoded data is not UTF-8 text".to_string())
+}
+
+/// 百分号编码，只保留 RFC 3986 的非保留字符
+fn url_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+fn url_decode(text: &str) -> Result<String, String> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let byte = text
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| format!("Invalid percent escape at position {}", i))?;
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| "Decoded data is not UTF-8 text".to_string())
+}
+
+/// 转义为 JSON 字符串的内容（不含两端的引号）
+fn json_escape(text: &str) -> String {
+    let quoted = serde_json::to_string(text).unwrap_or_default();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+/// 反转义 JSON 字符串，两端的引号可有可无
+fn json_unescape(text: &str) -> Result<String, String> {
+    let trimmed = text.trim();
+    let quoted = if trimmed.len() >= 2 && trimmed.starts_with('"') && trimmed.ends_with('"') {
+        trimmed.to_string()
+    } else {
+        format!("\"{}\"", text)
+    };
+    serde_json::from_str(&quoted).map_err(|e| format!("Invalid JSON string: {}", e))
+}
+
+/// 转义为一个 POSIX shell 参数，只含安全字符时原样返回
+fn shell_escape(text: &str) -> String {
+    let safe = !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-./:=@%+,".contains(c));
+    if safe {
+        text.to_string()
+    } else {
+        format!("'{}'", text.replace('\'', r"'\''"))
+    }
+}
+
+/// 去掉 shell 的引号和反斜杠转义，单词之间的空白保持不变
+fn shell_unescape(text: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => loop {
+                match chars.next() {
+                    Some('\'') => break,
+                    Some(c) => out.push(c),
+                    None => return Err("Unterminated single quote".to_string()),
+                }
+            },
+            '"' => loop {
+                match chars.next() {
+                    Some('"') => break,
+                    // 双引号内只有这几个字符可以转义
+                    Some('\\') => match chars.next() {
+                        Some(c @ ('"' | '\\' | '$' | '`')) => out.push(c),
+                        Some('\n') => {}
+                        Some(c) => {
+                            out.push('\\');
+                            out.push(c);
+                        }
+                        None => return Err("Unterminated double quote".to_string()),
+                    },
+                    Some(c) => out.push(c),
+                    None => return Err("Unterminated double quote".to_string()),
+                }
+            },
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some(c) => out.push(c),
+                None => out.push('\\'),
+            },
+            c => out.push(c),
+        }
+    }
+    Ok(out)
+}
+
+/// 按行排序，`dedupe` 时改为去除重复行并保持原有顺序
+fn sort_lines(text: &str, dedupe: bool) -> String {
+    let mut lines: Vec<&str> = text.lines().collect();
+    if dedupe {
+        let mut seen = std::collections::HashSet::new();
+        lines.retain(|line| seen.insert(*line));
+    } else {
+        lines.sort_unstable();
+    }
+    let mut result = lines.join("\n");
+    if text.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+/// 去掉终端颜色代码、HTML 标签和实体以及不可见字符，只留下纯文本
+fn strip_formatting(text: &str) -> String {
+    let ansi = Regex::new(r"\x1b\[[0-9;?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)").unwrap();
+    let tags = Regex::new(r"</?[A-Za-z][^<>]*>").unwrap();
+    let entities = Regex::new(r"&(#[0-9]+|#[xX][0-9A-Fa-f]+|[A-Za-z]+);").unwrap();
+
+    let text = ansi.replace_all(text, "");
+    let text = tags.replace_all(&text, "");
+    let text = entities.replace_all(&text, |captures: &regex::Captures| {
+        let entity = &captures[1];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|n| n.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        decoded.map_or_else(|| captures[0].to_string(), String::from)
+    });
+    text.chars()
+        .filter(|c| {
+            !matches!(
+                c,
+                '\u{200b}'..='\u{200d}' | '\u{2060}' | '\u{feff}' | '\u{ad}'
+            )
+        })
+        .map(|c| match c {
+            '\u{a0}' | '\u{202f}' => ' ',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(transform: Transform, text: &str) -> String {
+        transform.apply(text).unwrap()
+    }
+
+    #[test]
+    fn test_whitespace() {
+        assert_eq!(
+            apply(Transform::Trim, "  hello  \n  world \t\n\n"),
+            "hello\n  world"
+        );
+        assert_eq!(
+            apply(Transform::CollapseWhitespace, " a \t b\n\n c "),
+            "a b c"
+        );
+    }
+
+    #[test]
+    fn test_case_conversion() {
+        assert_eq!(apply(Transform::Uppercase, "straße"), "STRASSE");
+        assert_eq!(apply(Transform::Lowercase, "HeLLo"), "hello");
+        assert_eq!(
+            apply(Transform::TitleCase, "the QUICK brown-fox"),
+            "The Quick Brown-Fox"
+        );
+        assert_eq!(
+            apply(Transform::SnakeCase, "parseHTTPServer response-code"),
+            "parse_http_server_response_code"
+        );
+        assert_eq!(
+            apply(Transform::SnakeCase, "userId\nAPIKey\n"),
+            "user_id\napi_key\n"
+        );
+        assert_eq!(
+            apply(Transform::CamelCase, "user_account id"),
+            "userAccountId"
+        );
+        assert_eq!(
+            apply(Transform::CamelCase, "XMLHttpRequest"),
+            "xmlHttpRequest"
+        );
+    }
+
+    #[test]
+    fn test_json_formatting() {
+        let json = r#"{"b": [1, 2.50, {}], "a": {"s": "x, y: \"z\" {"}, "e": []}"#;
+        assert_eq!(
+            apply(Transform::JsonMinify, json),
+            r#"{"b":[1,2.50,{}],"a":{"s":"x, y: \"z\" {"},"e":[]}"#
+        );
+        assert_eq!(
+            apply(Transform::JsonPretty, json),
+            "{\n  \"b\": [\n    1,\n    2.50,\n    {}\n  ],\n  \"a\": {\n    \"s\": \"x, y: \\\"z\\\" {\"\n  },\n  \"e\": []\n}"
+        );
+        assert!(Transform::JsonPretty.apply("{not json}").is_err());
+        assert!(Transform::JsonMinify.apply("[1] [2]").is_err());
+    }
+
+    #[test]
+    fn test_encodings() {
+        assert_eq!(
+            apply(Transform::Base64Encode, "hello 世界"),
+            "aGVsbG8g5LiW55WM"
+        );
+        assert_eq!(
+            apply(Transform::Base64Decode, "aGVsbG8g\n5LiW55WM"),
+            "hello 世界"
+        );
+        assert_eq!(apply(Transform::Base64Decode, "Pz8_"), "???");
+        assert!(Transform::Base64Decode.apply("not base64!").is_err());
+        assert!(Transform::Base64Decode.apply("/w==").is_err());
+
+        assert_eq!(
+            apply(Transform::UrlEncode, "a b&c=d/é~"),
+            "a%20b%26c%3Dd%2F%C3%A9~"
+        );
+        assert_eq!(
+            apply(Transform::UrlDecode, "a%20b%26c%3dd%2F%C3%A9"),
+            "a b&c=d/é"
+        );
+        assert!(Transform::UrlDecode.apply("100%").is_err());
+        assert!(Transform::UrlDecode.apply("%zz").is_err());
+    }
+
+    #[test]
+    fn test_escaping() {
+        let text = "say \"hi\"\n\tC:\\path";
+        let escaped = apply(Transform::JsonEscape, text);
+        assert_eq!(escaped, r#"say \"hi\"\n\tC:\\path"#);
+        assert_eq!(apply(Transform::JsonUnescape, &escaped), text);
+        assert_eq!(apply(Transform::JsonUnescape, r#""é""#), "é");
+        assert!(Transform::JsonUnescape.apply(r"bad \q").is_err());
+
+        assert_eq!(apply(Transform::ShellEscape, "file.txt"), "file.txt");
+        assert_eq!(apply(Transform::ShellEscape, ""), "''");
+        let escaped = apply(Transform::ShellEscape, "it's $HOME");
+        assert_eq!(escaped, r"'it'\''s $HOME'");
+        assert_eq!(apply(Transform::ShellUnescape, &escaped), "it's $HOME");
+        assert_eq!(
+            apply(Transform::ShellUnescape, r#"echo "a \"b\" \$c" d\ e"#),
+            r#"echo a "b" $c d e"#
+        );
+        assert!(Transform::ShellUnescape.apply("'open").is_err());
+        assert!(Transform::ShellUnescape.apply("\"open").is_err());
+    }
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(apply(Transform::SortLines, "b\na\nc\na\n"), "a\na\nb\nc\n");
+        assert_eq!(apply(Transform::DedupeLines, "b\na\nb\nc\na"), "b\na\nc");
+    }
+
+    #[test]
+    fn test_strip_formatting() {
+        assert_eq!(
+            apply(Transform::StripFormatting, "\x1b[1;31merror\x1b[0m: failed"),
+            "error: failed"
+        );
+        assert_eq!(
+            apply(
+                Transform::StripFormatting,
+                "<p>Fish &amp; <b>chips</b>&nbsp;&#8364;5 &#x263A; &bogus;</p>"
+            ),
+            "Fish & chips €5 ☺ &bogus;"
+        );
+        assert_eq!(
+            apply(Transform::StripFormatting, "a\u{200b}b\u{a0}c 1 < 2"),
+            "ab c 1 < 2"
+        );
+    }
+
+    #[test]
+    fn test_groups_cover_all_transforms() {
+        let count: usize = Transform::GROUPS.iter().map(|group| group.len()).sum();
+        assert_eq!(count, 20);
+        for group in Transform::GROUPS {
+            for transform in group {
+                assert_ne!(crate::i18n::t(transform.text_key()), "");
+            }
+        }
+    }
+}
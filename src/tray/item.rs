@@ -0,0 +1,71 @@
+// org.kde.StatusNotifierItem 接口
+
+use super::{EventSink, TrayEvent, TrayState, MENU_PATH};
+use std::sync::{Arc, Mutex};
+use zbus::zvariant::OwnedObjectPath;
+
+pub(crate) struct StatusNotifierItem {
+    state: Arc<Mutex<TrayState>>,
+    sink: Arc<EventSink>,
+}
+
+impl StatusNotifierItem {
+    pub(crate) fn new(state: Arc<Mutex<TrayState>>, sink: Arc<EventSink>) -> Self {
+        Self { state, sink }
+    }
+}
+
+#[zbus::interface(name = "org.kde.StatusNotifierItem")]
+impl StatusNotifierItem {
+    /// 左键点击图标打开主窗口
+    fn activate(&self, _x: i32, _y: i32) {
+        self.sink.send(TrayEvent::OpenWindow);
+    }
+
+    fn secondary_activate(&self, _x: i32, _y: i32) {
+        self.sink.send(TrayEvent::TogglePause);
+    }
+
+    fn context_menu(&self, _x: i32, _y: i32) {}
+
+    fn scroll(&self, _delta: i32, _orientation: String) {}
+
+    #[zbus(property)]
+    fn category(&self) -> String {
+        "ApplicationStatus".to_string()
+    }
+
+    #[zbus(property)]
+    fn id(&self) -> String {
+        "clipmanager".to_string()
+    }
+
+    #[zbus(property)]
+    fn title(&self) -> String {
+        "ClipManager".to_string()
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> String {
+        "Active".to_string()
+    }
+
+    #[zbus(property)]
+    fn icon_name(&self) -> String {
+        if self.state.lock().unwrap().paused {
+            "media-playback-pause".to_string()
+        } else {
+            "edit-paste".to_string()
+        }
+    }
+
+    #[zbus(property)]
+    fn item_is_menu(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn menu(&self) -> OwnedObjectPath {
+        OwnedObjectPath::try_from(MENU_PATH).unwrap()
+    }
+}
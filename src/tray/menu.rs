@@ -0,0 +1,238 @@
+// com.canonical.dbusmenu 接口
+
+use super::{EventSink, TrayEvent, TrayState};
+use crate::i18n::{self, TextKey};
+use crate::ui::main_window::private_mode_options;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use zbus::fdo;
+use zbus::zvariant::{OwnedValue, Value};
+
+const ROOT_ID: i32 = 0;
+/// 最近条目使用 1..=N 作为菜单 ID
+const RECENT_EMPTY_ID: i32 = 999;
+const SEPARATOR_ID: i32 = 1000;
+const PAUSE_ID: i32 = 1001;
+const OPEN_ID: i32 = 1002;
+const SETTINGS_ID: i32 = 1003;
+const SEPARATOR_QUIT_ID: i32 = 1004;
+const QUIT_ID: i32 = 1005;
+/// 未暂停时显示的定时暂停选项
+const PAUSE_OPTION_IDS: [i32; 3] = [1006, 1007, 1008];
+
+type Properties = HashMap<String, OwnedValue>;
+type Layout = (i32, Properties, Vec<OwnedValue>);
+
+pub(crate) struct DbusMenu {
+    state: Arc<Mutex<TrayState>>,
+    sink: Arc<EventSink>,
+}
+
+impl DbusMenu {
+    pub(crate) fn new(state: Arc<Mutex<TrayState>>, sink: Arc<EventSink>) -> Self {
+        Self { state, sink }
+    }
+
+    fn label(label: &str) -> Properties {
+        let mut properties = Properties::new();
+        properties.insert("label".to_string(), Value::from(label).try_into().unwrap());
+        properties
+    }
+
+    fn separator() -> Properties {
+        let mut properties = Properties::new();
+        properties.insert(
+            "type".to_string(),
+            Value::from("separator").try_into().unwrap(),
+        );
+        properties
+    }
+
+    /// 当前状态下的所有菜单项（不含根节点）
+    fn entries(&self) -> Vec<(i32, Properties)> {
+        let state = self.state.lock().unwrap();
+        let mut entries = Vec::new();
+
+        if state.recent.is_empty() {
+            let mut properties = Self::label(i18n::t(TextKey::TrayRecentEmpty));
+            properties.insert(
+                "enabled".to_string(),
+                Value::from(false).try_into().unwrap(),
+            );
+            entries.push((RECENT_EMPTY_ID, properties));
+        }
+        for (index, (_, summary)) in state.recent.iter().enumerate() {
+            // 下划线在 dbusmenu 中表示快捷键助记符
+            let label = summary.replace('_', "__");
+            entries.push((index as i32 + 1, Self::label(&label)));
+        }
+
+        entries.push((SEPARATOR_ID, Self::separator()));
+        let mut pause = Self::label(i18n::t(TextKey::TrayPauseRecording));
+        pause.insert(
+            "toggle-type".to_string(),
+            Value::from("checkmark").try_into().unwrap(),
+        );
+        pause.insert(
+            "toggle-state".to_string(),
+            Value::from(state.paused as i32).try_into().unwrap(),
+        );
+        entries.push((PAUSE_ID, pause));
+        if !state.paused {
+            // 最后一项“直到手动恢复”与上面的开关相同
+            for (id, (label, _)) in PAUSE_OPTION_IDS.iter().zip(private_mode_options()) {
+                entries.push((*id, Self::label(i18n::t(label))));
+            }
+        }
+        entries.push((OPEN_ID, Self::label(i18n::t(TextKey::TrayOpenWindow))));
+        entries.push((SETTINGS_ID, Self::label(i18n::t(TextKey::Settings))));
+        entries.push((SEPARATOR_QUIT_ID, Self::separator()));
+        entries.push((QUIT_ID, Self::label(i18n::t(TextKey::TrayQuit))));
+        entries
+    }
+
+    fn event_for(&self, id: i32) -> Option<TrayEvent> {
+        match id {
+            PAUSE_ID => Some(TrayEvent::TogglePause),
+            id if PAUSE_OPTION_IDS.contains(&id) => PAUSE_OPTION_IDS
+                .iter()
+                .zip(private_mode_options())
+                .find(|(option, _)| **option == id)
+                .map(|(_, (_, duration))| TrayEvent::Pause(duration)),
+            OPEN_ID => Some(TrayEvent::OpenWindow),
+            SETTINGS_ID => Some(TrayEvent::OpenSettings),
+            QUIT_ID => Some(TrayEvent::Quit),
+            id if id > 0 => {
+                let state = self.state.lock().unwrap();
+                state
+                    .recent
+                    .get(id as usize - 1)
+                    .map(|(item_id, _)| TrayEvent::CopyItem(*item_id))
+            }
+            _ => None,
+        }
+    }
+
+    fn filter(properties: Properties, names: &[String]) -> Properties {
+        if names.is_empty() {
+            return properties;
+        }
+        properties
+            .into_iter()
+            .filter(|(name, _)| names.contains(name))
+            .collect()
+    }
+}
+
+#[zbus::interface(name = "com.canonical.dbusmenu")]
+impl DbusMenu {
+    /// 菜单只有一层，所有条目都挂在根节点下
+    fn get_layout(
+        &self,
+        parent_id: i32,
+        _recursion_depth: i32,
+        property_names: Vec<String>,
+    ) -> fdo::Result<(u32, Layout)> {
+        let revision = self.state.lock().unwrap().revision;
+        if parent_id != ROOT_ID {
+            let properties = self
+                .entries()
+                .into_iter()
+                .find(|(id, _)| *id == parent_id)
+                .map(|(_, properties)| Self::filter(properties, &property_names))
+                .ok_or_else(|| fdo::Error::InvalidArgs(format!("No menu item {}", parent_id)))?;
+            return Ok((revision, (parent_id, properties, Vec::new())));
+        }
+
+        let mut children = Vec::new();
+        for (id, properties) in self.entries() {
+            let child: Layout = (id, Self::filter(properties, &property_names), Vec::new());
+            let value = OwnedValue::try_from(Value::from(child))
+                .map_err(|e| fdo::Error::Failed(e.to_string()))?;
+            children.push(value);
+        }
+
+        let mut root = Properties::new();
+        root.insert(
+            "children-display".to_string(),
+            Value::from("submenu").try_into().unwrap(),
+        );
+        Ok((revision, (ROOT_ID, root, children)))
+    }
+
+    fn get_group_properties(
+        &self,
+        ids: Vec<i32>,
+        property_names: Vec<String>,
+    ) -> Vec<(i32, Properties)> {
+        self.entries()
+            .into_iter()
+            .filter(|(id, _)| ids.is_empty() || ids.contains(id))
+            .map(|(id, properties)| (id, Self::filter(properties, &property_names)))
+            .collect()
+    }
+
+    fn get_property(&self, id: i32, name: String) -> fdo::Result<OwnedValue> {
+        self.entries()
+            .into_iter()
+            .find(|(entry_id, _)| *entry_id == id)
+            .and_then(|(_, mut properties)| properties.remove(&name))
+            .ok_or_else(|| fdo::Error::InvalidArgs(format!("No property {} on {}", name, id)))
+    }
+
+    fn event(&self, id: i32, event_id: String, _data: OwnedValue, _timestamp: u32) {
+        if event_id != "clicked" {
+            return;
+        }
+        if let Some(event) = self.event_for(id) {
+            self.sink.send(event);
+        }
+    }
+
+    /// 返回找不到的菜单 ID
+    fn event_group(&self, events: Vec<(i32, String, OwnedValue, u32)>) -> Vec<i32> {
+        let mut missing = Vec::new();
+        for (id, event_id, data, timestamp) in events {
+            if event_id == "clicked" && self.event_for(id).is_none() {
+                missing.push(id);
+            }
+            self.event(id, event_id, data, timestamp);
+        }
+        missing
+    }
+
+    fn about_to_show(&self, _id: i32) -> bool {
+        false
+    }
+
+    fn about_to_show_group(&self, _ids: Vec<i32>) -> (Vec<i32>, Vec<i32>) {
+        (Vec::new(), Vec::new())
+    }
+
+    #[zbus(signal)]
+    async fn layout_updated(
+        emitter: &zbus::object_server::SignalEmitter<'_>,
+        revision: u32,
+        parent: i32,
+    ) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    fn version(&self) -> u32 {
+        3
+    }
+
+    #[zbus(property)]
+    fn text_direction(&self) -> String {
+        "ltr".to_string()
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> String {
+        "normal".to_string()
+    }
+
+    #[zbus(property)]
+    fn icon_theme_path(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
@@ -0,0 +1,246 @@
+//! System tray icon
+//!
+//! Exposes a StatusNotifierItem and its com.canonical.dbusmenu menu on the
+//! session bus. The menu lists recent items and offers pause/resume with timed
+//! private mode, open
+//! window, settings and quit; clicks are delivered as [`TrayEvent`]s.
+
+mod item;
+mod menu;
+
+use crate::clipboard::private_mode::PrivateDuration;
+use crate::error::{ClipManagerError, Result};
+use std::sync::{mpsc, Arc, Mutex};
+
+pub const ITEM_PATH: &str = "/StatusNotifierItem";
+pub const MENU_PATH: &str = "/MenuBar";
+
+/// 托盘菜单触发的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayEvent {
+    CopyItem(i64),
+    TogglePause,
+    /// 隐私模式，到期后自动恢复
+    Pause(PrivateDuration),
+    OpenWindow,
+    OpenSettings,
+    Quit,
+}
+
+/// 菜单显示的状态，由应用层更新
+#[derive(Debug, Default)]
+pub(crate) struct TrayState {
+    /// 最近条目的 ID 与摘要
+    recent: Vec<(i64, String)>,
+    paused: bool,
+    /// 菜单布局版本，每次更新递增
+    revision: u32,
+}
+
+/// 把托盘事件发给应用层并唤醒 UI
+pub(crate) struct EventSink {
+    sender: Mutex<mpsc::Sender<TrayEvent>>,
+    on_event: Box<dyn Fn() + Send + Sync>,
+}
+
+impl EventSink {
+    fn send(&self, event: TrayEvent) {
+        log::debug!("Tray event: {:?}", event);
+        if self.sender.lock().unwrap().send(event).is_ok() {
+            (self.on_event)();
+        }
+    }
+}
+
+pub struct Tray {
+    connection: zbus::blocking::Connection,
+    state: Arc<Mutex<TrayState>>,
+}
+
+impl Tray {
+    /// 在会话总线上注册托盘图标
+    ///
+    /// 没有 StatusNotifierWatcher（桌面不支持托盘）时返回错误。
+    pub fn start(
+        sender: mpsc::Sender<TrayEvent>,
+        on_event: impl Fn() + Send + Sync + 'static,
+    ) -> Result<Self> {
+        Self::start_with(
+            zbus::blocking::connection::Builder::session()?,
+            sender,
+            on_event,
+        )
+    }
+
+    pub fn start_with(
+        builder: zbus::blocking::connection::Builder<'_>,
+        sender: mpsc::Sender<TrayEvent>,
+        on_event: impl Fn() + Send + Sync + 'static,
+    ) -> Result<Self> {
+        let state = Arc::new(Mutex::new(TrayState::default()));
+        let sink = Arc::new(EventSink {
+            sender: Mutex::new(sender),
+            on_event: Box::new(on_event),
+        });
+
+        let name = format!("org.kde.StatusNotifierItem-{}-1", std::process::id());
+        let connection = builder
+            .name(name.as_str())?
+            .serve_at(
+                ITEM_PATH,
+                item::StatusNotifierItem::new(Arc::clone(&state), Arc::clone(&sink)),
+            )?
+            .serve_at(MENU_PATH, menu::DbusMenu::new(Arc::clone(&state), sink))?
+            .build()?;
+
+        let watcher = zbus::blocking::Proxy::new(
+            &connection,
+            "org.kde.StatusNotifierWatcher",
+            "/StatusNotifierWatcher",
+            "org.kde.StatusNotifierWatcher",
+        )?;
+        watcher
+            .call_method("RegisterStatusNotifierItem", &(name.as_str(),))
+            .map_err(|e| ClipManagerError::Platform {
+                message: format!("No system tray available: {}", e),
+            })?;
+        log::info!("Tray icon registered as {}", name);
+
+        Ok(Self { connection, state })
+    }
+
+    /// 更新最近条目和暂停状态，并通知托盘宿主刷新菜单
+    pub fn update(&self, recent: Vec<(i64, String)>, paused: bool) {
+        let (revision, paused_changed) = {
+            let mut state = self.state.lock().unwrap();
+            let paused_changed = state.paused != paused;
+            state.recent = recent;
+            state.paused = paused;
+            state.revision += 1;
+            (state.revision, paused_changed)
+        };
+
+        if let Err(e) = self.connection.emit_signal(
+            None::<()>,
+            MENU_PATH,
+            "com.canonical.dbusmenu",
+            "LayoutUpdated",
+            &(revision, 0i32),
+        ) {
+            log::warn!("Failed to emit tray menu update: {}", e);
+        }
+        if paused_changed {
+            let _ = self.connection.emit_signal(
+                None::<()>,
+                ITEM_PATH,
+                "org.kde.StatusNotifierItem",
+                "NewIcon",
+                &(),
+            );
+        }
+    }
+}
+
+/// 菜单中显示的条目摘要
+pub fn summarize(text: &str, max_chars: usize) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() > max_chars {
+        format!("{}…", line.chars().take(max_chars).collect::<String>())
+    } else {
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::PrivateBus;
+    use std::collections::HashMap;
+    use zbus::zvariant::OwnedValue;
+
+    /// 只记录注册名称的 StatusNotifierWatcher
+    struct MockWatcher {
+        registered: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[zbus::interface(name = "org.kde.StatusNotifierWatcher")]
+    impl MockWatcher {
+        fn register_status_notifier_item(&self, service: String) {
+            self.registered.lock().unwrap().push(service);
+        }
+    }
+
+    type Layout = (i32, HashMap<String, OwnedValue>, Vec<OwnedValue>);
+
+    #[test]
+    #[ignore = "requires dbus-daemon"]
+    fn test_tray_menu_over_dbus() {
+        let bus = PrivateBus::start();
+
+        let registered = Arc::new(Mutex::new(Vec::new()));
+        let _watcher = zbus::blocking::connection::Builder::address(bus.address())
+            .unwrap()
+            .name("org.kde.StatusNotifierWatcher")
+            .unwrap()
+            .serve_at(
+                "/StatusNotifierWatcher",
+                MockWatcher {
+                    registered: Arc::clone(&registered),
+                },
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        let tray = Tray::start_with(
+            zbus::blocking::connection::Builder::address(bus.address()).unwrap(),
+            sender,
+            || {},
+        )
+        .unwrap();
+        tray.update(vec![(42, "hello world".to_string())], false);
+
+        let name = registered.lock().unwrap()[0].clone();
+        assert!(name.starts_with("org.kde.StatusNotifierItem-"));
+
+        let client = bus.connect();
+        let menu =
+            zbus::blocking::Proxy::new(&client, name.as_str(), MENU_PATH, "com.canonical.dbusmenu")
+                .unwrap();
+        let (revision, (root_id, _, children)): (u32, Layout) = menu
+            .call("GetLayout", &(0i32, -1i32, Vec::<String>::new()))
+            .unwrap();
+        assert_eq!(revision, 1);
+        assert_eq!(root_id, 0);
+
+        let first: Layout = children[0].try_clone().unwrap().try_into().unwrap();
+        let label: String = first.1["label"].try_clone().unwrap().try_into().unwrap();
+        assert_eq!(label, "hello world");
+
+        // 点击第一个条目
+        let _: () = menu
+            .call(
+                "Event",
+                &(first.0, "clicked", zbus::zvariant::Value::from(0i32), 0u32),
+            )
+            .unwrap();
+        assert_eq!(receiver.recv().unwrap(), TrayEvent::CopyItem(42));
+
+        let item = zbus::blocking::Proxy::new(
+            &client,
+            name.as_str(),
+            ITEM_PATH,
+            "org.kde.StatusNotifierItem",
+        )
+        .unwrap();
+        let _: () = item.call("Activate", &(0i32, 0i32)).unwrap();
+        assert_eq!(receiver.recv().unwrap(), TrayEvent::OpenWindow);
+    }
+
+    #[test]
+    fn test_summarize() {
+        assert_eq!(summarize("  a\n b  ", 10), "a b");
+        assert_eq!(summarize("abcdef", 3), "abc…");
+    }
+}
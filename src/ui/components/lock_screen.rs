@@ -0,0 +1,109 @@
+use crate::i18n::{self, TextKey};
+use eframe::egui;
+
+/// 窗口锁定时代替主界面显示，输入口令解锁
+#[derive(Default)]
+pub struct LockScreen {
+    passphrase: String,
+    /// 上次输入的口令错误
+    failed: bool,
+    /// 配置中的口令哈希无效，无法解锁
+    error: Option<String>,
+    /// 正在后台校验口令
+    verifying: bool,
+    request_focus: bool,
+}
+
+impl LockScreen {
+    pub fn new() -> Self {
+        Self {
+            request_focus: true,
+            ..Self::default()
+        }
+    }
+
+    /// 重新锁定时清空输入
+    pub fn reset(&mut self) {
+        self.passphrase.clear();
+        self.failed = false;
+        self.error = None;
+        self.verifying = false;
+        self.request_focus = true;
+    }
+
+    pub fn set_failed(&mut self) {
+        self.passphrase.clear();
+        self.failed = true;
+        self.request_focus = true;
+    }
+
+    pub fn set_error(&mut self, message: String) {
+        self.passphrase.clear();
+        self.error = Some(message);
+        self.request_focus = true;
+    }
+
+    pub fn set_verifying(&mut self, verifying: bool) {
+        self.verifying = verifying;
+    }
+
+    /// 返回用户提交的口令
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<String> {
+        let mut submitted = None;
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(ui.available_height() / 3.0);
+                ui.heading("🔒");
+                ui.heading(i18n::t(TextKey::AppLocked));
+                ui.add_space(10.0);
+
+                let response = ui.add_enabled(
+                    !self.verifying,
+                    egui::TextEdit::singleline(&mut self.passphrase)
+                        .password(true)
+                        .hint_text(i18n::t(TextKey::Passphrase))
+                        .desired_width(220.0),
+                );
+                if std::mem::take(&mut self.request_focus) {
+                    response.request_focus();
+                }
+                let entered =
+                    response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if response.changed() {
+                    self.failed = false;
+                    self.error = None;
+                }
+
+                ui.add_space(5.0);
+                let can_submit = !self.verifying && !self.passphrase.is_empty();
+                let clicked = ui
+                    .add_enabled(can_submit, egui::Button::new(i18n::t(TextKey::Unlock)))
+                    .clicked();
+                if (entered || clicked) && can_submit {
+                    submitted = Some(std::mem::take(&mut self.passphrase));
+                    self.failed = false;
+                    self.error = None;
+                }
+
+                if self.verifying {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(i18n::t(TextKey::VerifyingPassphrase));
+                    });
+                } else if let Some(error) = &self.error {
+                    ui.colored_label(
+                        ui.visuals().error_fg_color,
+                        i18n::t(TextKey::LockMisconfigured),
+                    );
+                    ui.label(egui::RichText::new(error).small().weak());
+                } else if self.failed {
+                    ui.colored_label(
+                        ui.visuals().error_fg_color,
+                        i18n::t(TextKey::WrongPassphrase),
+                    );
+                }
+            });
+        });
+        submitted
+    }
+}
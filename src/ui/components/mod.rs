@@ -1,4 +1,12 @@
 // UI 组件模块
+pub mod lock_screen;
+pub mod preview_panel;
 pub mod settings_window;
+pub mod snippet_form;
+pub mod snippets_window;
 
+pub use lock_screen::LockScreen;
+pub use preview_panel::PreviewPanel;
 pub use settings_window::SettingsWindow;
+pub use snippet_form::SnippetForm;
+pub use snippets_window::SnippetsWindow;
@@ -0,0 +1,319 @@
+use crate::clipboard::types::{ClipboardContent, ClipboardItem, ImageData};
+use crate::i18n::{self, TextKey};
+use crate::ui::thumbnail_cache::{ThumbnailCache, FULL_SIZE_MEMORY_BUDGET};
+use eframe::egui;
+use std::ops::Range;
+
+const MIN_ZOOM: f32 = 0.1;
+const MAX_ZOOM: f32 = 8.0;
+
+/// 选中条目的完整预览面板
+pub struct PreviewPanel {
+    wrap_text: bool,
+    show_line_numbers: bool,
+    zoom: f32,
+    /// 全尺寸图片在后台解码，首次显示图片时创建
+    images: Option<ThumbnailCache>,
+    /// 当前显示的图片，切换图片时重置缩放
+    current_image: Option<String>,
+    /// 当前文本每一行的字节范围，按内容哈希缓存
+    line_index: (String, Vec<Range<usize>>),
+}
+
+impl Default for PreviewPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PreviewPanel {
+    pub fn new() -> Self {
+        Self {
+            wrap_text: true,
+            show_line_numbers: true,
+            zoom: 1.0,
+            images: None,
+            current_image: None,
+            line_index: (String::new(), Vec::new()),
+        }
+    }
+
+    /// 显示条目；敏感条目在 `revealed` 为 false 时隐藏内容。
+    /// 返回 true 表示用户切换了显示状态
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        item: Option<&ClipboardItem>,
+        revealed: bool,
+    ) -> bool {
+        ui.heading(i18n::t(TextKey::Preview));
+        ui.separator();
+
+        let Some(item) = item else {
+            ui.centered_and_justified(|ui| {
+                ui.label(i18n::t(TextKey::NoSelection));
+            });
+            return false;
+        };
+
+        if let ClipboardContent::Text(text) = &item.content {
+            self.index_lines(&item.content_hash, text);
+        }
+        Self::show_metadata(ui, item, self.line_index.1.len());
+        ui.separator();
+
+        let mut toggled = false;
+        if item.sensitive.is_some() {
+            ui.horizontal(|ui| {
+                let text = if revealed {
+                    TextKey::HideSensitive
+                } else {
+                    TextKey::RevealSensitive
+                };
+                toggled = ui.button(i18n::t(text)).clicked();
+                if !revealed {
+                    ui.label(i18n::t(TextKey::SensitiveHidden));
+                }
+            });
+            if !revealed {
+                return toggled;
+            }
+        }
+
+        match &item.content {
+            ClipboardContent::Text(text) => self.show_text(ui, text),
+            ClipboardContent::Image(image_data) => {
+                self.show_image(ui, &item.content_hash, image_data)
+            }
+        }
+        toggled
+    }
+
+    /// 选中条目变化时重新计算行范围，避免每帧遍历整段文本
+    fn index_lines(&mut self, content_hash: &str, text: &str) {
+        if self.line_index.0 == content_hash {
+            return;
+        }
+        let lines = text
+            .lines()
+            .map(|line| {
+                let start = line.as_ptr() as usize - text.as_ptr() as usize;
+                start..start + line.len()
+            })
+            .collect();
+        self.line_index = (content_hash.to_string(), lines);
+    }
+
+    fn show_metadata(ui: &mut egui::Ui, item: &ClipboardItem, line_count: usize) {
+        egui::Grid::new("preview_metadata")
+            .num_columns(2)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("Size:");
+                ui.label(format_size(item.content_size));
+                ui.end_row();
+
+                match &item.content {
+                    ClipboardContent::Text(text) => {
+                        ui.label("Lines:");
+                        ui.label(format!(
+                            "{} ({} {})",
+                            line_count,
+                            text.chars().count(),
+                            i18n::t(TextKey::CharactersCount)
+                        ));
+                        ui.end_row();
+                    }
+                    ClipboardContent::Image(image_data) => {
+                        ui.label("Dimensions:");
+                        ui.label(format!(
+                            "{} × {} ({})",
+                            image_data.width,
+                            image_data.height,
+                            image_data.format.file_extension().to_uppercase()
+                        ));
+                        ui.end_row();
+                    }
+                }
+
+                let time_format = "%Y-%m-%d %H:%M:%S";
+                ui.label("Created:");
+                ui.label(
+                    item.created_at
+                        .with_timezone(&chrono::Local)
+                        .format(time_format)
+                        .to_string(),
+                );
+                ui.end_row();
+
+                ui.label("Last used:");
+                ui.label(
+                    item.accessed_at
+                        .with_timezone(&chrono::Local)
+                        .format(time_format)
+                        .to_string(),
+                );
+                ui.end_row();
+
+                ui.label("Access count:");
+                ui.label(item.access_count.to_string());
+                ui.end_row();
+
+                if let Some(source) = &item.source {
+                    ui.label("Source:");
+                    let name = source.name.as_deref().unwrap_or("unknown");
+                    match source.pid {
+                        Some(pid) => ui.label(format!("{} (PID {})", name, pid)),
+                        None => ui.label(name),
+                    };
+                    ui.end_row();
+                }
+
+                if let Some(label) = &item.sensitive {
+                    ui.label("Sensitive:");
+                    ui.label(format!("🔒 {}", label));
+                    ui.end_row();
+                }
+
+                if let Some(expires_at) = item.expires_at {
+                    ui.label("Expires:");
+                    ui.label(
+                        expires_at
+                            .with_timezone(&chrono::Local)
+                            .format(time_format)
+                            .to_string(),
+                    );
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn show_text(&mut self, ui: &mut egui::Ui, text: &str) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.wrap_text, "Wrap");
+            ui.checkbox(&mut self.show_line_numbers, "Line numbers");
+        });
+
+        let lines = &self.line_index.1;
+        let number_width = lines.len().max(1).to_string().len();
+        let font = egui::TextStyle::Monospace.resolve(ui.style());
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+        let wrap_text = self.wrap_text;
+        let show_line_numbers = self.show_line_numbers;
+
+        let scroll = if wrap_text {
+            egui::ScrollArea::vertical()
+        } else {
+            egui::ScrollArea::both()
+        };
+
+        // 只布局可见的行；折行的行比估计的行高更高，只影响滚动条的精度
+        scroll
+            .id_salt("preview_text")
+            .auto_shrink([false; 2])
+            .show_rows(ui, row_height, lines.len(), |ui, rows| {
+                ui.spacing_mut().item_spacing.y = 0.0;
+                for number in rows {
+                    let line = &text[lines[number].clone()];
+                    ui.horizontal(|ui| {
+                        if show_line_numbers {
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "{:>width$}",
+                                    number + 1,
+                                    width = number_width
+                                ))
+                                .font(font.clone())
+                                .weak(),
+                            );
+                        }
+                        let label = egui::Label::new(egui::RichText::new(line).font(font.clone()));
+                        ui.add(if wrap_text {
+                            label.wrap()
+                        } else {
+                            label.extend()
+                        });
+                    });
+                }
+            });
+    }
+
+    fn show_image(&mut self, ui: &mut egui::Ui, content_hash: &str, image_data: &ImageData) {
+        if self.current_image.as_deref() != Some(content_hash) {
+            self.current_image = Some(content_hash.to_string());
+            self.zoom = 1.0;
+        }
+
+        // 全尺寸图片在后台解码，完成前显示占位
+        let images = self.images.get_or_insert_with(|| {
+            ThumbnailCache::full_size(ui.ctx().clone(), FULL_SIZE_MEMORY_BUDGET)
+        });
+        let Some(texture) = images.get(ui.ctx(), content_hash, image_data) else {
+            if images.is_failed(content_hash) {
+                ui.label("🖼️ Unable to decode image");
+            } else {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Loading image…");
+                });
+            }
+            return;
+        };
+
+        let available_width = ui.available_width();
+        ui.horizontal(|ui| {
+            if ui.button("➖").clicked() {
+                self.zoom = (self.zoom / 1.25).max(MIN_ZOOM);
+            }
+            ui.label(format!("{:.0}%", self.zoom * 100.0));
+            if ui.button("➕").clicked() {
+                self.zoom = (self.zoom * 1.25).min(MAX_ZOOM);
+            }
+            if ui.button("1:1").clicked() {
+                self.zoom = 1.0;
+            }
+            if ui.button("Fit").clicked() {
+                let size = texture.size_vec2();
+                self.zoom = (available_width / size.x).clamp(MIN_ZOOM, MAX_ZOOM);
+            }
+        });
+
+        let output = egui::ScrollArea::both()
+            .id_salt("preview_image")
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                // 拖动平移由 ScrollArea 处理
+                ui.add(
+                    egui::Image::from_texture(&texture)
+                        .fit_to_exact_size(texture.size_vec2() * self.zoom),
+                )
+            });
+
+        // Ctrl + 滚轮缩放
+        let hovered = ui
+            .input(|i| i.pointer.hover_pos())
+            .is_some_and(|pos| output.inner_rect.contains(pos));
+        if hovered {
+            let zoom_delta = ui.input(|i| i.zoom_delta());
+            if zoom_delta != 1.0 {
+                self.zoom = (self.zoom * zoom_delta).clamp(MIN_ZOOM, MAX_ZOOM);
+            }
+        }
+    }
+}
+
+/// 以人类可读的形式格式化字节数
+pub fn format_size(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
@@ -1,11 +1,42 @@
-use crate::config::settings::{AppConfig, ThemeMode};
+use crate::config::actions::{ActionsConfig, CommandAction};
+use crate::config::capture::{CaptureConfig, CaptureRule, RuleField, RuleKind};
+use crate::config::paste::{PasteConfig, PasteMethod, PasteOverride};
+use crate::config::sensitive::{SensitiveAction, SensitiveConfig, SensitivePattern};
+use crate::config::settings::{AppConfig, ExpiryConfig, LockConfig, ThemeMode};
+use crate::hotkey::{self, HotkeyBackend};
 use crate::i18n::{self, TextKey};
+use crate::sensitive::SensitiveDetector;
+use chrono::{DateTime, Local};
 use eframe::egui;
+use std::collections::VecDeque;
+
+/// 设置中保留的拦截记录数
+const RECENT_BLOCKS: usize = 10;
+
+/// 一次被捕获规则拦截的记录
+struct BlockedCapture {
+    rule: String,
+    app: Option<String>,
+    at: DateTime<Local>,
+}
+
+/// 设置口令时输入的内容，保存前只保留哈希
+#[derive(Default)]
+struct PassphraseDraft {
+    new: String,
+    confirm: String,
+    error: Option<String>,
+}
 
 pub struct SettingsWindow {
     pub open: bool,
     config: AppConfig,
     temp_config: AppConfig,
+    /// 全局快捷键的注册结果
+    hotkey_backend: Option<HotkeyBackend>,
+    /// 最近被捕获规则拦截的内容，最新的在前
+    recent_blocks: VecDeque<BlockedCapture>,
+    passphrase: PassphraseDraft,
 }
 
 impl SettingsWindow {
@@ -14,6 +45,9 @@ impl SettingsWindow {
             open: false,
             temp_config: config.clone(),
             config,
+            hotkey_backend: None,
+            recent_blocks: VecDeque::new(),
+            passphrase: PassphraseDraft::default(),
         }
     }
 
@@ -27,6 +61,20 @@ impl SettingsWindow {
             .resizable(true)
             .show(ctx, |ui| {
                 egui::ScrollArea::vertical().show(ui, |ui| {
+                    let hotkey_valid = hotkey::validate(&self.temp_config.hotkey);
+                    let paste_valid = self.temp_config.paste.validate();
+                    let sensitive_valid = self
+                        .temp_config
+                        .sensitive
+                        .custom_patterns
+                        .iter()
+                        .try_for_each(|pattern| {
+                            SensitiveDetector::validate_pattern(&pattern.pattern)
+                                .map_err(|e| format!("Pattern '{}': {}", pattern.name, e))
+                        });
+                    let capture_valid = self.temp_config.capture.validate();
+                    let actions_valid = self.temp_config.actions.validate();
+
                     // General Settings
                     ui.heading("General Settings");
                     ui.separator();
@@ -54,10 +102,41 @@ impl SettingsWindow {
                     });
 
                     ui.checkbox(&mut self.temp_config.auto_start, "Auto start with system");
+                    ui.checkbox(
+                        &mut self.temp_config.private_mode.persist,
+                        "Keep private mode across restarts",
+                    )
+                    .on_hover_text("Pauses chosen \"until restart\" always end on restart");
                     ui.checkbox(
                         &mut self.temp_config.show_notifications,
                         "Show notifications",
                     );
+                    ui.add_enabled_ui(self.temp_config.show_notifications, |ui| {
+                        let notifications = &mut self.temp_config.notifications;
+                        ui.indent("notification_categories", |ui| {
+                            ui.checkbox(&mut notifications.item_copied, "Item copied");
+                            ui.checkbox(
+                                &mut notifications.recording_paused,
+                                "Recording paused or resumed",
+                            );
+                            ui.checkbox(
+                                &mut notifications.sensitive_skipped,
+                                "Sensitive item skipped",
+                            );
+                            ui.checkbox(
+                                &mut notifications.storage_failed,
+                                "Saving or backup failed",
+                            );
+                            ui.horizontal(|ui| {
+                                ui.label("Minimum interval per type:");
+                                ui.add(
+                                    egui::DragValue::new(&mut notifications.throttle_secs)
+                                        .range(0..=3600)
+                                        .suffix("s"),
+                                );
+                            });
+                        });
+                    });
 
                     ui.add_space(10.0);
 
@@ -99,6 +178,26 @@ impl SettingsWindow {
                         ui.text_edit_singleline(&mut self.temp_config.hotkey);
                     });
 
+                    if let Err(e) = &hotkey_valid {
+                        ui.colored_label(ui.visuals().error_fg_color, e);
+                    } else if let Some(HotkeyBackend::IpcFallback { reason }) = &self.hotkey_backend
+                    {
+                        ui.colored_label(ui.visuals().warn_fg_color, reason);
+                        ui.label(
+                            "Bind the command `clipmanager toggle` in your desktop's keyboard shortcut settings instead.",
+                        );
+                    }
+
+                    ui.add_space(10.0);
+
+                    // Paste Settings
+                    ui.heading("Paste Settings");
+                    ui.separator();
+                    Self::show_paste_settings(ui, &mut self.temp_config.paste);
+                    if let Err(e) = &paste_valid {
+                        ui.colored_label(ui.visuals().error_fg_color, e);
+                    }
+
                     ui.add_space(10.0);
 
                     // Font Settings
@@ -160,11 +259,138 @@ impl SettingsWindow {
                         "Start minimized",
                     );
 
+                    ui.checkbox(
+                        &mut self.temp_config.tray.enabled,
+                        "Show tray icon (closing the window hides it to the tray)",
+                    );
+                    ui.horizontal(|ui| {
+                        ui.label("Recent items in tray menu:");
+                        ui.add(
+                            egui::DragValue::new(&mut self.temp_config.tray.recent_items)
+                                .range(0..=30),
+                        );
+                    });
+
+                    ui.add_space(10.0);
+
+                    // Popup Settings
+                    ui.heading("Popup Picker");
+                    ui.separator();
+
+                    let popup = &mut self.temp_config.popup;
+                    ui.checkbox(
+                        &mut popup.enabled,
+                        "Global hotkey opens the compact popup instead of the main window",
+                    );
+                    ui.horizontal(|ui| {
+                        ui.label("Items shown:");
+                        ui.add(egui::DragValue::new(&mut popup.max_items).range(5..=100));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Popup size:");
+                        ui.add(egui::DragValue::new(&mut popup.width).range(240.0..=1000.0));
+                        ui.label("×");
+                        ui.add(egui::DragValue::new(&mut popup.height).range(200.0..=1000.0));
+                    });
+
+                    ui.add_space(10.0);
+
+                    // Sensitive Content
+                    ui.heading("Sensitive Content");
+                    ui.separator();
+                    Self::show_sensitive_settings(ui, &mut self.temp_config.sensitive);
+                    if let Err(e) = &sensitive_valid {
+                        ui.colored_label(ui.visuals().error_fg_color, e);
+                    }
+
+                    ui.add_space(10.0);
+
+                    // Expiry
+                    ui.heading("Expiry");
+                    ui.separator();
+                    Self::show_expiry_settings(ui, &mut self.temp_config.expiry);
+
+                    ui.add_space(10.0);
+
+                    // App Lock
+                    ui.heading("App Lock");
+                    ui.separator();
+                    Self::show_lock_settings(ui, &mut self.temp_config.lock, &mut self.passphrase);
+
+                    ui.add_space(10.0);
+
+                    // Capture Rules
+                    ui.heading("Capture Rules");
+                    ui.separator();
+                    Self::show_capture_rules(ui, &mut self.temp_config.capture);
+                    if let Err(e) = &capture_valid {
+                        ui.colored_label(ui.visuals().error_fg_color, e);
+                    }
+                    Self::show_recent_blocks(ui, &self.recent_blocks);
+
+                    ui.add_space(10.0);
+
+                    // Actions
+                    ui.heading("Actions");
+                    ui.separator();
+                    Self::show_actions(ui, &mut self.temp_config.actions);
+                    if let Err(e) = &actions_valid {
+                        ui.colored_label(ui.visuals().error_fg_color, e);
+                    }
+
+                    ui.add_space(10.0);
+
+                    // HTTP API
+                    ui.heading("Local HTTP API");
+                    ui.separator();
+
+                    let http = &mut self.temp_config.http;
+                    ui.checkbox(&mut http.enabled, "Serve the REST API on 127.0.0.1");
+                    ui.add_enabled_ui(http.enabled, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Port:");
+                            ui.add(egui::DragValue::new(&mut http.port).range(1024..=65535));
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Allowed web origin:");
+                            let mut origin = http.allowed_origin.clone().unwrap_or_default();
+                            if ui
+                                .add(
+                                    egui::TextEdit::singleline(&mut origin)
+                                        .hint_text("https://example.com"),
+                                )
+                                .changed()
+                            {
+                                http.allowed_origin = (!origin.is_empty()).then_some(origin);
+                            }
+                        });
+                        #[cfg(unix)]
+                        if let Ok(path) = crate::ipc::http::token_path() {
+                            ui.label(format!("Bearer token: {}", path.display()));
+                        }
+                    });
+
                     ui.add_space(20.0);
 
                     // Buttons
                     ui.horizontal(|ui| {
-                        if ui.button("Save").clicked() {
+                        if ui
+                            .add_enabled(
+                                hotkey_valid.is_ok()
+                                    && paste_valid.is_ok()
+                                    && sensitive_valid.is_ok()
+                                    && capture_valid.is_ok()
+                                    && actions_valid.is_ok(),
+                                egui::Button::new("Save"),
+                            )
+                            .clicked()
+                        {
+                            // 编辑参数时留下的末尾空行不作为参数
+                            for action in &mut self.temp_config.actions.actions {
+                                while action.args.last().is_some_and(|arg| arg.is_empty()) {
+                                    action.args.pop();
+                                }
+                            }
                             self.config = self.temp_config.clone();
                             result = Some(self.config.clone());
                             should_close = true;
@@ -176,7 +402,14 @@ impl SettingsWindow {
                         }
 
                         if ui.button("Reset to Default").clicked() {
-                            self.temp_config = AppConfig::default();
+                            // 口令不随其他设置重置
+                            self.temp_config = AppConfig {
+                                lock: LockConfig {
+                                    passphrase_hash: self.temp_config.lock.passphrase_hash.take(),
+                                    ..LockConfig::default()
+                                },
+                                ..AppConfig::default()
+                            };
                         }
                     });
                 });
@@ -189,15 +422,408 @@ impl SettingsWindow {
         result
     }
 
+    fn show_paste_settings(ui: &mut egui::Ui, paste: &mut PasteConfig) {
+        ui.checkbox(
+            &mut paste.paste_on_select,
+            "Paste into the previous window after selecting an item",
+        );
+
+        ui.add_enabled_ui(paste.paste_on_select, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Method:");
+                egui::ComboBox::from_id_salt("paste_method")
+                    .selected_text(format!("{:?}", paste.method))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut paste.method, PasteMethod::Auto, "Auto");
+                        ui.selectable_value(&mut paste.method, PasteMethod::XTest, "XTest");
+                        ui.selectable_value(&mut paste.method, PasteMethod::Command, "Command");
+                    });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Paste command:");
+                ui.text_edit_singleline(&mut paste.command)
+                    .on_hover_text("{keys} is replaced with e.g. ctrl+v");
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Paste keystroke:");
+                ui.text_edit_singleline(&mut paste.keystroke);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Delay (ms):");
+                ui.add(egui::DragValue::new(&mut paste.delay_ms).range(0..=2000));
+            });
+
+            ui.label("Per-application keystrokes (WM_CLASS):");
+            let mut remove = None;
+            for (index, rule) in paste.app_overrides.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut rule.app).desired_width(140.0));
+                    ui.add(egui::TextEdit::singleline(&mut rule.keystroke).desired_width(100.0));
+                    if ui.small_button("✖").clicked() {
+                        remove = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = remove {
+                paste.app_overrides.remove(index);
+            }
+            if ui.button("Add application").clicked() {
+                paste.app_overrides.push(PasteOverride {
+                    app: String::new(),
+                    keystroke: "Ctrl+Shift+V".to_string(),
+                });
+            }
+        });
+    }
+
+    fn show_sensitive_settings(ui: &mut egui::Ui, sensitive: &mut SensitiveConfig) {
+        ui.horizontal(|ui| {
+            ui.label("Content marked secret by password managers:");
+            Self::action_combo(ui, "password_managers", &mut sensitive.password_managers);
+        });
+
+        ui.checkbox(
+            &mut sensitive.enabled,
+            "Detect passwords, keys and card numbers before saving",
+        );
+
+        ui.add_enabled_ui(sensitive.enabled, |ui| {
+            egui::Grid::new("sensitive_actions")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for (label, action) in [
+                        ("Private keys:", &mut sensitive.private_keys),
+                        ("API keys and tokens:", &mut sensitive.api_keys),
+                        ("JWTs:", &mut sensitive.jwts),
+                        ("Card numbers:", &mut sensitive.card_numbers),
+                        ("Passwords:", &mut sensitive.passwords),
+                    ] {
+                        ui.label(label);
+                        Self::action_combo(ui, label, action);
+                        ui.end_row();
+                    }
+                });
+
+            ui.horizontal(|ui| {
+                ui.label("Expire after:");
+                ui.add(
+                    egui::DragValue::new(&mut sensitive.expire_minutes)
+                        .range(1..=1440)
+                        .suffix(" min"),
+                );
+            });
+
+            ui.label("Custom patterns (name, regular expression, action):");
+            let mut remove = None;
+            for (index, pattern) in sensitive.custom_patterns.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut pattern.name).desired_width(90.0));
+                    ui.add(egui::TextEdit::singleline(&mut pattern.pattern).desired_width(150.0));
+                    Self::action_combo(ui, index, &mut pattern.action);
+                    if ui.small_button("✖").clicked() {
+                        remove = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = remove {
+                sensitive.custom_patterns.remove(index);
+            }
+            if ui.button("Add pattern").clicked() {
+                sensitive.custom_patterns.push(SensitivePattern {
+                    name: "custom".to_string(),
+                    pattern: String::new(),
+                    action: SensitiveAction::Mask,
+                });
+            }
+        });
+    }
+
+    fn show_capture_rules(ui: &mut egui::Ui, capture: &mut CaptureConfig) {
+        ui.label(
+            "Deny rules block matching captures. When any allow rule is enabled, \
+             only captures matching an allow rule are recorded. Expire rules record \
+             matching captures but delete them after the given time.",
+        );
+
+        let mut remove = None;
+        for (index, rule) in capture.rules.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut rule.enabled, "");
+                ui.add(egui::TextEdit::singleline(&mut rule.name).desired_width(90.0));
+                egui::ComboBox::from_id_salt(("capture_kind", index))
+                    .selected_text(format!("{:?}", rule.kind))
+                    .width(60.0)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut rule.kind, RuleKind::Deny, "Deny");
+                        ui.selectable_value(&mut rule.kind, RuleKind::Allow, "Allow");
+                        ui.selectable_value(&mut rule.kind, RuleKind::Expire, "Expire");
+                    });
+                egui::ComboBox::from_id_salt(("capture_field", index))
+                    .selected_text(format!("{:?}", rule.field))
+                    .width(70.0)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut rule.field, RuleField::App, "App");
+                        ui.selectable_value(&mut rule.field, RuleField::Title, "Title");
+                        ui.selectable_value(&mut rule.field, RuleField::Content, "Content");
+                    });
+                ui.add(egui::TextEdit::singleline(&mut rule.pattern).desired_width(120.0))
+                    .on_hover_text("Regular expression; app and title match ignore case");
+                if rule.kind == RuleKind::Expire {
+                    ui.add(
+                        egui::DragValue::new(&mut rule.ttl_secs)
+                            .range(1..=604_800)
+                            .suffix(" s"),
+                    );
+                }
+                if ui.small_button("✖").clicked() {
+                    remove = Some(index);
+                }
+            });
+        }
+        if let Some(index) = remove {
+            capture.rules.remove(index);
+        }
+        if ui.button("Add rule").clicked() {
+            capture.rules.push(CaptureRule {
+                name: "rule".to_string(),
+                kind: RuleKind::Deny,
+                field: RuleField::App,
+                pattern: String::new(),
+                enabled: true,
+                ttl_secs: 60,
+            });
+        }
+    }
+
+    fn show_actions(ui: &mut egui::Ui, actions: &mut ActionsConfig) {
+        ui.label(
+            "Run an external command on a text item: the item is written to its stdin \
+             and its stdout becomes the result. Commands run directly, not through a shell.",
+        );
+
+        let mut remove = None;
+        for (index, action) in actions.actions.iter_mut().enumerate() {
+            egui::CollapsingHeader::new(&action.name)
+                .id_salt(("action", index))
+                .show(ui, |ui| {
+                    egui::Grid::new(("action_grid", index))
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            ui.label("Name:");
+                            ui.text_edit_singleline(&mut action.name);
+                            ui.end_row();
+
+                            ui.label("Command:");
+                            ui.text_edit_singleline(&mut action.command);
+                            ui.end_row();
+
+                            ui.label("Arguments:");
+                            let mut args = action.args.join("\n");
+                            if ui
+                                .add(egui::TextEdit::multiline(&mut args).desired_rows(2))
+                                .on_hover_text("One argument per line")
+                                .changed()
+                            {
+                                action.args = args.split('\n').map(str::to_string).collect();
+                            }
+                            ui.end_row();
+
+                            ui.label("Timeout:");
+                            ui.add(
+                                egui::DragValue::new(&mut action.timeout_secs)
+                                    .range(1..=600)
+                                    .suffix(" s"),
+                            );
+                            ui.end_row();
+
+                            for (label, bytes) in [
+                                ("Max input (KB):", &mut action.max_input_bytes),
+                                ("Max output (KB):", &mut action.max_output_bytes),
+                            ] {
+                                ui.label(label);
+                                let mut kb = *bytes / 1024;
+                                if ui
+                                    .add(egui::DragValue::new(&mut kb).range(1..=102_400))
+                                    .changed()
+                                {
+                                    *bytes = kb * 1024;
+                                }
+                                ui.end_row();
+                            }
+
+                            ui.label("Shortcut:");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut action.shortcut)
+                                    .hint_text("e.g. Ctrl+Shift+U"),
+                            );
+                            ui.end_row();
+
+                            ui.label("Run on capture:");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut action.auto_pattern)
+                                    .hint_text("Regular expression"),
+                            )
+                            .on_hover_text(
+                                "Captured text matching this expression is replaced \
+                                 by the command's output",
+                            );
+                            ui.end_row();
+                        });
+                    if ui.button("Remove action").clicked() {
+                        remove = Some(index);
+                    }
+                });
+        }
+        if let Some(index) = remove {
+            actions.actions.remove(index);
+        }
+        if ui.button("Add action").clicked() {
+            actions.actions.push(CommandAction::new("action", ""));
+        }
+    }
+
+    fn show_expiry_settings(ui: &mut egui::Ui, expiry: &mut ExpiryConfig) {
+        ui.label(
+            "Delete items automatically after a while (0 keeps them). Favorites never expire.",
+        );
+        egui::Grid::new("expiry_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (label, minutes) in [
+                    ("Text items:", &mut expiry.text_minutes),
+                    ("Images:", &mut expiry.image_minutes),
+                ] {
+                    ui.label(label);
+                    ui.add(
+                        egui::DragValue::new(minutes)
+                            .range(0..=525_600)
+                            .suffix(" min"),
+                    );
+                    ui.end_row();
+                }
+            });
+        ui.checkbox(
+            &mut expiry.clear_clipboard,
+            "Clear the system clipboard when its content expires",
+        );
+    }
+
+    fn show_lock_settings(ui: &mut egui::Ui, lock: &mut LockConfig, draft: &mut PassphraseDraft) {
+        ui.label(
+            "Require a passphrase to view or search the history. Recording continues while locked.",
+        );
+        egui::Grid::new("passphrase_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("New passphrase:");
+                ui.add(egui::TextEdit::singleline(&mut draft.new).password(true));
+                ui.end_row();
+                ui.label("Confirm:");
+                ui.add(egui::TextEdit::singleline(&mut draft.confirm).password(true));
+                ui.end_row();
+            });
+
+        ui.horizontal(|ui| {
+            let label = if lock.is_enabled() {
+                "Change passphrase"
+            } else {
+                "Set passphrase"
+            };
+            if ui
+                .add_enabled(!draft.new.is_empty(), egui::Button::new(label))
+                .clicked()
+            {
+                if draft.new != draft.confirm {
+                    draft.error = Some("Passphrases do not match".to_string());
+                } else {
+                    match crate::lock::hash_passphrase(&draft.new) {
+                        Ok(hash) => {
+                            lock.passphrase_hash = Some(hash);
+                            *draft = PassphraseDraft::default();
+                        }
+                        Err(e) => draft.error = Some(e.to_string()),
+                    }
+                }
+            }
+            if lock.is_enabled() && ui.button("Remove passphrase").clicked() {
+                lock.passphrase_hash = None;
+            }
+        });
+        if let Some(error) = &draft.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        } else if lock.is_enabled() {
+            ui.label("A passphrase is set; changes apply when you save.");
+        }
+
+        ui.add_enabled_ui(lock.is_enabled(), |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Lock after idle:");
+                ui.add(
+                    egui::DragValue::new(&mut lock.idle_minutes)
+                        .range(0..=1440)
+                        .suffix(" min"),
+                );
+                ui.label("(0 = never)");
+            });
+            ui.checkbox(&mut lock.lock_with_screen, "Lock when the screen locks");
+        });
+    }
+
+    fn show_recent_blocks(ui: &mut egui::Ui, recent_blocks: &VecDeque<BlockedCapture>) {
+        if recent_blocks.is_empty() {
+            return;
+        }
+        ui.label("Recently blocked:");
+        ui.indent("recent_blocks", |ui| {
+            for block in recent_blocks {
+                ui.label(format!(
+                    "{}  {} — rule '{}'",
+                    block.at.format("%H:%M:%S"),
+                    block.app.as_deref().unwrap_or("unknown application"),
+                    block.rule
+                ));
+            }
+        });
+    }
+
+    /// 记录一次被捕获规则拦截的内容
+    pub fn record_blocked(&mut self, rule: String, app: Option<String>) {
+        self.recent_blocks.push_front(BlockedCapture {
+            rule,
+            app,
+            at: Local::now(),
+        });
+        self.recent_blocks.truncate(RECENT_BLOCKS);
+    }
+
+    fn action_combo(ui: &mut egui::Ui, id: impl std::hash::Hash, action: &mut SensitiveAction) {
+        egui::ComboBox::from_id_salt(("sensitive_action", id))
+            .selected_text(format!("{:?}", action))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(action, SensitiveAction::Ignore, "Ignore");
+                ui.selectable_value(action, SensitiveAction::Expire, "Expire");
+                ui.selectable_value(action, SensitiveAction::Mask, "Mask");
+                ui.selectable_value(action, SensitiveAction::Skip, "Skip");
+            });
+    }
+
     pub fn open(&mut self) {
         self.open = true;
         self.temp_config = self.config.clone();
+        self.passphrase = PassphraseDraft::default();
     }
 
     pub fn is_open(&self) -> bool {
         self.open
     }
 
+    pub fn set_hotkey_backend(&mut self, backend: HotkeyBackend) {
+        self.hotkey_backend = Some(backend);
+    }
+
     pub fn update_config(&mut self, config: AppConfig) {
         self.config = config;
         if !self.open {
@@ -0,0 +1,88 @@
+use crate::i18n::{self, TextKey};
+use crate::snippets::template::Field;
+use crate::snippets::Snippet;
+use eframe::egui;
+use std::collections::HashMap;
+
+/// 填写字段的结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormOutcome {
+    Submit(i64, HashMap<String, String>),
+    Cancel,
+}
+
+/// 使用带 `{field:...}` 占位符的片段前填写字段
+pub struct SnippetForm {
+    id: i64,
+    title: String,
+    fields: Vec<(Field, String)>,
+    request_focus: bool,
+}
+
+impl SnippetForm {
+    /// 片段没有需要填写的字段时返回 None
+    pub fn new(snippet: &Snippet) -> Option<Self> {
+        let fields = snippet.template().fields();
+        if fields.is_empty() {
+            return None;
+        }
+        Some(Self {
+            id: snippet.id?,
+            title: snippet.path(),
+            fields: fields
+                .into_iter()
+                .map(|field| {
+                    let value = field.default.clone();
+                    (field, value)
+                })
+                .collect(),
+            request_focus: true,
+        })
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Enter 提交，Escape 取消
+    pub fn show(&mut self, ui: &mut egui::Ui) -> Option<FormOutcome> {
+        let mut outcome = None;
+        egui::Grid::new(("snippet_form", self.id))
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (index, (field, value)) in self.fields.iter_mut().enumerate() {
+                    ui.label(format!("{}:", field.name));
+                    let response = ui.add(
+                        egui::TextEdit::singleline(value)
+                            .hint_text(&field.default)
+                            .desired_width(220.0),
+                    );
+                    if index == 0 && std::mem::take(&mut self.request_focus) {
+                        response.request_focus();
+                    }
+                    ui.end_row();
+                }
+            });
+
+        let (enter, escape) = ui.input(|i| {
+            (
+                i.key_pressed(egui::Key::Enter),
+                i.key_pressed(egui::Key::Escape),
+            )
+        });
+        ui.horizontal(|ui| {
+            if ui.button(i18n::t(TextKey::SnippetInsert)).clicked() || enter {
+                let values = self
+                    .fields
+                    .iter()
+                    .map(|(field, value)| (field.name.clone(), value.clone()))
+                    .collect();
+                outcome = Some(FormOutcome::Submit(self.id, values));
+            }
+            if ui.button(i18n::t(TextKey::Cancel)).clicked() || escape {
+                outcome = Some(FormOutcome::Cancel);
+            }
+        });
+        outcome
+    }
+}
@@ -0,0 +1,259 @@
+use crate::i18n::{self, TextKey};
+use crate::snippets::Snippet;
+use eframe::egui;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// 片段管理窗口返回给应用层的操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnippetsAction {
+    /// 保存片段，没有 ID 时新建
+    Save(Snippet),
+    Delete(i64),
+    Import(PathBuf),
+    Export(PathBuf),
+}
+
+/// 浏览、编辑片段，并从文件导入或导出到文件
+pub struct SnippetsWindow {
+    open: bool,
+    snippets: Vec<Snippet>,
+    filter: String,
+    /// 正在编辑的片段，新片段没有 ID
+    draft: Option<Snippet>,
+    file_path: String,
+    message: Option<Result<String, String>>,
+}
+
+impl Default for SnippetsWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SnippetsWindow {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            snippets: Vec::new(),
+            filter: String::new(),
+            draft: None,
+            file_path: String::new(),
+            message: None,
+        }
+    }
+
+    pub fn open(&mut self, snippets: Vec<Snippet>) {
+        self.open = true;
+        self.message = None;
+        self.set_snippets(snippets);
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+        self.draft = None;
+    }
+
+    /// 片段变化后刷新列表，正在编辑的内容保持不变
+    pub fn set_snippets(&mut self, snippets: Vec<Snippet>) {
+        self.snippets = snippets;
+    }
+
+    /// 保存成功后记录新片段的 ID，后续保存改为更新
+    pub fn on_saved(&mut self, id: i64) {
+        if let Some(draft) = &mut self.draft {
+            draft.id = Some(id);
+        }
+        self.message = Some(Ok("Saved".to_string()));
+    }
+
+    pub fn on_deleted(&mut self) {
+        self.draft = None;
+        self.message = None;
+    }
+
+    /// 显示操作结果或错误
+    pub fn set_message(&mut self, message: Result<String, String>) {
+        self.message = Some(message);
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<SnippetsAction> {
+        if !self.open {
+            return None;
+        }
+        let mut action = None;
+        let mut open = self.open;
+        egui::Window::new(i18n::t(TextKey::Snippets))
+            .open(&mut open)
+            .default_size([640.0, 420.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("File:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.file_path)
+                            .hint_text("snippets.toml or snippets.json")
+                            .desired_width(260.0),
+                    );
+                    let path = self.file_path.trim();
+                    if ui
+                        .add_enabled(!path.is_empty(), egui::Button::new("Import"))
+                        .clicked()
+                    {
+                        action = Some(SnippetsAction::Import(PathBuf::from(path)));
+                    }
+                    if ui
+                        .add_enabled(!path.is_empty(), egui::Button::new("Export"))
+                        .clicked()
+                    {
+                        action = Some(SnippetsAction::Export(PathBuf::from(path)));
+                    }
+                });
+                match &self.message {
+                    Some(Ok(message)) => {
+                        ui.colored_label(egui::Color32::GREEN, message);
+                    }
+                    Some(Err(error)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                    }
+                    None => {}
+                }
+                ui.separator();
+
+                ui.columns(2, |columns| {
+                    self.show_list(&mut columns[0]);
+                    if let Some(editor_action) = self.show_editor(&mut columns[1]) {
+                        action = Some(editor_action);
+                    }
+                });
+            });
+        if !open {
+            self.close();
+        }
+        action
+    }
+
+    /// 按文件夹分组的片段列表
+    fn show_list(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.filter)
+                    .hint_text(i18n::t(TextKey::SearchPlaceholder))
+                    .desired_width(ui.available_width() - 60.0),
+            );
+            if ui.button("New").clicked() {
+                let mut snippet = Snippet::new("", "");
+                // 新片段默认放在当前编辑片段的文件夹中
+                if let Some(draft) = &self.draft {
+                    snippet.folder = draft.folder.clone();
+                }
+                self.draft = Some(snippet);
+                self.message = None;
+            }
+        });
+
+        let filter = self.filter.to_lowercase();
+        let mut folders: BTreeMap<&str, Vec<&Snippet>> = BTreeMap::new();
+        for snippet in &self.snippets {
+            let matches = filter.is_empty()
+                || snippet.path().to_lowercase().contains(&filter)
+                || snippet.abbreviation.to_lowercase().contains(&filter);
+            if matches {
+                folders.entry(&snippet.folder).or_default().push(snippet);
+            }
+        }
+
+        let mut selected = None;
+        egui::ScrollArea::vertical()
+            .id_salt("snippet_list")
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                if folders.is_empty() {
+                    ui.label(i18n::t(TextKey::NoRecords));
+                }
+                for (folder, snippets) in folders {
+                    let list = |ui: &mut egui::Ui| {
+                        for snippet in snippets {
+                            let current = self
+                                .draft
+                                .as_ref()
+                                .is_some_and(|draft| draft.id.is_some() && draft.id == snippet.id);
+                            let mut label = snippet.name.clone();
+                            if !snippet.abbreviation.is_empty() {
+                                label.push_str(&format!("  ({})", snippet.abbreviation));
+                            }
+                            if ui.selectable_label(current, label).clicked() {
+                                selected = Some(snippet.clone());
+                            }
+                        }
+                    };
+                    if folder.is_empty() {
+                        list(ui);
+                    } else {
+                        egui::CollapsingHeader::new(format!("📁 {}", folder))
+                            .default_open(true)
+                            .show(ui, list);
+                    }
+                }
+            });
+        if let Some(snippet) = selected {
+            self.draft = Some(snippet);
+            self.message = None;
+        }
+    }
+
+    fn show_editor(&mut self, ui: &mut egui::Ui) -> Option<SnippetsAction> {
+        let Some(draft) = &mut self.draft else {
+            ui.label("Select a snippet or create a new one.");
+            return None;
+        };
+        let mut action = None;
+
+        egui::Grid::new("snippet_editor")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Name:");
+                ui.text_edit_singleline(&mut draft.name);
+                ui.end_row();
+                ui.label("Abbreviation:");
+                ui.text_edit_singleline(&mut draft.abbreviation);
+                ui.end_row();
+                ui.label("Folder:");
+                ui.text_edit_singleline(&mut draft.folder);
+                ui.end_row();
+            });
+        ui.add(
+            egui::TextEdit::multiline(&mut draft.body)
+                .code_editor()
+                .desired_rows(8)
+                .desired_width(f32::INFINITY),
+        );
+        ui.small(
+            "Placeholders: {date} {time} {date:%d.%m.%Y} {clipboard} {cursor} \
+             {field:Name} {field:Name=default}; {{ is a literal {",
+        );
+
+        let valid = draft.validate();
+        if let Err(error) = &valid {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(valid.is_ok(), egui::Button::new("Save"))
+                .clicked()
+            {
+                action = Some(SnippetsAction::Save(draft.clone()));
+            }
+            if let Some(id) = draft.id {
+                if ui.button("Delete").clicked() {
+                    action = Some(SnippetsAction::Delete(id));
+                }
+            }
+        });
+        action
+    }
+}
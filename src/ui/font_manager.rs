@@ -155,11 +155,9 @@ impl FontManager {
         for ch in text.chars() {
             match ch {
                 // 拉丁字母
-                'A'..='Z' | 'a'..='z' | '0'..='9' => {
-                    if !has_latin {
-                        scripts.push(LanguageScript::Latin);
-                        has_latin = true;
-                    }
+                'A'..='Z' | 'a'..='z' | '0'..='9' if !has_latin => {
+                    scripts.push(LanguageScript::Latin);
+                    has_latin = true;
                 }
                 // 中日韩字符
                 '\u{4E00}'..='\u{9FFF}' |  // CJK统一汉字
@@ -167,26 +165,20 @@ impl FontManager {
                 '\u{20000}'..='\u{2A6DF}' | // CJK扩展B
                 '\u{3040}'..='\u{309F}' |  // 平假名
                 '\u{30A0}'..='\u{30FF}' |  // 片假名
-                '\u{AC00}'..='\u{D7AF}' => { // 韩文音节
-                    if !has_cjk {
-                        scripts.push(LanguageScript::CJK);
-                        has_cjk = true;
-                    }
+                '\u{AC00}'..='\u{D7AF}' if !has_cjk => { // 韩文音节
+                    scripts.push(LanguageScript::CJK);
+                    has_cjk = true;
                 }
                 // 阿拉伯文
                 '\u{0600}'..='\u{06FF}' |  // 阿拉伯文
-                '\u{0750}'..='\u{077F}' => { // 阿拉伯文补充
-                    if !has_arabic {
-                        scripts.push(LanguageScript::Arabic);
-                        has_arabic = true;
-                    }
+                '\u{0750}'..='\u{077F}' if !has_arabic => { // 阿拉伯文补充
+                    scripts.push(LanguageScript::Arabic);
+                    has_arabic = true;
                 }
                 // 西里尔字母（俄文等）
-                '\u{0400}'..='\u{04FF}' => {
-                    if !has_cyrillic {
-                        scripts.push(LanguageScript::Cyrillic);
-                        has_cyrillic = true;
-                    }
+                '\u{0400}'..='\u{04FF}' if !has_cyrillic => {
+                    scripts.push(LanguageScript::Cyrillic);
+                    has_cyrillic = true;
                 }
                 _ => {}
             }
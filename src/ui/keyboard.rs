@@ -0,0 +1,239 @@
+use crate::config::actions::CommandAction;
+use crate::config::keybindings::{Accelerator, KeyBindings};
+use eframe::egui;
+
+/// 键盘命令
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyCommand {
+    FocusSearch,
+    SelectNext,
+    SelectPrevious,
+    PageDown,
+    PageUp,
+    SelectFirst,
+    SelectLast,
+    CopyAndClose,
+    Delete,
+    ToggleFavorite,
+    /// 快速复制第 N 个条目（从 0 开始）
+    QuickPaste(usize),
+    /// 对选中条目运行第 N 个用户动作
+    RunAction(usize),
+}
+
+impl KeyCommand {
+    /// 在搜索框获得焦点时仍然生效的命令
+    fn works_while_typing(&self) -> bool {
+        matches!(
+            self,
+            KeyCommand::SelectNext
+                | KeyCommand::SelectPrevious
+                | KeyCommand::PageDown
+                | KeyCommand::PageUp
+                | KeyCommand::CopyAndClose
+        )
+    }
+}
+
+struct Binding {
+    accelerator: Accelerator,
+    shortcut: egui::KeyboardShortcut,
+    command: KeyCommand,
+}
+
+/// 将配置中的按键绑定解析为 egui 快捷键并分发命令
+pub struct KeyboardController {
+    bindings: Vec<Binding>,
+}
+
+impl KeyboardController {
+    pub fn new(config: &KeyBindings) -> Self {
+        Self::with_actions(config, &[])
+    }
+
+    /// 同时绑定用户动作的快捷键
+    pub fn with_actions(config: &KeyBindings, actions: &[CommandAction]) -> Self {
+        let mut bindings = Vec::new();
+        let groups = [
+            (&config.focus_search, KeyCommand::FocusSearch),
+            (&config.select_next, KeyCommand::SelectNext),
+            (&config.select_previous, KeyCommand::SelectPrevious),
+            (&config.page_down, KeyCommand::PageDown),
+            (&config.page_up, KeyCommand::PageUp),
+            (&config.select_first, KeyCommand::SelectFirst),
+            (&config.select_last, KeyCommand::SelectLast),
+            (&config.copy_and_close, KeyCommand::CopyAndClose),
+            (&config.delete, KeyCommand::Delete),
+            (&config.toggle_favorite, KeyCommand::ToggleFavorite),
+        ];
+
+        for (keys, command) in groups {
+            for key in keys {
+                Self::push_binding(&mut bindings, key, command);
+            }
+        }
+        for index in 0..9 {
+            let key = format!("{}+{}", config.quick_paste_modifier, index + 1);
+            Self::push_binding(&mut bindings, &key, KeyCommand::QuickPaste(index));
+        }
+        for (index, action) in actions.iter().enumerate() {
+            if !action.shortcut.is_empty() {
+                Self::push_binding(
+                    &mut bindings,
+                    &action.shortcut,
+                    KeyCommand::RunAction(index),
+                );
+            }
+        }
+
+        // 修饰键越多越优先匹配，避免 Ctrl+Shift+X 被 Ctrl+X 抢先消费
+        bindings.sort_by_key(|binding| {
+            let a = &binding.accelerator;
+            std::cmp::Reverse(a.ctrl as u8 + a.shift as u8 + a.alt as u8 + a.super_key as u8)
+        });
+
+        Self { bindings }
+    }
+
+    fn push_binding(bindings: &mut Vec<Binding>, key: &str, command: KeyCommand) {
+        match key.parse::<Accelerator>() {
+            Ok(accelerator) => match accelerator.to_egui() {
+                Some(shortcut) => bindings.push(Binding {
+                    accelerator,
+                    shortcut,
+                    command,
+                }),
+                None => log::warn!("Key binding '{}' is not supported by the UI", key),
+            },
+            Err(e) => log::warn!("Invalid key binding: {}", e),
+        }
+    }
+
+    /// 读取本帧的按键并返回触发的命令
+    ///
+    /// 搜索框获得焦点时，不带修饰键的绑定只保留导航类命令，其余按键交给输入框。
+    pub fn poll(&self, ctx: &egui::Context) -> Option<KeyCommand> {
+        let typing = ctx.wants_keyboard_input();
+
+        let command = ctx.input_mut(|input| {
+            for binding in &self.bindings {
+                let plain_key = !binding.accelerator.ctrl
+                    && !binding.accelerator.alt
+                    && !binding.accelerator.super_key;
+                if typing && plain_key && !binding.command.works_while_typing() {
+                    continue;
+                }
+                if input.consume_shortcut(&binding.shortcut) {
+                    if plain_key {
+                        // 单键命令（如 F）不应再作为文字输入到搜索框
+                        input
+                            .events
+                            .retain(|event| !matches!(event, egui::Event::Text(_)));
+                    }
+                    return Some(binding.command);
+                }
+            }
+            None
+        });
+
+        if command.is_some() {
+            return command;
+        }
+
+        // 在列表中直接输入文字时自动聚焦搜索框
+        let has_text = ctx.input(|input| {
+            input
+                .events
+                .iter()
+                .any(|event| matches!(event, egui::Event::Text(text) if !text.trim().is_empty()))
+        });
+        if !typing && has_text {
+            return Some(KeyCommand::FocusSearch);
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_event(key: egui::Key, modifiers: egui::Modifiers) -> egui::Event {
+        egui::Event::Key {
+            key,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers,
+        }
+    }
+
+    fn run_frame(controller: &KeyboardController, events: Vec<egui::Event>) -> Option<KeyCommand> {
+        let ctx = egui::Context::default();
+        let mut command = None;
+        let input = egui::RawInput {
+            events,
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            command = controller.poll(ctx);
+        });
+        command
+    }
+
+    #[test]
+    fn test_default_bindings_dispatch() {
+        let controller = KeyboardController::new(&KeyBindings::default());
+
+        assert_eq!(
+            run_frame(
+                &controller,
+                vec![key_event(egui::Key::ArrowDown, egui::Modifiers::NONE)]
+            ),
+            Some(KeyCommand::SelectNext)
+        );
+        assert_eq!(
+            run_frame(
+                &controller,
+                vec![key_event(egui::Key::D, egui::Modifiers::CTRL)]
+            ),
+            Some(KeyCommand::ToggleFavorite)
+        );
+        assert_eq!(
+            run_frame(
+                &controller,
+                vec![key_event(egui::Key::Num3, egui::Modifiers::CTRL)]
+            ),
+            Some(KeyCommand::QuickPaste(2))
+        );
+    }
+
+    #[test]
+    fn test_action_bindings() {
+        let mut action = CommandAction::new("Shorten", "shorten-url");
+        action.shortcut = "Ctrl+Shift+U".to_string();
+        let actions = [CommandAction::new("Format", "jq"), action];
+        let controller = KeyboardController::with_actions(&KeyBindings::default(), &actions);
+
+        assert_eq!(
+            run_frame(
+                &controller,
+                vec![key_event(
+                    egui::Key::U,
+                    egui::Modifiers::CTRL | egui::Modifiers::SHIFT
+                )]
+            ),
+            Some(KeyCommand::RunAction(1))
+        );
+    }
+
+    #[test]
+    fn test_typing_focuses_search() {
+        let controller = KeyboardController::new(&KeyBindings::default());
+        assert_eq!(
+            run_frame(&controller, vec![egui::Event::Text("r".to_string())]),
+            Some(KeyCommand::FocusSearch)
+        );
+    }
+}
@@ -1,30 +1,250 @@
 use crate::app::ItemAction;
+use crate::clipboard::private_mode::{PrivateDuration, PrivateStatus};
 use crate::clipboard::types::{ClipboardContent, ClipboardItem, SearchFilter};
+use crate::config::actions::ActionsConfig;
+use crate::config::keybindings::KeyBindings;
 use crate::i18n::{self, TextKey};
+use crate::snippets::Snippet;
+use crate::transform::Transform;
+use crate::ui::components::snippet_form::FormOutcome;
+use crate::ui::components::{PreviewPanel, SnippetForm};
+use crate::ui::keyboard::{KeyCommand, KeyboardController};
+use crate::ui::thumbnail_cache::ThumbnailCache;
 use eframe::egui;
+use std::collections::HashSet;
+
+/// 列表行高（不含间距）
+const ROW_HEIGHT: f32 = 60.0;
+
+/// 距离列表底部多少行时开始加载下一页
+const LOAD_MORE_THRESHOLD: usize = 20;
 
 pub struct MainWindow {
-    selected_item: Option<usize>,
-    image_cache: std::collections::HashMap<String, egui::TextureHandle>,
+    /// 按条目 ID 记录选中项，刷新列表后依然有效
+    selected_item: Option<i64>,
+    image_cache: ThumbnailCache,
+    preview: PreviewPanel,
+    show_preview: bool,
+    keyboard: KeyboardController,
+    /// 上一帧可见的行范围，用于翻页和滚动到选中项
+    visible_rows: std::ops::Range<usize>,
+    /// 键盘选择后需要滚动到的行
+    scroll_to_row: Option<usize>,
+    /// 用户选择显示原文的敏感条目
+    revealed: HashSet<i64>,
+    /// 本次运行中未保存的机密条目数
+    skipped_count: u64,
+    private_status: PrivateStatus,
+    /// 设置了口令时在标题栏显示锁定按钮
+    lock_enabled: bool,
+    /// 右键菜单中列出的用户动作名称，顺序与配置一致
+    action_names: Vec<String>,
+    /// 与搜索内容匹配的片段，显示在历史记录之前
+    snippets: Vec<Snippet>,
+    /// 选中带字段的片段后显示的表单
+    snippet_form: Option<SnippetForm>,
 }
 
 impl MainWindow {
-    pub fn new() -> Self {
+    pub fn new(
+        image_cache: ThumbnailCache,
+        key_bindings: &KeyBindings,
+        actions: &ActionsConfig,
+    ) -> Self {
         Self {
             selected_item: None,
-            image_cache: std::collections::HashMap::new(),
+            image_cache,
+            preview: PreviewPanel::new(),
+            show_preview: true,
+            keyboard: KeyboardController::with_actions(key_bindings, &actions.actions),
+            visible_rows: 0..0,
+            scroll_to_row: None,
+            revealed: HashSet::new(),
+            skipped_count: 0,
+            private_status: PrivateStatus::default(),
+            lock_enabled: false,
+            action_names: Self::action_names(actions),
+            snippets: Vec::new(),
+            snippet_form: None,
+        }
+    }
+
+    pub fn selected_item(&self) -> Option<i64> {
+        self.selected_item
+    }
+
+    pub fn set_skipped_count(&mut self, count: u64) {
+        self.skipped_count = count;
+    }
+
+    pub fn set_private_status(&mut self, status: PrivateStatus) {
+        self.private_status = status;
+    }
+
+    pub fn set_lock_enabled(&mut self, enabled: bool) {
+        self.lock_enabled = enabled;
+    }
+
+    /// 窗口锁定时隐藏已显示原文的敏感条目
+    pub fn on_locked(&mut self) {
+        self.revealed.clear();
+        self.snippet_form = None;
+    }
+
+    pub fn set_snippets(&mut self, snippets: Vec<Snippet>) {
+        self.snippets = snippets;
+    }
+
+    pub fn set_key_bindings(&mut self, key_bindings: &KeyBindings, actions: &ActionsConfig) {
+        self.keyboard = KeyboardController::with_actions(key_bindings, &actions.actions);
+        self.action_names = Self::action_names(actions);
+    }
+
+    fn action_names(actions: &ActionsConfig) -> Vec<String> {
+        actions
+            .actions
+            .iter()
+            .map(|action| action.name.clone())
+            .collect()
+    }
+
+    fn is_revealed(&self, item: Option<&ClipboardItem>) -> bool {
+        item.and_then(|item| item.id)
+            .is_some_and(|id| self.revealed.contains(&id))
+    }
+
+    fn toggle_revealed(&mut self, id: i64) {
+        if !self.revealed.remove(&id) {
+            self.revealed.insert(id);
+        }
+    }
+
+    fn search_box_id() -> egui::Id {
+        egui::Id::new("history_search_box")
+    }
+
+    /// 聚焦搜索框并全选已有内容，便于直接输入新的查询
+    pub fn focus_search(&mut self, ctx: &egui::Context, query: &str) {
+        let id = Self::search_box_id();
+        let mut state = egui::TextEdit::load_state(ctx, id).unwrap_or_default();
+        state
+            .cursor
+            .set_char_range(Some(egui::text::CCursorRange::two(
+                egui::text::CCursor::new(0),
+                egui::text::CCursor::new(query.chars().count()),
+            )));
+        egui::TextEdit::store_state(ctx, id, state);
+        ctx.memory_mut(|memory| memory.request_focus(id));
+    }
+
+    /// 处理键盘命令，返回需要应用层执行的操作
+    fn handle_keyboard(
+        &mut self,
+        ctx: &egui::Context,
+        items: &[ClipboardItem],
+    ) -> Option<ItemAction> {
+        let command = self.keyboard.poll(ctx)?;
+        let current = self
+            .selected_item
+            .and_then(|id| items.iter().position(|item| item.id == Some(id)));
+        let last = items.len().checked_sub(1);
+        let page = self.visible_rows.len().max(1);
+
+        let target = match command {
+            KeyCommand::FocusSearch => {
+                ctx.memory_mut(|memory| memory.request_focus(Self::search_box_id()));
+                return None;
+            }
+            KeyCommand::SelectNext => current.map_or(Some(0), |index| Some(index + 1)),
+            KeyCommand::SelectPrevious => current.map(|index| index.saturating_sub(1)),
+            KeyCommand::PageDown => current.map_or(Some(0), |index| Some(index + page)),
+            KeyCommand::PageUp => current.map(|index| index.saturating_sub(page)),
+            KeyCommand::SelectFirst => Some(0),
+            KeyCommand::SelectLast => last,
+            KeyCommand::CopyAndClose => {
+                return current.and_then(|index| items[index].id.map(ItemAction::CopyAndClose));
+            }
+            KeyCommand::QuickPaste(index) => {
+                return items
+                    .get(index)
+                    .and_then(|item| item.id)
+                    .map(ItemAction::CopyAndClose);
+            }
+            KeyCommand::Delete => {
+                let index = current?;
+                let id = items[index].id?;
+                // 删除后选中相邻条目
+                self.selected_item = items
+                    .get(index + 1)
+                    .or_else(|| index.checked_sub(1).and_then(|i| items.get(i)))
+                    .and_then(|item| item.id);
+                return Some(ItemAction::Delete(id));
+            }
+            KeyCommand::ToggleFavorite => {
+                return current.and_then(|index| items[index].id.map(ItemAction::ToggleFavorite));
+            }
+            KeyCommand::RunAction(index) => {
+                let item = &items[current?];
+                if item.sensitive.is_some() || item.get_text_content().is_none() {
+                    return None;
+                }
+                return item.id.map(|id| ItemAction::RunAction {
+                    id,
+                    index,
+                    save: false,
+                });
+            }
+        };
+
+        if let (Some(target), Some(last)) = (target, last) {
+            let target = target.min(last);
+            self.selected_item = items[target].id;
+            self.scroll_to_row = Some(target);
+            // 键盘选择时让搜索框失去焦点，以便使用单键命令
+            ctx.memory_mut(|memory| memory.surrender_focus(Self::search_box_id()));
         }
+        None
     }
 
     pub fn show(
         &mut self,
         ctx: &egui::Context,
-        items: &mut [ClipboardItem],
+        items: &[ClipboardItem],
+        has_more: bool,
         search_filter: &mut SearchFilter,
         error_message: &Option<String>,
         copy_feedback: &Option<String>,
     ) -> Option<ItemAction> {
-        let mut action = None;
+        // 填写片段字段时按键交给表单
+        let mut action = match self.snippet_form {
+            Some(_) => self.show_snippet_form(ctx),
+            None => self.handle_keyboard(ctx, items),
+        };
+
+        if self.private_status.paused {
+            if let Some(resume) = self.show_private_banner(ctx) {
+                action = Some(resume);
+            }
+        }
+
+        // Preview panel must be added before the central panel
+        if self.show_preview {
+            let selected = self
+                .selected_item
+                .and_then(|id| items.iter().find(|item| item.id == Some(id)));
+            egui::SidePanel::right("preview_panel")
+                .resizable(true)
+                .default_width(280.0)
+                .min_width(200.0)
+                .show(ctx, |ui| {
+                    let revealed = self.is_revealed(selected);
+                    if self.preview.show(ui, selected, revealed) {
+                        if let Some(id) = selected.and_then(|item| item.id) {
+                            self.toggle_revealed(id);
+                        }
+                    }
+                });
+        }
 
         egui::CentralPanel::default().show(ctx, |ui| {
             // 设置最小窗口尺寸
@@ -41,12 +261,42 @@ impl MainWindow {
             ui.horizontal(|ui| {
                 ui.heading(i18n::t(TextKey::AppTitle));
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    // 预览面板开关
+                    if ui
+                        .selectable_label(self.show_preview, "👁")
+                        .on_hover_text("Toggle Preview")
+                        .clicked()
+                    {
+                        self.show_preview = !self.show_preview;
+                    }
+
+                    if let Some(private) = self.show_private_menu(ui) {
+                        action = Some(private);
+                    }
+
+                    if self.lock_enabled
+                        && ui
+                            .button("🔒")
+                            .on_hover_text(i18n::t(TextKey::LockNow))
+                            .clicked()
+                    {
+                        action = Some(ItemAction::Lock);
+                    }
+
                     // 主题切换按钮
                     if ui.button("🎨").on_hover_text("Toggle Theme").clicked() {
                         log::info!("Theme toggle button clicked");
                         action = Some(ItemAction::ToggleTheme);
                     }
 
+                    if ui
+                        .button("✂")
+                        .on_hover_text(i18n::t(TextKey::Snippets))
+                        .clicked()
+                    {
+                        action = Some(ItemAction::OpenSnippets);
+                    }
+
                     if ui.button(i18n::t(TextKey::ClearAll)).clicked() {
                         action = Some(ItemAction::ClearAll);
                     }
@@ -61,7 +311,9 @@ impl MainWindow {
             // Search bar
             ui.horizontal(|ui| {
                 ui.label(i18n::t(TextKey::SearchPlaceholder));
-                ui.text_edit_singleline(&mut search_filter.query);
+                ui.add(
+                    egui::TextEdit::singleline(&mut search_filter.query).id(Self::search_box_id()),
+                );
 
                 ui.separator();
 
@@ -116,22 +368,53 @@ impl MainWindow {
                 ui.separator();
             }
 
-            // Item list
-            egui::ScrollArea::vertical()
-                .auto_shrink([false; 2])
-                .show(ui, |ui| {
-                    if items.is_empty() {
-                        ui.centered_and_justified(|ui| {
-                            ui.label(i18n::t(TextKey::NoRecords));
-                        });
-                    } else {
-                        for (index, item) in items.iter().enumerate() {
-                            if let Some(item_action) = self.show_item(ui, item, index) {
-                                action = Some(item_action);
-                            }
+            // 搜索时匹配的片段显示在历史记录之前
+            if !self.snippets.is_empty() {
+                if let Some(snippet_action) = self.show_snippet_matches(ui) {
+                    action = Some(snippet_action);
+                }
+                ui.separator();
+            }
+
+            // Item list: only the visible rows are laid out
+            if items.is_empty() {
+                ui.centered_and_justified(|ui| {
+                    ui.label(i18n::t(TextKey::NoRecords));
+                });
+            } else {
+                let status_bar_height = ui.spacing().interact_size.y + 8.0;
+                let list_height = ui.available_height() - status_bar_height;
+                let mut scroll_area = egui::ScrollArea::vertical()
+                    .id_salt("history_list")
+                    .auto_shrink([false; 2])
+                    .max_height(list_height);
+
+                // 键盘选中的行不在可见范围内时滚动过去
+                if let Some(row) = self.scroll_to_row.take() {
+                    let stride = ROW_HEIGHT + ui.spacing().item_spacing.y;
+                    if row < self.visible_rows.start + 1 {
+                        scroll_area = scroll_area.vertical_scroll_offset(row as f32 * stride);
+                    } else if row + 1 >= self.visible_rows.end {
+                        let offset = (row + 1) as f32 * stride - list_height;
+                        scroll_area = scroll_area.vertical_scroll_offset(offset.max(0.0));
+                    }
+                }
+
+                scroll_area.show_rows(ui, ROW_HEIGHT, items.len(), |ui, row_range| {
+                    self.visible_rows = row_range.clone();
+
+                    // 接近底部时请求加载下一页
+                    if has_more && row_range.end + LOAD_MORE_THRESHOLD >= items.len() {
+                        action.get_or_insert(ItemAction::LoadMore);
+                    }
+
+                    for index in row_range {
+                        if let Some(item_action) = self.show_item(ui, &items[index], index) {
+                            action = Some(item_action);
                         }
                     }
                 });
+            }
 
             // Status bar
             ui.separator();
@@ -141,6 +424,14 @@ impl MainWindow {
                     items.len(),
                     i18n::t(TextKey::RecordsCount)
                 ));
+                if self.skipped_count > 0 {
+                    ui.separator();
+                    ui.label(format!(
+                        "🔒 {} {}",
+                        self.skipped_count,
+                        i18n::t(TextKey::SkippedCount)
+                    ));
+                }
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     ui.label(i18n::t(TextKey::AppVersion));
                 });
@@ -150,6 +441,103 @@ impl MainWindow {
         action
     }
 
+    fn show_snippet_matches(&mut self, ui: &mut egui::Ui) -> Option<ItemAction> {
+        let mut clicked = None;
+        ui.horizontal_wrapped(|ui| {
+            ui.label(i18n::t(TextKey::Snippets));
+            for snippet in &self.snippets {
+                let mut label = format!("✂ {}", snippet.path());
+                if !snippet.abbreviation.is_empty() {
+                    label.push_str(&format!(" ({})", snippet.abbreviation));
+                }
+                let preview: String = snippet.body.chars().take(200).collect();
+                if ui.button(label).on_hover_text(preview).clicked() {
+                    clicked = Some(snippet.clone());
+                }
+            }
+        });
+
+        let snippet = clicked?;
+        match SnippetForm::new(&snippet) {
+            Some(form) => {
+                self.snippet_form = Some(form);
+                None
+            }
+            None => snippet
+                .id
+                .map(|id| ItemAction::UseSnippet(id, Default::default())),
+        }
+    }
+
+    fn show_snippet_form(&mut self, ctx: &egui::Context) -> Option<ItemAction> {
+        let form = self.snippet_form.as_mut()?;
+        let mut outcome = None;
+        egui::Window::new(format!("✂ {}", form.title()))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| outcome = form.show(ui));
+        match outcome? {
+            FormOutcome::Submit(id, fields) => {
+                self.snippet_form = None;
+                Some(ItemAction::UseSnippet(id, fields))
+            }
+            FormOutcome::Cancel => {
+                self.snippet_form = None;
+                None
+            }
+        }
+    }
+
+    /// 隐私模式横幅，显示剩余时间和恢复按钮
+    fn show_private_banner(&self, ctx: &egui::Context) -> Option<ItemAction> {
+        let status = self.private_status;
+        let mut action = None;
+        egui::TopBottomPanel::top("private_mode_banner").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    format!("🕶 {}", i18n::t(TextKey::PrivateModeActive)),
+                );
+                if let Some(remaining) = status.remaining() {
+                    ui.label(format_remaining(remaining));
+                } else if status.until_restart {
+                    ui.label(i18n::t(TextKey::PrivateModeUntilRestart));
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button(i18n::t(TextKey::ResumeRecording)).clicked() {
+                        action = Some(ItemAction::SetPrivateMode(None));
+                    }
+                });
+            });
+        });
+        // 倒计时每秒刷新一次
+        if status.resume_at.is_some() {
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+        }
+        action
+    }
+
+    fn show_private_menu(&self, ui: &mut egui::Ui) -> Option<ItemAction> {
+        let mut action = None;
+        ui.menu_button("🕶", |ui| {
+            if self.private_status.paused {
+                if ui.button(i18n::t(TextKey::ResumeRecording)).clicked() {
+                    action = Some(ItemAction::SetPrivateMode(None));
+                }
+                return;
+            }
+            for (label, duration) in private_mode_options() {
+                if ui.button(i18n::t(label)).clicked() {
+                    action = Some(ItemAction::SetPrivateMode(Some(duration)));
+                }
+            }
+        })
+        .response
+        .on_hover_text(i18n::t(TextKey::PrivateMode));
+        action
+    }
+
     fn show_item(
         &mut self,
         ui: &mut egui::Ui,
@@ -165,7 +553,7 @@ impl MainWindow {
 
         // 绘制条目背景
         let rect = response.rect;
-        let is_selected = self.selected_item == Some(index);
+        let is_selected = item.id.is_some() && self.selected_item == item.id;
         let is_hovered = response.hovered();
 
         let bg_color = if is_selected {
@@ -191,29 +579,21 @@ impl MainWindow {
                         ui.label("📄");
                     }
                     ClipboardContent::Image(image_data) => {
-                        // 显示图片缩略图
-                        if let Ok(image) = image::load_from_memory(&image_data.data) {
-                            let thumbnail_size = icon_size as u32;
-                            let thumbnail = image.thumbnail(thumbnail_size, thumbnail_size);
-                            let rgba_image = thumbnail.to_rgba8();
-                            let size = [thumbnail.width() as usize, thumbnail.height() as usize];
-                            let pixels = rgba_image.as_flat_samples();
-
-                            let color_image =
-                                egui::ColorImage::from_rgba_unmultiplied(size, pixels.as_slice());
-
-                            let texture = ui.ctx().load_texture(
-                                format!("thumbnail_{}", item.content_hash),
-                                color_image,
-                                egui::TextureOptions::default(),
-                            );
-
-                            ui.add(
-                                egui::Image::from_texture(&texture)
-                                    .max_size(egui::Vec2::new(icon_size, icon_size)),
-                            );
-                        } else {
-                            ui.label("🖼️");
+                        // 显示缓存的缩略图，解码完成前显示占位符
+                        let icon = egui::Vec2::new(icon_size, icon_size);
+                        match self
+                            .image_cache
+                            .get(ui.ctx(), &item.content_hash, image_data)
+                        {
+                            Some(texture) => {
+                                ui.add(egui::Image::from_texture(&texture).max_size(icon));
+                            }
+                            None if self.image_cache.is_failed(&item.content_hash) => {
+                                ui.label("🖼️");
+                            }
+                            None => {
+                                ui.add_sized(icon, egui::Spinner::new());
+                            }
                         }
                     }
                 }
@@ -222,8 +602,35 @@ impl MainWindow {
                     ui.horizontal(|ui| {
                         // Content preview with responsive length
                         let preview_length = if is_narrow { 25 } else { 50 };
-                        let preview = item.preview(preview_length);
-                        ui.label(egui::RichText::new(preview).strong());
+                        if item.sensitive.is_some() && !self.is_revealed(Some(item)) {
+                            ui.label(egui::RichText::new("••••••••").strong());
+                        } else {
+                            let preview = item.preview(preview_length);
+                            ui.label(egui::RichText::new(preview).strong());
+                        }
+
+                        // 敏感内容标记
+                        if let Some(label) = &item.sensitive {
+                            let badge = ui.label(
+                                egui::RichText::new(format!("🔒 {}", label))
+                                    .small()
+                                    .color(ui.visuals().warn_fg_color),
+                            );
+                            if let Some(id) = item.id {
+                                let hint = if self.revealed.contains(&id) {
+                                    i18n::t(TextKey::HideSensitive)
+                                } else {
+                                    i18n::t(TextKey::RevealSensitive)
+                                };
+                                if badge
+                                    .on_hover_text(hint)
+                                    .interact(egui::Sense::click())
+                                    .clicked()
+                                {
+                                    self.toggle_revealed(id);
+                                }
+                            }
+                        }
 
                         // Favorite indicator
                         if item.is_favorite {
@@ -316,7 +723,7 @@ impl MainWindow {
 
         // Handle click events
         if response.clicked() {
-            self.selected_item = Some(index);
+            self.selected_item = item.id;
 
             // 单击时只更新访问记录，不复制
             if let Some(id) = item.id {
@@ -324,27 +731,11 @@ impl MainWindow {
             }
         }
 
-        // Handle double-click events for copying
+        // 双击与回车相同：复制、隐藏窗口并按配置粘贴
         if response.double_clicked() {
-            log::info!("Double-click detected on item {}", index);
-
-            match &item.content {
-                ClipboardContent::Text(text) => {
-                    log::info!("Double-click copy text: {} characters", text.len());
-                    action = Some(ItemAction::DoubleClickCopy(text.clone()));
-                }
-                ClipboardContent::Image(_) => {
-                    // 对于图片，我们可以复制一个描述或者实际的图片数据
-                    // 这里先复制描述信息
-                    let description = format!(
-                        "Image ({}x{}, {} bytes)",
-                        item.get_image_data().map(|img| img.width).unwrap_or(0),
-                        item.get_image_data().map(|img| img.height).unwrap_or(0),
-                        item.content_size
-                    );
-                    log::info!("Double-click copy image description: {}", description);
-                    action = Some(ItemAction::DoubleClickCopy(description));
-                }
+            if let Some(id) = item.id {
+                log::info!("Double-click detected on item {}", index);
+                action = Some(ItemAction::CopyAndClose(id));
             }
         }
 
@@ -362,6 +753,13 @@ impl MainWindow {
                 }
                 ui.close();
             }
+            // 敏感条目不提供转换，避免生成不会过期的明文副本
+            if item.sensitive.is_none() && item.get_text_content().is_some() {
+                if let Some(id) = item.id {
+                    Self::show_transform_menu(ui, id, &mut action);
+                    Self::show_action_menu(ui, &self.action_names, id, &mut action);
+                }
+            }
             if let Some(id) = item.id {
                 ui.separator();
                 let favorite_text = if item.is_favorite {
@@ -373,9 +771,113 @@ impl MainWindow {
                     action = Some(ItemAction::ToggleFavorite(id));
                     ui.close();
                 }
+                // 收藏的条目不会过期
+                if !item.is_favorite {
+                    ui.menu_button(i18n::t(TextKey::ExpireIn), |ui| {
+                        for (label, ttl) in expiry_options() {
+                            if ui.button(i18n::t(label)).clicked() {
+                                action = Some(ItemAction::SetExpiry(id, Some(ttl)));
+                                ui.close();
+                            }
+                        }
+                        if item.expires_at.is_some() {
+                            ui.separator();
+                            if ui.button(i18n::t(TextKey::ExpireNever)).clicked() {
+                                action = Some(ItemAction::SetExpiry(id, None));
+                                ui.close();
+                            }
+                        }
+                    });
+                }
             }
         });
 
         action
     }
+
+    /// 文本条目的转换菜单，结果可以复制或保存为新条目
+    fn show_transform_menu(ui: &mut egui::Ui, id: i64, action: &mut Option<ItemAction>) {
+        ui.menu_button(i18n::t(TextKey::TransformMenu), |ui| {
+            for (i, group) in Transform::GROUPS.iter().enumerate() {
+                if i > 0 {
+                    ui.separator();
+                }
+                for &transform in *group {
+                    ui.menu_button(i18n::t(transform.text_key()), |ui| {
+                        for (label, save) in [
+                            (TextKey::TransformCopy, false),
+                            (TextKey::TransformSave, true),
+                        ] {
+                            if ui.button(i18n::t(label)).clicked() {
+                                *action = Some(ItemAction::Transform {
+                                    id,
+                                    transform,
+                                    save,
+                                });
+                                ui.close();
+                            }
+                        }
+                    });
+                }
+            }
+        });
+    }
+
+    /// 用户动作菜单，没有配置动作时不显示
+    fn show_action_menu(
+        ui: &mut egui::Ui,
+        names: &[String],
+        id: i64,
+        action: &mut Option<ItemAction>,
+    ) {
+        if names.is_empty() {
+            return;
+        }
+        ui.menu_button(i18n::t(TextKey::ActionsMenu), |ui| {
+            for (index, name) in names.iter().enumerate() {
+                ui.menu_button(name, |ui| {
+                    for (label, save) in [
+                        (TextKey::TransformCopy, false),
+                        (TextKey::TransformSave, true),
+                    ] {
+                        if ui.button(i18n::t(label)).clicked() {
+                            *action = Some(ItemAction::RunAction { id, index, save });
+                            ui.close();
+                        }
+                    }
+                });
+            }
+        });
+    }
+}
+
+/// 条目右键菜单中的过期时间选项
+fn expiry_options() -> [(TextKey, chrono::Duration); 4] {
+    [
+        (TextKey::ExpireIn1Minute, chrono::Duration::minutes(1)),
+        (TextKey::ExpireIn10Minutes, chrono::Duration::minutes(10)),
+        (TextKey::ExpireIn1Hour, chrono::Duration::hours(1)),
+        (TextKey::ExpireIn1Day, chrono::Duration::days(1)),
+    ]
+}
+
+/// 隐私模式菜单中的时长选项
+pub fn private_mode_options() -> [(TextKey, PrivateDuration); 4] {
+    [
+        (TextKey::PauseFor5Minutes, PrivateDuration::minutes(5)),
+        (TextKey::PauseFor1Hour, PrivateDuration::minutes(60)),
+        (TextKey::PauseUntilRestart, PrivateDuration::UntilRestart),
+        (TextKey::PauseUntilResumed, PrivateDuration::UntilResumed),
+    ]
+}
+
+/// 剩余时间显示为 `m:ss` 或 `h:mm:ss`
+fn format_remaining(remaining: chrono::Duration) -> String {
+    let seconds = remaining.num_seconds();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
 }
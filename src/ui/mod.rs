@@ -1,7 +1,10 @@
 pub mod components;
 pub mod font_manager;
+pub mod keyboard;
 pub mod main_window;
+pub mod popup;
 pub mod theme;
+pub mod thumbnail_cache;
 
 pub use font_manager::FontManager;
 pub use main_window::MainWindow;
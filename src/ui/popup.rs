@@ -0,0 +1,339 @@
+use crate::clipboard::types::{ClipboardContent, ClipboardItem};
+use crate::config::keybindings::KeyBindings;
+use crate::config::settings::PopupConfig;
+use crate::i18n::{self, TextKey};
+use crate::snippets::Snippet;
+use crate::ui::components::snippet_form::{FormOutcome, SnippetForm};
+use crate::ui::keyboard::{KeyCommand, KeyboardController};
+use eframe::egui;
+use std::collections::HashMap;
+
+/// 弹出选择器中每行最多显示的字符数
+const PREVIEW_CHARS: usize = 80;
+
+/// 弹出选择器返回给应用层的操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum PopupAction {
+    /// 复制（并按配置自动粘贴）选中的条目
+    Pick(i64),
+    /// 展开片段后复制并粘贴，附带填写的字段
+    PickSnippet(i64, HashMap<String, String>),
+    /// 搜索内容变化，需要重新加载条目
+    QueryChanged,
+    Closed,
+}
+
+/// 无边框、置顶的紧凑选择器，类似 Windows 的 Win+V
+///
+/// 作为独立视口显示在鼠标附近，失去焦点或按 Escape 时关闭。
+pub struct PopupPicker {
+    open: bool,
+    position: egui::Pos2,
+    query: String,
+    selected: usize,
+    keyboard: KeyboardController,
+    /// 视口获得过焦点后，失去焦点才视为关闭
+    was_focused: bool,
+    request_focus: bool,
+    /// 键盘选择后滚动到选中行
+    scroll_to_selected: bool,
+    /// 选中带字段的片段后显示的表单
+    form: Option<SnippetForm>,
+}
+
+/// 选择器中的一行：搜索时匹配的片段排在历史条目之前
+#[derive(Clone, Copy)]
+enum Row<'a> {
+    Snippet(&'a Snippet),
+    Item(&'a ClipboardItem),
+}
+
+impl PopupPicker {
+    pub fn new(key_bindings: &KeyBindings) -> Self {
+        Self {
+            open: false,
+            position: egui::Pos2::ZERO,
+            query: String::new(),
+            selected: 0,
+            keyboard: KeyboardController::new(key_bindings),
+            was_focused: false,
+            request_focus: false,
+            scroll_to_selected: false,
+            form: None,
+        }
+    }
+
+    fn viewport_id() -> egui::ViewportId {
+        egui::ViewportId::from_hash_of("clipmanager_popup")
+    }
+
+    fn search_box_id() -> egui::Id {
+        egui::Id::new("popup_search_box")
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn set_key_bindings(&mut self, key_bindings: &KeyBindings) {
+        self.keyboard = KeyboardController::new(key_bindings);
+    }
+
+    /// 在指定位置（逻辑坐标）打开，保证窗口不超出屏幕
+    pub fn open_at(
+        &mut self,
+        position: egui::Pos2,
+        monitor: Option<egui::Vec2>,
+        config: &PopupConfig,
+    ) {
+        let mut position = position;
+        if let Some(monitor) = monitor {
+            position.x = position.x.min(monitor.x - config.width).max(0.0);
+            position.y = position.y.min(monitor.y - config.height).max(0.0);
+        }
+        self.position = position;
+        self.open = true;
+        self.query.clear();
+        self.selected = 0;
+        self.was_focused = false;
+        self.request_focus = true;
+        self.form = None;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        items: &[ClipboardItem],
+        snippets: &[Snippet],
+        config: &PopupConfig,
+    ) -> Option<PopupAction> {
+        if !self.open {
+            return None;
+        }
+
+        let builder = egui::ViewportBuilder::default()
+            .with_title(i18n::t(TextKey::AppTitle))
+            .with_decorations(false)
+            .with_always_on_top()
+            .with_taskbar(false)
+            .with_resizable(false)
+            .with_inner_size([config.width, config.height])
+            .with_position(self.position);
+
+        let action = ctx.show_viewport_immediate(Self::viewport_id(), builder, |ctx, _| {
+            self.show_contents(ctx, items, snippets)
+        });
+
+        if matches!(
+            action,
+            Some(PopupAction::Pick(_) | PopupAction::PickSnippet(..) | PopupAction::Closed)
+        ) {
+            self.open = false;
+        }
+        action
+    }
+
+    /// 选中一行：片段有字段时先显示表单
+    fn pick(&mut self, row: Row<'_>) -> Option<PopupAction> {
+        match row {
+            Row::Item(item) => item.id.map(PopupAction::Pick),
+            Row::Snippet(snippet) => match SnippetForm::new(snippet) {
+                Some(form) => {
+                    self.form = Some(form);
+                    None
+                }
+                None => snippet
+                    .id
+                    .map(|id| PopupAction::PickSnippet(id, HashMap::new())),
+            },
+        }
+    }
+
+    fn show_form(&mut self, ctx: &egui::Context) -> Option<PopupAction> {
+        let form = self.form.as_mut()?;
+        let mut outcome = None;
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.strong(format!("✂ {}", form.title()));
+            ui.separator();
+            outcome = form.show(ui);
+        });
+        match outcome? {
+            FormOutcome::Submit(id, fields) => Some(PopupAction::PickSnippet(id, fields)),
+            FormOutcome::Cancel => {
+                self.form = None;
+                self.request_focus = true;
+                None
+            }
+        }
+    }
+
+    fn show_contents(
+        &mut self,
+        ctx: &egui::Context,
+        items: &[ClipboardItem],
+        snippets: &[Snippet],
+    ) -> Option<PopupAction> {
+        if self.request_focus {
+            self.request_focus = false;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+            ctx.memory_mut(|memory| memory.request_focus(Self::search_box_id()));
+        }
+
+        // 失去焦点、按 Escape 或窗口被关闭时收起
+        let (focused, escape, close_requested) = ctx.input(|i| {
+            (
+                i.viewport().focused,
+                i.key_pressed(egui::Key::Escape),
+                i.viewport().close_requested(),
+            )
+        });
+        if focused == Some(true) {
+            self.was_focused = true;
+        }
+        // 表单中的 Escape 只关闭表单
+        if close_requested || (self.was_focused && focused == Some(false)) {
+            return Some(PopupAction::Closed);
+        }
+        if self.form.is_some() {
+            return self.show_form(ctx);
+        }
+        if escape {
+            return Some(PopupAction::Closed);
+        }
+
+        let rows: Vec<Row<'_>> = snippets
+            .iter()
+            .map(Row::Snippet)
+            .chain(items.iter().map(Row::Item))
+            .collect();
+        let mut action = None;
+        let previous = self.selected;
+        self.selected = self.selected.min(rows.len().saturating_sub(1));
+
+        match self.keyboard.poll(ctx) {
+            Some(KeyCommand::SelectNext) => {
+                self.selected = (self.selected + 1).min(rows.len().saturating_sub(1));
+            }
+            Some(KeyCommand::SelectPrevious) => self.selected = self.selected.saturating_sub(1),
+            Some(KeyCommand::SelectFirst) => self.selected = 0,
+            Some(KeyCommand::SelectLast) => self.selected = rows.len().saturating_sub(1),
+            Some(KeyCommand::CopyAndClose) => {
+                if let Some(&row) = rows.get(self.selected) {
+                    action = self.pick(row);
+                }
+            }
+            Some(KeyCommand::QuickPaste(index)) => {
+                if let Some(&row) = rows.get(index) {
+                    action = self.pick(row);
+                }
+            }
+            Some(KeyCommand::FocusSearch) => {
+                ctx.memory_mut(|memory| memory.request_focus(Self::search_box_id()));
+            }
+            _ => {}
+        }
+        self.scroll_to_selected |= self.selected != previous;
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let search = ui.add(
+                egui::TextEdit::singleline(&mut self.query)
+                    .id(Self::search_box_id())
+                    .hint_text(i18n::t(TextKey::SearchPlaceholder))
+                    .desired_width(f32::INFINITY),
+            );
+            if search.changed() {
+                self.selected = 0;
+                action.get_or_insert(PopupAction::QueryChanged);
+            }
+            ui.separator();
+
+            if rows.is_empty() {
+                ui.centered_and_justified(|ui| {
+                    ui.label(i18n::t(TextKey::NoRecords));
+                });
+                return;
+            }
+
+            egui::ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    for (index, &row) in rows.iter().enumerate() {
+                        let selected = index == self.selected;
+                        let summary = match row {
+                            Row::Snippet(snippet) => Self::snippet_summary(snippet),
+                            Row::Item(item) => Self::summary(item),
+                        };
+                        let label = format!("{}  {}", index + 1, summary);
+                        let response = ui.add_sized(
+                            [ui.available_width(), 0.0],
+                            egui::Button::selectable(selected, label).truncate(),
+                        );
+                        if selected && self.scroll_to_selected {
+                            response.scroll_to_me(None);
+                            self.scroll_to_selected = false;
+                        }
+                        if response.clicked() {
+                            action = self.pick(row);
+                        }
+                    }
+                });
+        });
+
+        action
+    }
+
+    fn snippet_summary(snippet: &Snippet) -> String {
+        let mut label = format!("✂ {}", snippet.path());
+        if !snippet.abbreviation.is_empty() {
+            label.push_str(&format!(" ({})", snippet.abbreviation));
+        }
+        label
+    }
+
+    fn summary(item: &ClipboardItem) -> String {
+        match &item.content {
+            _ if item.sensitive.is_some() => item.safe_preview(PREVIEW_CHARS),
+            ClipboardContent::Text(text) => {
+                let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                if line.chars().count() > PREVIEW_CHARS {
+                    format!("{}…", line.chars().take(PREVIEW_CHARS).collect::<String>())
+                } else {
+                    line
+                }
+            }
+            ClipboardContent::Image(image) => {
+                format!("🖼️ {} × {}", image.width, image.height)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_at_stays_on_screen() {
+        let config = PopupConfig::default();
+        let mut popup = PopupPicker::new(&KeyBindings::default());
+        let monitor = egui::vec2(1920.0, 1080.0);
+
+        popup.open_at(egui::pos2(1900.0, 1000.0), Some(monitor), &config);
+        assert!(popup.is_open());
+        assert_eq!(popup.position.x, 1920.0 - config.width);
+        assert_eq!(popup.position.y, 1080.0 - config.height);
+
+        popup.open_at(egui::pos2(100.0, 200.0), Some(monitor), &config);
+        assert_eq!(popup.position, egui::pos2(100.0, 200.0));
+        popup.close();
+        assert!(!popup.is_open());
+    }
+}
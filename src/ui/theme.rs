@@ -81,7 +81,7 @@ impl ThemeManager {
 
         // 尝试检测GNOME设置
         if let Ok(output) = std::process::Command::new("gsettings")
-            .args(&["get", "org.gnome.desktop.interface", "gtk-theme"])
+            .args(["get", "org.gnome.desktop.interface", "gtk-theme"])
             .output()
         {
             if let Ok(theme) = String::from_utf8(output.stdout) {
@@ -0,0 +1,321 @@
+use crate::clipboard::types::ImageData;
+use crate::storage::database::Database;
+use eframe::egui;
+use std::collections::{HashMap, HashSet};
+use std::io::Cursor;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// 缩略图边长（像素）
+pub const THUMBNAIL_SIZE: u32 = 48;
+
+/// 默认纹理内存预算：16 MB
+pub const DEFAULT_MEMORY_BUDGET: usize = 16 * 1024 * 1024;
+
+/// 预览面板中全尺寸图片的纹理内存预算：64 MB
+pub const FULL_SIZE_MEMORY_BUDGET: usize = 64 * 1024 * 1024;
+
+struct DecodeRequest {
+    content_hash: String,
+    image: ImageData,
+}
+
+struct DecodeResult {
+    content_hash: String,
+    image: Option<egui::ColorImage>,
+}
+
+struct CacheEntry {
+    texture: egui::TextureHandle,
+    size_bytes: usize,
+    last_used: u64,
+}
+
+/// 图片缩略图缓存
+///
+/// 以内容哈希为键，在后台线程解码（优先读取数据库中的持久化缩略图），
+/// 内存中的纹理按 LRU 策略在预算内淘汰。[`ThumbnailCache::full_size`] 创建的实例
+/// 不缩放也不持久化，供预览面板显示原图。
+pub struct ThumbnailCache {
+    entries: HashMap<String, CacheEntry>,
+    pending: HashSet<String>,
+    failed: HashSet<String>,
+    memory_budget: usize,
+    used_bytes: usize,
+    clock: u64,
+    requests: mpsc::Sender<DecodeRequest>,
+    results: mpsc::Receiver<DecodeResult>,
+}
+
+impl ThumbnailCache {
+    pub fn new(ctx: egui::Context, store: Option<Arc<Database>>, memory_budget: usize) -> Self {
+        Self::spawn(ctx, store, Some(THUMBNAIL_SIZE), memory_budget)
+    }
+
+    /// 解码全尺寸图片的缓存
+    pub fn full_size(ctx: egui::Context, memory_budget: usize) -> Self {
+        Self::spawn(ctx, None, None, memory_budget)
+    }
+
+    /// `size` 为 None 时保持原始尺寸
+    fn spawn(
+        ctx: egui::Context,
+        store: Option<Arc<Database>>,
+        size: Option<u32>,
+        memory_budget: usize,
+    ) -> Self {
+        let (requests, request_rx) = mpsc::channel::<DecodeRequest>();
+        let (result_tx, results) = mpsc::channel();
+
+        thread::spawn(move || {
+            log::debug!("Thumbnail decoder thread started");
+            while let Ok(request) = request_rx.recv() {
+                let image = match size {
+                    Some(_) => Self::load_or_decode(store.as_deref(), &request),
+                    None => decode_image(&request.image).map(|image| Self::to_color_image(&image)),
+                };
+                let result = DecodeResult {
+                    content_hash: request.content_hash,
+                    image,
+                };
+                if result_tx.send(result).is_err() {
+                    break;
+                }
+                ctx.request_repaint();
+            }
+            log::debug!("Thumbnail decoder thread exiting");
+        });
+
+        Self {
+            entries: HashMap::new(),
+            pending: HashSet::new(),
+            failed: HashSet::new(),
+            memory_budget,
+            used_bytes: 0,
+            clock: 0,
+            requests,
+            results,
+        }
+    }
+
+    /// 获取缩略图纹理；尚未解码时提交后台任务并返回 `None`
+    pub fn get(
+        &mut self,
+        ctx: &egui::Context,
+        content_hash: &str,
+        image: &ImageData,
+    ) -> Option<egui::TextureHandle> {
+        self.poll(ctx);
+        self.clock += 1;
+
+        if let Some(entry) = self.entries.get_mut(content_hash) {
+            entry.last_used = self.clock;
+            return Some(entry.texture.clone());
+        }
+
+        if !self.pending.contains(content_hash) && !self.failed.contains(content_hash) {
+            let request = DecodeRequest {
+                content_hash: content_hash.to_string(),
+                image: image.clone(),
+            };
+            if self.requests.send(request).is_ok() {
+                self.pending.insert(content_hash.to_string());
+            }
+        }
+        None
+    }
+
+    /// 解码失败（不支持的格式）的条目不再重试
+    pub fn is_failed(&self, content_hash: &str) -> bool {
+        self.failed.contains(content_hash)
+    }
+
+    pub fn used_bytes(&self) -> usize {
+        self.used_bytes
+    }
+
+    /// 接收后台解码结果并上传为纹理
+    pub fn poll(&mut self, ctx: &egui::Context) {
+        while let Ok(result) = self.results.try_recv() {
+            self.pending.remove(&result.content_hash);
+            match result.image {
+                Some(image) => self.insert(ctx, result.content_hash, image),
+                None => {
+                    self.failed.insert(result.content_hash);
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, ctx: &egui::Context, content_hash: String, image: egui::ColorImage) {
+        let size_bytes = image.pixels.len() * 4;
+        let texture = ctx.load_texture(
+            format!("thumbnail_{}", content_hash),
+            image,
+            egui::TextureOptions::default(),
+        );
+
+        self.clock += 1;
+        if let Some(old) = self.entries.insert(
+            content_hash,
+            CacheEntry {
+                texture,
+                size_bytes,
+                last_used: self.clock,
+            },
+        ) {
+            self.used_bytes -= old.size_bytes;
+        }
+        self.used_bytes += size_bytes;
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.used_bytes > self.memory_budget && self.entries.len() > 1 {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(hash, _)| hash.clone());
+            match oldest.and_then(|hash| self.entries.remove(&hash)) {
+                Some(entry) => self.used_bytes -= entry.size_bytes,
+                None => break,
+            }
+        }
+    }
+
+    fn load_or_decode(
+        store: Option<&Database>,
+        request: &DecodeRequest,
+    ) -> Option<egui::ColorImage> {
+        // 先查持久化缓存
+        if let Some(store) = store {
+            match store.get_thumbnail(&request.content_hash) {
+                Ok(Some(png)) => {
+                    if let Ok(image) = image::load_from_memory(&png) {
+                        return Some(Self::to_color_image(&image));
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => log::warn!("Failed to read cached thumbnail: {}", e),
+            }
+        }
+
+        let thumbnail = decode_thumbnail(&request.image)?;
+
+        if let Some(store) = store {
+            let mut png = Vec::new();
+            if thumbnail
+                .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+                .is_ok()
+            {
+                if let Err(e) = store.put_thumbnail(&request.content_hash, &png) {
+                    log::warn!("Failed to store thumbnail: {}", e);
+                }
+            }
+        }
+
+        Some(Self::to_color_image(&thumbnail))
+    }
+
+    fn to_color_image(image: &image::DynamicImage) -> egui::ColorImage {
+        let rgba = image.to_rgba8();
+        let size = [rgba.width() as usize, rgba.height() as usize];
+        egui::ColorImage::from_rgba_unmultiplied(size, rgba.as_flat_samples().as_slice())
+    }
+}
+
+/// 将剪切板图片解码并缩放为缩略图
+pub fn decode_thumbnail(image: &ImageData) -> Option<image::DynamicImage> {
+    Some(decode_image(image)?.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE))
+}
+
+/// 解码剪切板图片
+///
+/// 支持编码后的图片（PNG/JPEG/GIF/BMP）以及剪切板直接提供的原始 RGBA 像素。
+pub fn decode_image(image: &ImageData) -> Option<image::DynamicImage> {
+    match image::load_from_memory(&image.data) {
+        Ok(decoded) => Some(decoded),
+        Err(_) => {
+            let raw = image::RgbaImage::from_raw(image.width, image.height, image.data.clone())?;
+            Some(image::DynamicImage::ImageRgba8(raw))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard::types::ImageFormat;
+
+    fn raw_image(width: u32, height: u32) -> ImageData {
+        ImageData::new(
+            vec![128; (width * height * 4) as usize],
+            ImageFormat::Png,
+            width,
+            height,
+        )
+    }
+
+    #[test]
+    fn test_decode_raw_rgba_thumbnail() {
+        let thumbnail = decode_thumbnail(&raw_image(200, 100)).unwrap();
+        assert_eq!(thumbnail.width(), THUMBNAIL_SIZE);
+        assert!(thumbnail.height() <= THUMBNAIL_SIZE);
+    }
+
+    #[test]
+    fn test_full_size_decodes_in_background() {
+        let ctx = egui::Context::default();
+        let mut cache = ThumbnailCache::full_size(ctx.clone(), FULL_SIZE_MEMORY_BUDGET);
+        let image = raw_image(200, 100);
+
+        assert!(cache.get(&ctx, "full", &image).is_none());
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let texture = loop {
+            if let Some(texture) = cache.get(&ctx, "full", &image) {
+                break texture;
+            }
+            assert!(
+                std::time::Instant::now() < deadline,
+                "image was not decoded"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        };
+        assert_eq!(texture.size(), [200, 100]);
+    }
+
+    #[test]
+    fn test_decode_invalid_data() {
+        let image = ImageData::new(vec![1, 2, 3], ImageFormat::Png, 10, 10);
+        assert!(decode_thumbnail(&image).is_none());
+    }
+
+    #[test]
+    fn test_lru_eviction_respects_budget() {
+        let ctx = egui::Context::default();
+        let entry_bytes = 4 * 4 * 4;
+        let mut cache = ThumbnailCache::new(ctx.clone(), None, entry_bytes * 2);
+
+        for hash in ["a", "b"] {
+            cache.insert(
+                &ctx,
+                hash.to_string(),
+                egui::ColorImage::filled([4, 4], egui::Color32::RED),
+            );
+        }
+        // 访问 "a"，使 "b" 成为最久未使用的条目
+        assert!(cache.get(&ctx, "a", &raw_image(4, 4)).is_some());
+        cache.insert(
+            &ctx,
+            "c".to_string(),
+            egui::ColorImage::filled([4, 4], egui::Color32::RED),
+        );
+
+        assert!(cache.used_bytes() <= entry_bytes * 2);
+        assert!(cache.entries.contains_key("a"));
+        assert!(!cache.entries.contains_key("b"));
+        assert!(cache.entries.contains_key("c"));
+    }
+}